@@ -1,10 +1,11 @@
+mod shiori;
 mod types;
 
 extern crate proc_macro;
 use proc_macro::TokenStream;
 use quote::quote;
 use syn::parse::{Parse, ParseStream};
-use syn::{parse_macro_input, LitByteStr, LitInt, Token};
+use syn::{parse_macro_input, ItemFn, LitByteStr, LitInt, Token};
 use types::ByteLiteral;
 
 /// BytesSliceInput is a struct for parsing input of bytes_slice! and bytes_slice_length! macro.
@@ -102,3 +103,43 @@ pub fn bytes_length(input: TokenStream) -> TokenStream {
 
     quote!(#len).into()
 }
+
+/// #[shiori] is an attribute that generates the SHIORI DLL exports `load`, `unload` and `request`.
+///
+/// The attribute is put on an `async fn` handler that takes extractors such as `Context<T>` and `v3::Request`
+/// and returns any `IntoResponse`, or on a `fn() -> S` that builds the service such as `Router<T>`.
+/// The `Adapter` is initialized on the first `load`, and `unload` and `request` fail until then.
+/// Panics are caught at the DLL boundary.
+///
+/// The context type is inferred from the first generic argument of the handler's context or the service,
+/// and it can be specified with `context = T`. `crash_report` enables `Adapter::with_crash_report`.
+///
+/// # Example
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use uka_shiori::runtime::{Context, ContextData};
+/// # use uka_shiori::types::v3;
+/// #
+/// struct Data;
+/// impl ContextData for Data {
+///     type Error = v3::ShioriError;
+///     fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+///         Ok(Self)
+///     }
+/// }
+///
+/// #[uka_macro::shiori(crash_report)]
+/// async fn handler(_ctx: Context<Data>, req: v3::Request) -> v3::Response {
+///     v3::Response::no_content(&req)
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn shiori(args: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as shiori::ShioriArgs);
+    let item = parse_macro_input!(item as ItemFn);
+
+    shiori::expand(args, item)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
@@ -0,0 +1,180 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{FnArg, GenericArgument, Ident, ItemFn, PathArguments, ReturnType, Token, Type};
+
+/// ShioriArgs is a struct for parsing arguments of #[shiori] attribute.
+pub struct ShioriArgs {
+    crash_report: bool,
+    context: Option<Type>,
+}
+
+impl Parse for ShioriArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut args = Self {
+            crash_report: false,
+            context: None,
+        };
+        for arg in Punctuated::<ShioriArg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                ShioriArg::CrashReport => args.crash_report = true,
+                ShioriArg::Context(ty) => args.context = Some(*ty),
+            }
+        }
+        Ok(args)
+    }
+}
+
+enum ShioriArg {
+    CrashReport,
+    Context(Box<Type>),
+}
+
+impl Parse for ShioriArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        match ident.to_string().as_str() {
+            "crash_report" => Ok(Self::CrashReport),
+            "context" => {
+                input.parse::<Token![=]>()?;
+                Ok(Self::Context(Box::new(input.parse()?)))
+            }
+            _ => Err(syn::Error::new(
+                ident.span(),
+                "expected `crash_report` or `context = Type`",
+            )),
+        }
+    }
+}
+
+/// Expand #[shiori] attribute into the function and the SHIORI DLL exports.
+pub fn expand(args: ShioriArgs, item: ItemFn) -> syn::Result<TokenStream> {
+    let name = &item.sig.ident;
+    let (context, service_type, service) = match (&item.sig.asyncness, item.sig.inputs.len()) {
+        // async fn handler(ctx: Context<C>, req: v3::Request) -> impl IntoResponse
+        (Some(_), _) => {
+            let context = match &args.context {
+                Some(context) => context.clone(),
+                None => item
+                    .sig
+                    .inputs
+                    .iter()
+                    .map(input_type)
+                    .find(|ty| last_segment_is(ty, "Context"))
+                    .and_then(first_generic_argument)
+                    .ok_or_else(|| {
+                        syn::Error::new(
+                            item.sig.inputs.span(),
+                            "expected `Context<T>` argument, or specify the context with `#[shiori(context = T)]`",
+                        )
+                    })?,
+            };
+            (
+                context.clone(),
+                quote!(::uka_shiori::runtime::BoxHandlerV3<#context>),
+                quote!(::uka_shiori::runtime::Handler::<#context, _>::into_service(#name)),
+            )
+        }
+        // fn service() -> S
+        (None, 0) => {
+            let ReturnType::Type(_, service_type) = &item.sig.output else {
+                return Err(syn::Error::new(
+                    item.sig.span(),
+                    "the function that builds the service must return it",
+                ));
+            };
+            let context = match &args.context {
+                Some(context) => context.clone(),
+                None => first_generic_argument(service_type).ok_or_else(|| {
+                    syn::Error::new(
+                        service_type.span(),
+                        "cannot infer the context, specify it with `#[shiori(context = T)]`",
+                    )
+                })?,
+            };
+            (context, quote!(#service_type), quote!(#name()))
+        }
+        _ => {
+            return Err(syn::Error::new(
+                item.sig.span(),
+                "expected `async fn` handler or `fn() -> S` that builds the service",
+            ))
+        }
+    };
+
+    let adapter = format_ident!("__UKA_SHIORI_ADAPTER");
+    let crash_report = args.crash_report.then(|| quote!(.with_crash_report()));
+
+    // `unload` and `request` do not construct the adapter, so that they fail without starting a runtime before `load`.
+    Ok(quote! {
+        #item
+
+        #[doc(hidden)]
+        static #adapter: ::std::sync::OnceLock<::uka_shiori::dll::Adapter<#context, #service_type>> =
+            ::std::sync::OnceLock::new();
+
+        #[allow(clippy::missing_safety_doc)]
+        #[no_mangle]
+        pub unsafe extern "C" fn load(h: isize, len: usize) -> bool {
+            ::std::panic::catch_unwind(|| unsafe {
+                #adapter
+                    .get_or_init(|| ::uka_shiori::dll::Adapter::from(#service) #crash_report)
+                    .load(h, len)
+            })
+            .unwrap_or(false)
+        }
+
+        #[no_mangle]
+        pub extern "C" fn unload() -> bool {
+            ::std::panic::catch_unwind(|| match #adapter.get() {
+                Some(adapter) => adapter.unload(),
+                None => false,
+            })
+            .unwrap_or(false)
+        }
+
+        #[allow(clippy::missing_safety_doc)]
+        #[no_mangle]
+        pub unsafe extern "C" fn request(h: isize, len: *mut usize) -> isize {
+            ::std::panic::catch_unwind(|| match #adapter.get() {
+                Some(adapter) => unsafe { adapter.request(h, len) },
+                None => 0,
+            })
+            .unwrap_or(0)
+        }
+    })
+}
+
+fn input_type(arg: &FnArg) -> &Type {
+    match arg {
+        FnArg::Typed(pat) => &pat.ty,
+        FnArg::Receiver(receiver) => &receiver.ty,
+    }
+}
+
+fn last_segment_is(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+/// Returns `T` of `Context<T>`, `Router<T>` and so on.
+fn first_generic_argument(ty: &Type) -> Option<Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let PathArguments::AngleBracketed(args) = &path.path.segments.last()?.arguments else {
+        return None;
+    };
+    args.args.iter().find_map(|arg| match arg {
+        GenericArgument::Type(ty) => Some(ty.clone()),
+        _ => None,
+    })
+}
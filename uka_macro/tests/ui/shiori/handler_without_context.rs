@@ -0,0 +1,6 @@
+#[uka_shiori::shiori]
+async fn handler(req: uka_shiori::types::v3::Request) -> uka_shiori::types::v3::Response {
+    uka_shiori::types::v3::Response::no_content(&req)
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+struct Data;
+
+#[uka_shiori::shiori]
+fn service(_name: &str) -> uka_shiori::runtime::Router<Data> {
+    unimplemented!()
+}
+
+fn main() {}
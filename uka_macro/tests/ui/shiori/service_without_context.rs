@@ -0,0 +1,8 @@
+type Service = uka_shiori::runtime::BoxHandlerV3<()>;
+
+#[uka_shiori::shiori]
+fn service() -> Service {
+    unimplemented!()
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+#[uka_shiori::shiori]
+fn service() {}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[uka_shiori::shiori(panic_report)]
+async fn handler(
+    _ctx: uka_shiori::runtime::Context<()>,
+    req: uka_shiori::types::v3::Request,
+) -> uka_shiori::types::v3::Response {
+    uka_shiori::types::v3::Response::no_content(&req)
+}
+
+fn main() {}
@@ -0,0 +1,199 @@
+use crate::parse::{parse, Error};
+use crate::script::{Scope, Script};
+use crate::token::{Span, Tag, TokenKind};
+use std::fmt;
+use std::fmt::Display;
+
+/// `Ast` is the parsed SakuraScript.
+///
+/// `Ast` is converted back into a script with `to_string`.
+/// The script is written in the canonical form, for example `\h` becomes `\0` and `\s0` becomes `\s[0]`,
+/// so parsing it again results in the same nodes, except for the spans.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::{Ast, NodeKind, Scope};
+/// #
+/// let ast = Ast::parse(r"\h\s0Hello, %username\e").unwrap();
+/// assert_eq!(ast.nodes()[0].kind, NodeKind::Scope(Scope::SAKURA));
+/// assert_eq!(ast.nodes()[1].kind, NodeKind::Surface(0));
+/// assert_eq!(ast.text(), "Hello, ");
+/// assert_eq!(ast.to_string(), r"\0\s[0]Hello, %username\e");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Ast {
+    nodes: Vec<Node>,
+}
+
+impl Ast {
+    /// Parse the script into an Ast.
+    pub fn parse(script: &str) -> Result<Self, Error> {
+        parse(script)
+    }
+
+    pub(crate) fn new(nodes: Vec<Node>) -> Self {
+        Self { nodes }
+    }
+
+    /// Returns the top-level nodes.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Returns all nodes in depth-first order, including the nodes in the anchors.
+    pub fn walk(&self) -> Vec<&Node> {
+        fn walk<'a>(nodes: &'a [Node], buf: &mut Vec<&'a Node>) {
+            for node in nodes {
+                buf.push(node);
+                if let NodeKind::Anchor { children, .. } = &node.kind {
+                    walk(children, buf);
+                }
+            }
+        }
+        let mut buf = Vec::new();
+        walk(&self.nodes, &mut buf);
+        buf
+    }
+
+    /// Returns the plain text that the script displays, without the tags and the variables.
+    pub fn text(&self) -> String {
+        self.walk()
+            .into_iter()
+            .filter_map(|node| match &node.kind {
+                NodeKind::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Display for Ast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.nodes.iter().try_for_each(|node| node.fmt(f))
+    }
+}
+
+impl TryFrom<&Script> for Ast {
+    type Error = Error;
+
+    fn try_from(value: &Script) -> Result<Self, Self::Error> {
+        parse(value.as_str())
+    }
+}
+
+/// Node is an element of [`Ast`], with its span in the script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    pub kind: NodeKind,
+    pub span: Span,
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+/// NodeKind is the kind of the node.
+///
+/// The tags that have no dedicated kind are represented as [`NodeKind::Tag`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeKind {
+    /// Plain text.
+    Text(String),
+    /// Environment variable such as `%username`, without `%`.
+    Variable(String),
+    /// `\0`, `\1`, `\h`, `\u` and `\p[n]`.
+    Scope(Scope),
+    /// `\s[n]`.
+    Surface(i32),
+    /// `\n`.
+    Newline,
+    /// `\q[label,target,r0,...]`.
+    ///
+    /// `target` is the id passed to `OnChoiceSelect`, or the event raised with the references.
+    Choice {
+        label: String,
+        target: Option<String>,
+        references: Vec<String>,
+    },
+    /// `\_a[args]...\_a`.
+    Anchor {
+        args: Vec<String>,
+        children: Vec<Node>,
+    },
+    /// `\![name,args...]`.
+    Command { name: String, args: Vec<String> },
+    /// `\e`.
+    End,
+    /// Any other tag.
+    Tag(Tag),
+}
+
+impl NodeKind {
+    pub(crate) fn from_token(kind: TokenKind) -> Self {
+        match kind {
+            TokenKind::Text(text) => NodeKind::Text(text),
+            TokenKind::Variable(name) => NodeKind::Variable(name),
+            TokenKind::Tag(tag) => Self::from_tag(tag),
+        }
+    }
+
+    fn from_tag(tag: Tag) -> Self {
+        match (tag.name.as_str(), tag.args.as_slice()) {
+            ("0" | "h", []) => NodeKind::Scope(Scope::SAKURA),
+            ("1" | "u", []) => NodeKind::Scope(Scope::KERO),
+            ("p", [id]) => match id.trim().parse() {
+                Ok(id) => NodeKind::Scope(Scope::new(id)),
+                Err(_) => NodeKind::Tag(tag),
+            },
+            ("s", [id]) => match id.trim().parse() {
+                Ok(id) => NodeKind::Surface(id),
+                Err(_) => NodeKind::Tag(tag),
+            },
+            ("n", []) => NodeKind::Newline,
+            ("e", []) => NodeKind::End,
+            ("q", [label, rest @ ..]) if tag.brackets => NodeKind::Choice {
+                label: label.clone(),
+                target: rest.first().cloned(),
+                references: rest.iter().skip(1).cloned().collect(),
+            },
+            ("!", [name, args @ ..]) if tag.brackets => NodeKind::Command {
+                name: name.clone(),
+                args: args.to_vec(),
+            },
+            _ => NodeKind::Tag(tag),
+        }
+    }
+}
+
+impl Display for NodeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NodeKind::Text(text) => TokenKind::Text(text.clone()).fmt(f),
+            NodeKind::Variable(name) => write!(f, "%{name}"),
+            NodeKind::Scope(scope) => scope.fmt(f),
+            NodeKind::Surface(id) => write!(f, r"\s[{id}]"),
+            NodeKind::Newline => f.write_str(r"\n"),
+            NodeKind::Choice {
+                label,
+                target,
+                references,
+            } => {
+                let args = std::iter::once(label).chain(target).chain(references);
+                Tag::new("q", args.cloned()).fmt(f)
+            }
+            NodeKind::Anchor { args, children } => {
+                Tag::new("_a", args.iter().cloned()).fmt(f)?;
+                children.iter().try_for_each(|node| node.fmt(f))?;
+                f.write_str(r"\_a")
+            }
+            NodeKind::Command { name, args } => {
+                Tag::new("!", std::iter::once(name).chain(args).cloned()).fmt(f)
+            }
+            NodeKind::End => f.write_str(r"\e"),
+            NodeKind::Tag(tag) => tag.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,294 @@
+use crate::script::{escape_text, Scope, Script};
+use crate::token::Tag;
+use std::fmt::{Display, Write};
+use std::time::Duration;
+
+/// `Builder` is a builder of [`Script`].
+///
+/// Each method appends a tag or text to the script, escaping the text and the arguments.
+/// The script is finished with [`Builder::end`], which appends `\e`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::time::Duration;
+/// # use uka_sakura::{Scope, Script};
+/// #
+/// let script = Script::builder()
+///     .scope(Scope::SAKURA)
+///     .surface(0)
+///     .text("Hello")
+///     .wait(Duration::from_millis(500))
+///     .scope(Scope::KERO)
+///     .surface(10)
+///     .text("Hi")
+///     .newline()
+///     .choice("Talk", "OnTalk")
+///     .choice("Cancel", "OnCancel")
+///     .end();
+/// assert_eq!(
+///     script.as_str(),
+///     r"\0\s[0]Hello\_w[500]\1\s[10]Hi\n\q[Talk,OnTalk]\q[Cancel,OnCancel]\e"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Builder {
+    buf: String,
+}
+
+impl Builder {
+    /// Constructs an empty builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Switch the scope, `\0`, `\1` or `\p[n]`.
+    pub fn scope(self, scope: Scope) -> Self {
+        self.push(scope)
+    }
+
+    /// Switch the scope to the main character, `\0`.
+    pub fn sakura(self) -> Self {
+        self.scope(Scope::SAKURA)
+    }
+
+    /// Switch the scope to the partner character, `\1`.
+    pub fn kero(self) -> Self {
+        self.scope(Scope::KERO)
+    }
+
+    /// Change the surface of the current scope, `\s[n]`.
+    ///
+    /// `-1` hides the character.
+    pub fn surface(self, id: i32) -> Self {
+        self.push(format_args!(r"\s[{id}]"))
+    }
+
+    /// Play the animation of the current scope, `\i[n]`.
+    pub fn animation(self, id: u32) -> Self {
+        self.push(format_args!(r"\i[{id}]"))
+    }
+
+    /// Append the text, escaping `\` and `%`. Line breaks are converted into `\n`.
+    pub fn text(mut self, text: impl AsRef<str>) -> Self {
+        escape_text(&mut self.buf, text.as_ref());
+        self
+    }
+
+    /// Append the environment variable such as `%username`, which the baseware expands.
+    pub fn variable(self, name: impl AsRef<str>) -> Self {
+        self.push(format_args!("%{}", name.as_ref()))
+    }
+
+    /// Break the line, `\n`.
+    pub fn newline(self) -> Self {
+        self.push(r"\n")
+    }
+
+    /// Break the line by half of the height, `\n[half]`.
+    pub fn half_newline(self) -> Self {
+        self.push(r"\n[half]")
+    }
+
+    /// Clear the balloon of the current scope, `\c`.
+    pub fn clear(self) -> Self {
+        self.push(r"\c")
+    }
+
+    /// Wait for the duration in milliseconds, `\_w[ms]`.
+    pub fn wait(self, duration: Duration) -> Self {
+        self.push(format_args!(r"\_w[{}]", duration.as_millis()))
+    }
+
+    /// Wait until the user clicks the balloon, `\x`.
+    pub fn wait_click(self) -> Self {
+        self.push(r"\x")
+    }
+
+    /// Switch the balloon of the current scope, `\b[n]`.
+    ///
+    /// `-1` hides the balloon.
+    pub fn balloon(self, id: i32) -> Self {
+        self.push(format_args!(r"\b[{id}]"))
+    }
+
+    /// Display the text built by the function at once without waits, `\_q...\_q`.
+    pub fn quick(self, f: impl FnOnce(Self) -> Self) -> Self {
+        f(self.push(r"\_q")).push(r"\_q")
+    }
+
+    /// Enclose the text built by the function as a synchronized section, `\_s...\_s`.
+    ///
+    /// All characters speak the text of the section at the same time.
+    pub fn synchronized(self, f: impl FnOnce(Self) -> Self) -> Self {
+        f(self.push(r"\_s")).push(r"\_s")
+    }
+
+    /// Display the choice that raises `OnChoiceSelect` with the id, `\q[label,id]`.
+    pub fn choice(self, label: impl AsRef<str>, id: impl AsRef<str>) -> Self {
+        self.tag("q", [label.as_ref(), id.as_ref()])
+    }
+
+    /// Display the choice that raises the event with the references, `\q[label,event,r0,...]`.
+    pub fn choice_event<I, S>(
+        self,
+        label: impl AsRef<str>,
+        event: impl AsRef<str>,
+        references: I,
+    ) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let references = references.into_iter().collect::<Vec<_>>();
+        let args = [label.as_ref(), event.as_ref()]
+            .into_iter()
+            .chain(references.iter().map(AsRef::as_ref));
+        self.tag("q", args)
+    }
+
+    /// Display the text as the anchor that raises `OnAnchorSelect` with the id, `\_a[id]text\_a`.
+    pub fn anchor(self, id: impl AsRef<str>, text: impl AsRef<str>) -> Self {
+        self.tag("_a", [id.as_ref()]).text(text).push(r"\_a")
+    }
+
+    /// Raise the event with the references, `\![raise,event,r0,...]`.
+    pub fn raise<I, S>(self, event: impl AsRef<str>, references: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let references = references.into_iter().collect::<Vec<_>>();
+        let args = [event.as_ref()]
+            .into_iter()
+            .chain(references.iter().map(AsRef::as_ref));
+        self.command("raise", args)
+    }
+
+    /// Open the target such as `browser`, `inputbox` or `file` with the arguments, `\![open,target,...]`.
+    pub fn open<I, S>(self, target: impl AsRef<str>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args = args.into_iter().collect::<Vec<_>>();
+        let args = [target.as_ref()]
+            .into_iter()
+            .chain(args.iter().map(AsRef::as_ref));
+        self.command("open", args)
+    }
+
+    /// Execute the command with the arguments, `\![name,...]`.
+    pub fn command<I, S>(self, name: impl AsRef<str>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let args = args.into_iter().collect::<Vec<_>>();
+        let args = [name.as_ref()]
+            .into_iter()
+            .chain(args.iter().map(AsRef::as_ref));
+        self.tag("!", args)
+    }
+
+    /// Append the script as it is, without escaping.
+    ///
+    /// This is the escape hatch for the tags that the builder does not support.
+    pub fn raw(self, script: impl AsRef<str>) -> Self {
+        self.push(script.as_ref())
+    }
+
+    /// Finish the script with `\e`.
+    pub fn end(self) -> Script {
+        self.push(r"\e").build()
+    }
+
+    /// Finish the script without appending `\e`.
+    ///
+    /// This is useful to build the fragment that is embedded into another script.
+    pub fn build(self) -> Script {
+        Script::from_string(self.buf)
+    }
+
+    fn tag<'a>(self, name: &str, args: impl IntoIterator<Item = &'a str>) -> Self {
+        self.push(Tag::new(name, args))
+    }
+
+    fn push(mut self, value: impl Display) -> Self {
+        // writing to String never fails
+        let _ = write!(self.buf, "{value}");
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scopes_and_surfaces() {
+        let script = Builder::new()
+            .sakura()
+            .surface(5)
+            .kero()
+            .surface(-1)
+            .scope(Scope::new(2))
+            .animation(3)
+            .end();
+        assert_eq!(script.as_str(), r"\0\s[5]\1\s[-1]\p[2]\i[3]\e");
+    }
+
+    #[test]
+    fn test_choices_and_anchors() {
+        let script = Builder::new()
+            .choice("Yes, please", "OnYes")
+            .choice_event("Go]", "OnGo", ["a", "b,c"])
+            .anchor("link", "100%")
+            .end();
+        assert_eq!(
+            script.as_str(),
+            r#"\q["Yes, please",OnYes]\q[Go\],OnGo,a,"b,c"]\_a[link]100\%\_a\e"#
+        );
+    }
+
+    #[test]
+    fn test_commands() {
+        let script = Builder::new()
+            .raise("OnTimer", ["1"])
+            .open("file", [r"C:\ghost\readme.txt"])
+            .command("set", ["alpha", "50"])
+            .raise("OnNothing", std::iter::empty::<&str>())
+            .build();
+        assert_eq!(
+            script.as_str(),
+            r"\![raise,OnTimer,1]\![open,file,C:\\ghost\\readme.txt]\![set,alpha,50]\![raise,OnNothing]"
+        );
+    }
+
+    #[test]
+    fn test_balloon_control() {
+        let script = Builder::new()
+            .balloon(2)
+            .quick(|b| b.text("fast").half_newline())
+            .synchronized(|b| b.text("together"))
+            .wait(Duration::from_secs(1))
+            .wait_click()
+            .clear()
+            .balloon(-1)
+            .end();
+        assert_eq!(
+            script.as_str(),
+            r"\b[2]\_qfast\n[half]\_q\_stogether\_s\_w[1000]\x\c\b[-1]\e"
+        );
+    }
+
+    #[test]
+    fn test_variable_and_raw() {
+        let script = Builder::new()
+            .text("Hello, ")
+            .variable("username")
+            .raw(r"\![embed]")
+            .end();
+        assert_eq!(script.as_str(), r"Hello, %username\![embed]\e");
+    }
+}
@@ -0,0 +1,13 @@
+mod ast;
+mod builder;
+mod lint;
+mod parse;
+mod script;
+mod token;
+
+pub use ast::{Ast, Node, NodeKind};
+pub use builder::Builder;
+pub use lint::{Diagnostic, Linter, Rule, Severity};
+pub use parse::{parse, tokenize, Error as ParseError};
+pub use script::{Scope, Script};
+pub use token::{Span, Tag, Token, TokenKind};
@@ -0,0 +1,304 @@
+use crate::ast::{Ast, NodeKind};
+use crate::parse::parse;
+use crate::token::Span;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fmt::Display;
+
+/// Rule is a check that [`Linter`] runs over the script.
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub enum Rule {
+    /// The script cannot be parsed, such as an unterminated `\![`.
+    Syntax,
+    /// The script does not end with `\e`.
+    MissingEnd,
+    /// There are tags or text after `\e`, which the baseware ignores.
+    UnreachableAfterEnd,
+    /// `\s[n]` refers to a surface that is not in [`Linter::surfaces`].
+    UnknownSurface,
+    /// `\q[label]` has no id or event to raise when selected.
+    ChoiceWithoutTarget,
+}
+
+impl Rule {
+    /// All rules in the order they are checked.
+    pub const ALL: [Rule; 5] = [
+        Rule::Syntax,
+        Rule::MissingEnd,
+        Rule::UnreachableAfterEnd,
+        Rule::UnknownSurface,
+        Rule::ChoiceWithoutTarget,
+    ];
+
+    /// Returns the name of the rule in kebab-case, such as `missing-end`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Rule::Syntax => "syntax",
+            Rule::MissingEnd => "missing-end",
+            Rule::UnreachableAfterEnd => "unreachable-after-end",
+            Rule::UnknownSurface => "unknown-surface",
+            Rule::ChoiceWithoutTarget => "choice-without-target",
+        }
+    }
+
+    fn default_severity(&self) -> Severity {
+        match self {
+            Rule::Syntax | Rule::MissingEnd => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Severity is the importance of a [`Diagnostic`].
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+/// Diagnostic is a problem found by [`Linter`], with its span in the script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}[{}]: {} at {}..{}",
+            self.severity, self.rule, self.message, self.span.start, self.span.end
+        )
+    }
+}
+
+/// `Linter` checks SakuraScript for the problems that only show up at runtime in the baseware.
+///
+/// All rules are enabled by default, except [`Rule::UnknownSurface`] that needs the surfaces of the shell.
+/// Rules are disabled with [`Linter::allow`], and their severity is changed with [`Linter::severity`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::{Linter, Rule, Severity};
+/// #
+/// let linter = Linter::new()
+///     .surfaces([0, 1, 10])
+///     .severity(Rule::ChoiceWithoutTarget, Severity::Error);
+///
+/// let diagnostics = linter.lint(r"\0\s[5]Hello\q[Yes]");
+/// let rules = diagnostics.iter().map(|d| d.rule).collect::<Vec<_>>();
+/// assert_eq!(rules, vec![Rule::MissingEnd, Rule::UnknownSurface, Rule::ChoiceWithoutTarget]);
+///
+/// assert!(linter.lint(r"\0\s[10]Hello\q[Yes,OnYes]\e").is_empty());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Linter {
+    rules: BTreeMap<Rule, Severity>,
+    surfaces: Option<BTreeSet<i32>>,
+}
+
+impl Linter {
+    /// Constructs a linter with the default rules.
+    pub fn new() -> Self {
+        Self {
+            rules: Rule::ALL
+                .into_iter()
+                .map(|rule| (rule, rule.default_severity()))
+                .collect(),
+            surfaces: None,
+        }
+    }
+
+    /// Disable the rule.
+    pub fn allow(mut self, rule: Rule) -> Self {
+        self.rules.remove(&rule);
+        self
+    }
+
+    /// Enable the rule with the severity.
+    pub fn severity(mut self, rule: Rule, severity: Severity) -> Self {
+        self.rules.insert(rule, severity);
+        self
+    }
+
+    /// Set the surfaces of the shell, which enables [`Rule::UnknownSurface`].
+    ///
+    /// `\s[-1]`, which hides the character, is always allowed.
+    pub fn surfaces<I>(mut self, surfaces: I) -> Self
+    where
+        I: IntoIterator<Item = i32>,
+    {
+        self.surfaces = Some(surfaces.into_iter().collect());
+        self
+    }
+
+    /// Parse the script and check it.
+    ///
+    /// If the script cannot be parsed, only [`Rule::Syntax`] is reported,
+    /// with the span from the position of the error to the end of the script.
+    pub fn lint(&self, script: &str) -> Vec<Diagnostic> {
+        match parse(script) {
+            Ok(ast) => self.lint_ast(&ast, script.len()),
+            Err(e) => self
+                .diagnostic(
+                    Rule::Syntax,
+                    e.to_string(),
+                    Span::new(e.position(), script.len()),
+                )
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Check the parsed script, where `len` is the length of the script in bytes.
+    pub fn lint_ast(&self, ast: &Ast, len: usize) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let end = ast
+            .nodes()
+            .iter()
+            .position(|node| node.kind == NodeKind::End);
+        match end {
+            None => diagnostics.extend(self.diagnostic(
+                Rule::MissingEnd,
+                r"the script does not end with `\e`".to_string(),
+                Span::new(len, len),
+            )),
+            Some(i) if i + 1 < ast.nodes().len() => {
+                let start = ast.nodes()[i + 1].span.start;
+                diagnostics.extend(self.diagnostic(
+                    Rule::UnreachableAfterEnd,
+                    r"the script after `\e` is ignored".to_string(),
+                    Span::new(start, len),
+                ))
+            }
+            Some(_) => {}
+        }
+
+        for node in ast.walk() {
+            match &node.kind {
+                NodeKind::Surface(id) if *id != -1 => {
+                    let known = self
+                        .surfaces
+                        .as_ref()
+                        .is_none_or(|surfaces| surfaces.contains(id));
+                    if !known {
+                        diagnostics.extend(self.diagnostic(
+                            Rule::UnknownSurface,
+                            format!("surface {id} does not exist"),
+                            node.span,
+                        ));
+                    }
+                }
+                NodeKind::Choice { label, target, .. }
+                    if target.as_deref().is_none_or(|v| v.trim().is_empty()) =>
+                {
+                    diagnostics.extend(self.diagnostic(
+                        Rule::ChoiceWithoutTarget,
+                        format!("choice `{label}` has no id or event to raise"),
+                        node.span,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    fn diagnostic(&self, rule: Rule, message: String, span: Span) -> Option<Diagnostic> {
+        self.rules.get(&rule).map(|severity| Diagnostic {
+            rule,
+            severity: *severity,
+            message,
+            span,
+        })
+    }
+}
+
+impl Default for Linter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn rules(linter: &Linter, script: &str) -> Vec<Rule> {
+        linter.lint(script).into_iter().map(|d| d.rule).collect()
+    }
+
+    #[rstest]
+    #[case(r"\0\s[0]Hello\e", vec![])]
+    #[case(r"\0\s[0]Hello", vec![Rule::MissingEnd])]
+    #[case(r"\0Hello\e\1Hi", vec![Rule::UnreachableAfterEnd])]
+    #[case(r"\0\![raise,OnTest\e", vec![Rule::Syntax])]
+    #[case(r"\0\q[Yes]\q[No, ]\q[Ok,OnOk]\e", vec![Rule::ChoiceWithoutTarget, Rule::ChoiceWithoutTarget])]
+    #[case(r"\0\s[100]\s[-1]\e", vec![])]
+    fn test_default_rules(#[case] script: &str, #[case] expected: Vec<Rule>) {
+        assert_eq!(rules(&Linter::new(), script), expected);
+    }
+
+    #[test]
+    fn test_unknown_surface() {
+        let linter = Linter::new().surfaces([0, 10]);
+        let diagnostics = linter.lint(r"\0\s[0]\_a[OnLink]\s5\_a\1\s[10]\s[-1]\e");
+        assert_eq!(
+            diagnostics,
+            vec![Diagnostic {
+                rule: Rule::UnknownSurface,
+                severity: Severity::Warning,
+                message: "surface 5 does not exist".to_string(),
+                span: Span::new(18, 21),
+            }]
+        );
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "warning[unknown-surface]: surface 5 does not exist at 18..21"
+        );
+    }
+
+    #[test]
+    fn test_configure_rules() {
+        let linter = Linter::new()
+            .allow(Rule::MissingEnd)
+            .severity(Rule::ChoiceWithoutTarget, Severity::Error);
+        let diagnostics = linter.lint(r"\q[Yes]");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::ChoiceWithoutTarget);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        let linter = Linter::new().allow(Rule::Syntax);
+        assert!(linter.lint(r"\![raise").is_empty());
+    }
+
+    #[test]
+    fn test_syntax_error_span() {
+        let script = r"\0\![raise,OnTest";
+        let diagnostics = Linter::new().lint(script);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[0].span, Span::new(2, script.len()));
+    }
+}
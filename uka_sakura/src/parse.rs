@@ -0,0 +1,490 @@
+use crate::ast::{Ast, Node, NodeKind};
+use crate::token::{Span, Tag, Token, TokenKind};
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+/// Error that can occur when parsing SakuraScript.
+///
+/// The positions are byte offsets in the script.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    #[error("unexpected end of script after `\\` at {position}")]
+    IncompleteEscape { position: usize },
+
+    #[error("`\\{name}[` at {position} is not closed with `]`")]
+    UnterminatedBracket { name: String, position: usize },
+
+    #[error("quoted argument at {position} is not closed with `\"`")]
+    UnterminatedQuote { position: usize },
+
+    #[error("anchor `\\_a[` at {position} is not closed with `\\_a`")]
+    UnclosedAnchor { position: usize },
+
+    #[error("`\\_a` at {position} closes no anchor")]
+    UnexpectedAnchorEnd { position: usize },
+}
+
+impl Error {
+    /// Returns the byte offset in the script where the error occurred.
+    pub fn position(&self) -> usize {
+        match self {
+            Error::IncompleteEscape { position }
+            | Error::UnterminatedBracket { position, .. }
+            | Error::UnterminatedQuote { position }
+            | Error::UnclosedAnchor { position }
+            | Error::UnexpectedAnchorEnd { position } => *position,
+        }
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The tags that never take arguments, so that the following `[` is a text.
+const NO_ARGUMENT_TAGS: &[&str] = &[
+    "0", "1", "h", "u", "e", "t", "v", "y", "z", "*", "-", "4", "5", "6", "7", "_q", "_s", "_n",
+    "_V", "_?", "_+",
+];
+
+/// The tags that have the short form with a single digit, such as `\s0` and `\w5`.
+const SHORT_FORM_TAGS: &[&str] = &["s", "w", "b", "p", "i"];
+
+/// The environment variables expanded by the baseware, which are matched in the longest first.
+const VARIABLES: &[&str] = &[
+    "screenheight",
+    "screenwidth",
+    "lastobjectname",
+    "friendname",
+    "selfname2",
+    "wronghour",
+    "selfname",
+    "username",
+    "keroname",
+    "minute",
+    "second",
+    "month",
+    "hour",
+    "year",
+    "day",
+    "exh",
+    "dms",
+    "et",
+    "ms",
+    "mz",
+    "ml",
+    "mc",
+    "mh",
+    "mt",
+    "me",
+    "mp",
+    "m?",
+];
+
+/// Split the script into tokens.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::{tokenize, Span, Tag, TokenKind};
+/// #
+/// let tokens = tokenize(r"\0\s[0]100\% %username").unwrap();
+/// assert_eq!(tokens.len(), 4);
+/// assert_eq!(tokens[1].kind, TokenKind::Tag(Tag::new("s", ["0"])));
+/// assert_eq!(tokens[1].span, Span::new(2, 7));
+/// assert_eq!(tokens[2].kind, TokenKind::Text("100% ".to_string()));
+/// assert_eq!(tokens[3].kind, TokenKind::Variable("username".to_string()));
+/// ```
+pub fn tokenize(script: &str) -> Result<Vec<Token>> {
+    Tokenizer::new(script).collect()
+}
+
+/// Parse the script into an [`Ast`].
+///
+/// The anchors `\_a[...]...\_a` are nested, and the other tokens become the nodes as they are.
+pub fn parse(script: &str) -> Result<Ast> {
+    // the stack of the open anchors and the nodes of the current level
+    let mut stack: Vec<(Tag, usize, Vec<Node>)> = Vec::new();
+    let mut nodes = Vec::new();
+    for token in tokenize(script)? {
+        match token.kind {
+            TokenKind::Tag(tag) if tag.name == "_a" && tag.brackets => {
+                stack.push((tag, token.span.start, std::mem::take(&mut nodes)));
+            }
+            TokenKind::Tag(tag) if tag.name == "_a" => {
+                let (open, start, parent) = stack.pop().ok_or(Error::UnexpectedAnchorEnd {
+                    position: token.span.start,
+                })?;
+                let children = std::mem::replace(&mut nodes, parent);
+                nodes.push(Node {
+                    kind: NodeKind::Anchor {
+                        args: open.args,
+                        children,
+                    },
+                    span: Span::new(start, token.span.end),
+                });
+            }
+            kind => nodes.push(Node {
+                kind: NodeKind::from_token(kind),
+                span: token.span,
+            }),
+        }
+    }
+    match stack.pop() {
+        Some((_, position, _)) => Err(Error::UnclosedAnchor { position }),
+        None => Ok(Ast::new(nodes)),
+    }
+}
+
+struct Tokenizer<'a> {
+    script: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(script: &'a str) -> Self {
+        Self {
+            script,
+            chars: script.char_indices().peekable(),
+        }
+    }
+
+    fn offset(&mut self) -> usize {
+        self.chars
+            .peek()
+            .map(|(i, _)| *i)
+            .unwrap_or(self.script.len())
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>> {
+        let start = self.offset();
+        let kind = match self.chars.peek().map(|(_, c)| *c) {
+            None => return Ok(None),
+            Some('\\') if !self.is_escaped_text(start) => self.read_tag(start)?,
+            Some('%') => match self.variable_at(start + 1) {
+                Some(name) => {
+                    // `%` and the name
+                    for _ in 0..=name.chars().count() {
+                        self.chars.next();
+                    }
+                    TokenKind::Variable(name.to_string())
+                }
+                None => TokenKind::Text(self.read_text()),
+            },
+            Some(_) => TokenKind::Text(self.read_text()),
+        };
+        Ok(Some(Token {
+            kind,
+            span: Span::new(start, self.offset()),
+        }))
+    }
+
+    /// Returns true if the `\` at the position escapes `\` or `%`.
+    fn is_escaped_text(&self, position: usize) -> bool {
+        matches!(self.script[position + 1..].chars().next(), Some('\\' | '%'))
+    }
+
+    fn variable_at(&self, position: usize) -> Option<&'static str> {
+        let rest = &self.script[position..];
+        VARIABLES
+            .iter()
+            .copied()
+            .find(|name| rest.starts_with(name))
+    }
+
+    fn read_text(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(&(i, c)) = self.chars.peek() {
+            match c {
+                '\\' if self.is_escaped_text(i) => {
+                    self.chars.next();
+                    if let Some((_, c)) = self.chars.next() {
+                        text.push(c);
+                    }
+                }
+                '\\' => break,
+                '%' if self.variable_at(i + 1).is_some() => break,
+                c => {
+                    self.chars.next();
+                    text.push(c);
+                }
+            }
+        }
+        text
+    }
+
+    fn read_tag(&mut self, start: usize) -> Result<TokenKind> {
+        // `\`
+        self.chars.next();
+        let mut name = String::new();
+        while let Some((_, '_')) = self.chars.peek() {
+            name.push('_');
+            self.chars.next();
+        }
+        match self.chars.next() {
+            Some((_, c)) => name.push(c),
+            None => return Err(Error::IncompleteEscape { position: start }),
+        }
+
+        let next = self.chars.peek().map(|(_, c)| *c);
+        if SHORT_FORM_TAGS.contains(&name.as_str()) {
+            if let Some(digit) = next.filter(char::is_ascii_digit) {
+                self.chars.next();
+                return Ok(TokenKind::Tag(Tag::short(name, digit)));
+            }
+        }
+        if next == Some('[') && !NO_ARGUMENT_TAGS.contains(&name.as_str()) {
+            self.chars.next();
+            let args = self.read_args(&name, start)?;
+            return Ok(TokenKind::Tag(Tag {
+                name,
+                args,
+                brackets: true,
+            }));
+        }
+        Ok(TokenKind::Tag(Tag {
+            name,
+            args: Vec::new(),
+            brackets: false,
+        }))
+    }
+
+    /// Read the arguments after `[` until `]`.
+    fn read_args(&mut self, name: &str, start: usize) -> Result<Vec<String>> {
+        let unterminated = || Error::UnterminatedBracket {
+            name: name.to_string(),
+            position: start,
+        };
+        let mut args = Vec::new();
+        loop {
+            let mut arg = String::new();
+            if let Some(&(position, '"')) = self.chars.peek() {
+                self.chars.next();
+                self.read_quoted(&mut arg, position)?;
+            }
+            loop {
+                match self.chars.next() {
+                    Some((_, ',')) => break,
+                    Some((_, ']')) => {
+                        args.push(arg);
+                        return Ok(args);
+                    }
+                    Some((_, '\\')) => match self.chars.next() {
+                        Some((_, c @ ('\\' | '%' | ']' | ','))) => arg.push(c),
+                        Some((_, c)) => {
+                            arg.push('\\');
+                            arg.push(c);
+                        }
+                        None => return Err(unterminated()),
+                    },
+                    Some((_, c)) => arg.push(c),
+                    None => return Err(unterminated()),
+                }
+            }
+            args.push(arg);
+        }
+    }
+
+    /// Read the quoted argument after `"` until `"`, where `""` is a `"`.
+    fn read_quoted(&mut self, arg: &mut String, position: usize) -> Result<()> {
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => match self.chars.peek() {
+                    Some((_, '"')) => {
+                        self.chars.next();
+                        arg.push('"');
+                    }
+                    _ => return Ok(()),
+                },
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, c @ ('\\' | '%' | ']' | ','))) => arg.push(c),
+                    Some((_, c)) => {
+                        arg.push('\\');
+                        arg.push(c);
+                    }
+                    None => return Err(Error::UnterminatedQuote { position }),
+                },
+                Some((_, c)) => arg.push(c),
+                None => return Err(Error::UnterminatedQuote { position }),
+            }
+        }
+    }
+}
+
+impl Iterator for Tokenizer<'_> {
+    type Item = Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token().transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::script::{Scope, Script};
+    use rstest::rstest;
+    use std::ops::Range;
+
+    fn kinds(script: &str) -> Vec<NodeKind> {
+        parse(script)
+            .expect("failed to parse")
+            .nodes()
+            .iter()
+            .map(|node| node.kind.clone())
+            .collect()
+    }
+
+    fn tag(name: &str, args: &[&str]) -> NodeKind {
+        NodeKind::Tag(Tag::new(name, args.iter().copied()))
+    }
+
+    #[test]
+    fn test_tokenize_spans() -> Result<()> {
+        let script = r"\h\s[5]こんにちは、%usernameさん\n[half]\e";
+        let tokens = tokenize(script)?;
+        let spans = tokens
+            .iter()
+            .map(|token| &script[Range::from(token.span)])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            vec![
+                r"\h",
+                r"\s[5]",
+                "こんにちは、",
+                "%username",
+                "さん",
+                r"\n[half]",
+                r"\e"
+            ]
+        );
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r"\s0", vec![NodeKind::Surface(0)])]
+    #[case(r"\s10", vec![NodeKind::Surface(1), NodeKind::Text("0".to_string())])]
+    #[case(r"\s[-1]", vec![NodeKind::Surface(-1)])]
+    #[case(r"\s[smile]", vec![tag("s", &["smile"])])]
+    #[case(r"\0\1\h\u\p2\p[3]", vec![
+        NodeKind::Scope(Scope::SAKURA),
+        NodeKind::Scope(Scope::KERO),
+        NodeKind::Scope(Scope::SAKURA),
+        NodeKind::Scope(Scope::KERO),
+        NodeKind::Scope(Scope::new(2)),
+        NodeKind::Scope(Scope::new(3)),
+    ])]
+    #[case(r"\0[note]", vec![NodeKind::Scope(Scope::SAKURA), NodeKind::Text("[note]".to_string())])]
+    #[case(r"\w5\_w[300]\n\n[half]", vec![
+        NodeKind::Tag(Tag::short("w", '5')),
+        tag("_w", &["300"]),
+        NodeKind::Newline,
+        tag("n", &["half"]),
+    ])]
+    #[case(r"C:\\ghost 100\% 100% %m?", vec![
+        NodeKind::Text(r"C:\ghost 100% 100% ".to_string()),
+        NodeKind::Variable("m?".to_string()),
+    ])]
+    #[case(r"%selfname2%selfname", vec![
+        NodeKind::Variable("selfname2".to_string()),
+        NodeKind::Variable("selfname".to_string()),
+    ])]
+    fn test_parse_tags(#[case] script: &str, #[case] expected: Vec<NodeKind>) {
+        assert_eq!(kinds(script), expected);
+    }
+
+    #[test]
+    fn test_parse_choices_and_commands() {
+        assert_eq!(
+            kinds(r#"\q["Yes, please",OnYes]\q[Go,OnGo,a,"b""c"]\q[Label]"#),
+            vec![
+                NodeKind::Choice {
+                    label: "Yes, please".to_string(),
+                    target: Some("OnYes".to_string()),
+                    references: vec![],
+                },
+                NodeKind::Choice {
+                    label: "Go".to_string(),
+                    target: Some("OnGo".to_string()),
+                    references: vec!["a".to_string(), r#"b"c"#.to_string()],
+                },
+                NodeKind::Choice {
+                    label: "Label".to_string(),
+                    target: None,
+                    references: vec![],
+                },
+            ]
+        );
+        assert_eq!(
+            kinds(r"\![raise,OnTimer,1\]2]\![open,file,C:\\ghost\\readme.txt]"),
+            vec![
+                NodeKind::Command {
+                    name: "raise".to_string(),
+                    args: vec!["OnTimer".to_string(), "1]2".to_string()],
+                },
+                NodeKind::Command {
+                    name: "open".to_string(),
+                    args: vec!["file".to_string(), r"C:\ghost\readme.txt".to_string()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_anchor() -> Result<()> {
+        let script = r"\_a[OnLink]Link\_a!";
+        let ast = parse(script)?;
+        assert_eq!(ast.nodes().len(), 2);
+        assert_eq!(ast.nodes()[0].span, Span::new(0, 18));
+        assert_eq!(
+            ast.nodes()[0].kind,
+            NodeKind::Anchor {
+                args: vec!["OnLink".to_string()],
+                children: vec![Node {
+                    kind: NodeKind::Text("Link".to_string()),
+                    span: Span::new(11, 15),
+                }],
+            }
+        );
+        assert_eq!(ast.text(), "Link!");
+        Ok(())
+    }
+
+    #[rstest]
+    #[case(r"abc\", Error::IncompleteEscape { position: 3 })]
+    #[case(r"\0\![raise,OnTest", Error::UnterminatedBracket { name: "!".to_string(), position: 2 })]
+    #[case(r#"\q["label,OnTest]"#, Error::UnterminatedQuote { position: 3 })]
+    #[case(r"\_a[OnLink]Link", Error::UnclosedAnchor { position: 0 })]
+    #[case(r"Link\_a", Error::UnexpectedAnchorEnd { position: 4 })]
+    fn test_parse_errors(#[case] script: &str, #[case] expected: Error) {
+        let error = parse(script).expect_err("must fail");
+        assert_eq!(error.position(), expected.position());
+        assert_eq!(error, expected);
+    }
+
+    #[test]
+    fn test_round_trip() -> Result<()> {
+        let script = Script::builder()
+            .sakura()
+            .surface(0)
+            .text(r"100% C:\ghost [1], ")
+            .variable("username")
+            .newline()
+            .anchor("OnLink", "link")
+            .choice("Yes, please", "OnYes")
+            .choice_event("Go]", "OnGo", ["a", r#"say "hi""#])
+            .raise("OnTimer", ["1"])
+            .kero()
+            .surface(-1)
+            .scope(Scope::new(2))
+            .wait(std::time::Duration::from_millis(100))
+            .end();
+        assert_eq!(parse(script.as_str())?.to_string(), script.as_str());
+
+        // non-canonical scripts are normalized, and the nodes are preserved
+        let script = r#"\h\s0Hello\u\s[10]\q["A",OnA]"#;
+        let normalized = parse(script)?.to_string();
+        assert_eq!(normalized, r"\0\s[0]Hello\1\s[10]\q[A,OnA]");
+        assert_eq!(kinds(&normalized), kinds(script));
+        Ok(())
+    }
+}
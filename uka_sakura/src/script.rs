@@ -0,0 +1,171 @@
+use crate::builder::Builder;
+use std::fmt;
+use std::fmt::Display;
+
+/// `Script` is a SakuraScript string, such as `\0\s[0]Hello\e`.
+///
+/// `Script` is built with [`Builder`], so the text and the arguments of the tags are always escaped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::{Scope, Script};
+/// #
+/// let script = Script::builder()
+///     .scope(Scope::SAKURA)
+///     .surface(0)
+///     .text("100% \\ safe")
+///     .end();
+/// assert_eq!(script.as_str(), r"\0\s[0]100\% \\ safe\e");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Script(String);
+
+impl Script {
+    /// Returns a builder of SakuraScript.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Returns the script as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the script as a string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    pub(crate) fn from_string(script: String) -> Self {
+        Self(script)
+    }
+}
+
+impl Display for Script {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Script {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<Script> for String {
+    fn from(value: Script) -> Self {
+        value.0
+    }
+}
+
+/// Scope represents the character that the following tags and text apply to.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::Scope;
+/// assert_eq!(Scope::SAKURA.to_string(), r"\0");
+/// assert_eq!(Scope::KERO.to_string(), r"\1");
+/// assert_eq!(Scope::new(2).to_string(), r"\p[2]");
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct Scope(u32);
+
+impl Scope {
+    /// `\0`, the main character.
+    pub const SAKURA: Scope = Scope(0);
+
+    /// `\1`, the partner character.
+    pub const KERO: Scope = Scope(1);
+
+    /// Constructs the scope of the character with the id.
+    pub const fn new(id: u32) -> Self {
+        Self(id)
+    }
+
+    /// Returns the id of the character.
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+impl Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            0 => f.write_str(r"\0"),
+            1 => f.write_str(r"\1"),
+            id => write!(f, r"\p[{id}]"),
+        }
+    }
+}
+
+/// Escape the text so that it is displayed as it is.
+///
+/// `\` and `%` are escaped, and line breaks are converted into `\n`.
+pub(crate) fn escape_text(buf: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '\\' => buf.push_str(r"\\"),
+            '%' => buf.push_str(r"\%"),
+            '\n' => buf.push_str(r"\n"),
+            '\r' => {}
+            c => buf.push(c),
+        }
+    }
+}
+
+/// Escape the argument of the tag such as `\q[...]` and `\![...]`.
+///
+/// `\`, `%` and `]` are escaped, and the argument is quoted if it contains `,` or `"`.
+pub(crate) fn escape_argument(buf: &mut String, arg: &str) {
+    let quoted = arg.contains([',', '"']);
+    if quoted {
+        buf.push('"');
+    }
+    for c in arg.chars() {
+        match c {
+            '\\' => buf.push_str(r"\\"),
+            '%' => buf.push_str(r"\%"),
+            ']' => buf.push_str(r"\]"),
+            '"' => buf.push_str(r#""""#),
+            '\r' | '\n' => {}
+            c => buf.push(c),
+        }
+    }
+    if quoted {
+        buf.push('"');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("hello", "hello")]
+    #[case(r"C:\ghost", r"C:\\ghost")]
+    #[case("100%", r"100\%")]
+    #[case("[1]", "[1]")]
+    #[case("line1\r\nline2", r"line1\nline2")]
+    fn test_escape_text(#[case] input: &str, #[case] expected: &str) {
+        let mut buf = String::new();
+        escape_text(&mut buf, input);
+        assert_eq!(buf, expected);
+    }
+
+    #[rstest]
+    #[case("OnEvent", "OnEvent")]
+    #[case(r"C:\ghost", r"C:\\ghost")]
+    #[case("100%", r"100\%")]
+    #[case("[1]", r"[1\]")]
+    #[case("a,b", r#""a,b""#)]
+    #[case(r#"say "hi""#, r#""say ""hi""""#)]
+    fn test_escape_argument(#[case] input: &str, #[case] expected: &str) {
+        let mut buf = String::new();
+        escape_argument(&mut buf, input);
+        assert_eq!(buf, expected);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::script::{escape_argument, escape_text};
+use std::fmt;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// Span is the range of bytes in the script that a token or a node was parsed from.
+#[derive(Debug, PartialEq, Copy, Clone, Eq, Hash)]
+pub struct Span {
+    /// The byte offset of the first character.
+    pub start: usize,
+    /// The byte offset after the last character.
+    pub end: usize,
+}
+
+impl Span {
+    /// Constructs a span from the byte offsets.
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(value: Span) -> Self {
+        value.start..value.end
+    }
+}
+
+/// Token is the smallest unit of SakuraScript, with its span in the script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Span,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.fmt(f)
+    }
+}
+
+/// TokenKind is the kind of the token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    /// Plain text, with the escape sequences such as `\\` and `\%` resolved.
+    Text(String),
+    /// Environment variable such as `%username`, without `%`.
+    Variable(String),
+    /// Tag such as `\0`, `\s[10]` and `\![raise,OnEvent]`.
+    Tag(Tag),
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TokenKind::Text(text) => {
+                let mut buf = String::new();
+                escape_text(&mut buf, text);
+                f.write_str(&buf)
+            }
+            TokenKind::Variable(name) => write!(f, "%{name}"),
+            TokenKind::Tag(tag) => tag.fmt(f),
+        }
+    }
+}
+
+/// Tag is a SakuraScript tag, the name following `\` and its arguments.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_sakura::Tag;
+/// let tag = Tag::new("q", ["Yes, please", "OnYes"]);
+/// assert_eq!(tag.to_string(), r#"\q["Yes, please",OnYes]"#);
+///
+/// let tag = Tag::short("s", '0');
+/// assert_eq!(tag.to_string(), r"\s0");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    /// The name of the tag without `\`, such as `s`, `_w` and `!`.
+    pub name: String,
+    /// The arguments of the tag, with the quotes and the escape sequences resolved.
+    pub args: Vec<String>,
+    /// Whether the arguments are enclosed in brackets.
+    ///
+    /// This is false for the tags without arguments and the short forms such as `\s0` and `\w5`.
+    pub brackets: bool,
+}
+
+impl Tag {
+    /// Constructs a tag with the arguments enclosed in brackets.
+    ///
+    /// If there are no arguments, the tag is written without brackets.
+    pub fn new<I, S>(name: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let args = args.into_iter().map(Into::into).collect::<Vec<_>>();
+        Self {
+            name: name.into(),
+            brackets: !args.is_empty(),
+            args,
+        }
+    }
+
+    /// Constructs a tag in the short form with a single digit, such as `\s0`.
+    pub fn short(name: impl Into<String>, digit: char) -> Self {
+        Self {
+            name: name.into(),
+            args: vec![digit.to_string()],
+            brackets: false,
+        }
+    }
+
+    /// Returns the argument at the index.
+    pub fn arg(&self, index: usize) -> Option<&str> {
+        self.args.get(index).map(String::as_str)
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "\\{}", self.name)?;
+        if !self.brackets {
+            return self.args.iter().try_for_each(|arg| f.write_str(arg));
+        }
+        let mut buf = String::from("[");
+        for (i, arg) in self.args.iter().enumerate() {
+            if i > 0 {
+                buf.push(',');
+            }
+            escape_argument(&mut buf, arg);
+        }
+        buf.push(']');
+        f.write_str(&buf)
+    }
+}
@@ -1,5 +1,6 @@
 mod adapter;
 mod caller;
+mod panic;
 
-pub use adapter::Adapter;
+pub use adapter::{Adapter, Builder as AdapterBuilder, Error as AdapterError};
 pub use caller::{Error as CallerError, ShioriCaller};
@@ -1,11 +1,16 @@
+use crate::dll::panic::{catch_unwind, install_crash_report_hook, CatchUnwind};
+use crate::runtime::layer::error_response;
 use crate::runtime::{ContextData, Service, Shiori};
-use crate::types::v3;
+use crate::types;
+use crate::types::{v2, v3};
 use log::{error, trace};
 use std::alloc::System;
 use std::ffi::OsString;
 use std::future::Future;
-use tokio::runtime;
-use tokio::runtime::Runtime;
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tokio::runtime::{self, Handle, Runtime};
 use uka_util::ptr::{OwnedPtr, RawPtr};
 
 /// Adapter is adapted to SHIORI DLL calls.
@@ -18,7 +23,13 @@ where
     shiori: Shiori<C, S>,
 
     /// The runtime that handles asynchronous tasks.
-    runtime: Runtime,
+    runtime: AdapterRuntime,
+
+    /// Whether to write a crash report into the ghost directory on panic.
+    crash_report: bool,
+
+    /// The directory where the crash report is written, set between `load` and `unload`.
+    crash_report_dir: Mutex<Option<PathBuf>>,
 }
 
 impl<C, S, Fut> Adapter<C, S>
@@ -27,6 +38,17 @@ where
     S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError, Future = Fut>,
     Fut: Future<Output = Result<S::Response, S::Error>>,
 {
+    /// Write a crash report into the ghost directory when a panic occurs.
+    ///
+    /// Panics in handlers and in `ContextData::new` are caught by the adapter regardless of this setting,
+    /// the report is written in addition to the log, as `crash_report.txt` in the directory passed to `load`.
+    /// The panic hook is process-wide and is chained to the previously installed hook,
+    /// but only the panics caught by the adapter between `load` and `unload` are reported.
+    pub fn with_crash_report(mut self) -> Self {
+        self.crash_report = true;
+        self
+    }
+
     /// load is called when the SHIORI DLL is loaded.
     ///
     /// ```clang
@@ -85,17 +107,31 @@ where
             return false;
         }
         let ptr = RawPtr::<[Type]>::from_raw_address_parts(h, len).to_owned::<System>();
-        let s = OsString::from_bytes(ptr.as_slice());
+        let path = PathBuf::from(OsString::from_bytes(ptr.as_slice()));
+        if self.crash_report {
+            install_crash_report_hook();
+            *self
+                .crash_report_dir
+                .lock()
+                .unwrap_or_else(|e| e.into_inner()) = Some(path.clone());
+        }
 
-        match self.runtime.block_on(self.shiori.load(s.into())) {
-            Ok(_) => {
+        match self
+            .runtime
+            .block_on(self.catch_unwind(self.shiori.load(path)))
+        {
+            Ok(Ok(_)) => {
                 trace!("call success Adapter::load({h}, {len}) -> true");
                 true
             }
-            Err(e) => {
+            Ok(Err(e)) => {
                 error!("failed load: {e}");
                 false
             }
+            Err(message) => {
+                error!("failed load: panicked: {message}");
+                false
+            }
         }
     }
 
@@ -134,20 +170,59 @@ where
     /// ```
     pub fn unload(&self) -> bool {
         trace!("call Adapter::unload()");
-        match self.runtime.block_on(self.shiori.unload()) {
-            Ok(_) => {
+        let result = self
+            .runtime
+            .block_on(self.catch_unwind(self.shiori.unload()));
+        self.crash_report_dir
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        match result {
+            Ok(Ok(_)) => {
                 trace!("call success Adapter::unload() -> true");
                 true
             }
-            Err(e) => {
+            Ok(Err(e)) => {
                 error!("failed unload: {e}");
                 false
             }
+            Err(message) => {
+                error!("failed unload: panicked: {message}");
+                false
+            }
+        }
+    }
+
+    /// reload reloads the context data without unloading the SHIORI DLL.
+    ///
+    /// It is not a SHIORI DLL export, but it can be called from outside the handlers,
+    /// e.g. from a thread watching the dictionary files. See `Shiori::reload` for details.
+    /// It must not be called from inside the handlers, use `Context::request_reload` instead.
+    pub fn reload(&self) -> bool {
+        trace!("call Adapter::reload()");
+        match self
+            .runtime
+            .block_on(self.catch_unwind(self.shiori.reload()))
+        {
+            Ok(Ok(_)) => {
+                trace!("call success Adapter::reload() -> true");
+                true
+            }
+            Ok(Err(e)) => {
+                error!("failed reload: {e}");
+                false
+            }
+            Err(message) => {
+                error!("failed reload: panicked: {message}");
+                false
+            }
         }
     }
 
     /// request is called when the SHIORI DLL receives a request.
     ///
+    /// Both SHIORI/2.x and SHIORI/3.0 requests are accepted, and the response is returned in the protocol of the request.
+    ///
     /// ```clang
     /// extern "C" __declspec(dllexport) HGLOBAL __cdecl request(HGLOBAL h, long *len);
     /// function request(h: hglobal; var len: longint): hglobal; cdecl; export;
@@ -200,13 +275,38 @@ where
 
         let len = RawPtr::<usize>::from(len);
         let ptr = RawPtr::<[u8]>::from_raw_address_parts(h, *len.as_ref()).to_owned::<System>();
-        let resp = match v3::Request::parse(ptr.as_slice()) {
-            Ok(request) => self.runtime.block_on(self.shiori.request(request)),
+        let resp = match types::Request::parse(ptr.as_slice()) {
+            Ok(request) => {
+                let v2_version = match &request {
+                    types::Request::V2(request) => Some(request.version()),
+                    types::Request::V3(_) => None,
+                };
+                match self
+                    .runtime
+                    .block_on(self.catch_unwind(self.shiori.dispatch(request)))
+                {
+                    Ok(resp) => resp,
+                    Err(message) => {
+                        error!("failed request: panicked: {message}");
+                        panic_response(v2_version, message)
+                    }
+                }
+            }
+            Err(types::ParseError::V2(e)) => {
+                error!("failed request: {e}");
+                v2::Response::builder()
+                    .version(v2::Version::default())
+                    .status_code(v2::StatusCode::BAD_REQUEST)
+                    .build()
+                    .expect("failed to build error response")
+                    .into()
+            }
             Err(e) => {
                 error!("failed request: {e}");
                 v3::ShioriError::from(e)
                     .with_status_code(v3::StatusCode::BAD_REQUEST)
                     .into_response()
+                    .into()
             }
         };
 
@@ -229,13 +329,9 @@ where
     S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
 {
     fn from(value: Shiori<C, S>) -> Self {
-        Adapter {
-            shiori: value,
-            runtime: runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("failed to create tokio runtime"),
-        }
+        Adapter::builder()
+            .build(value)
+            .expect("failed to create tokio runtime")
     }
 }
 
@@ -245,15 +341,251 @@ where
     S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
 {
     fn from(value: S) -> Self {
-        let shiori = Shiori::from(value);
+        Adapter::builder()
+            .build(Shiori::from(value))
+            .expect("failed to create tokio runtime")
+    }
+}
+
+impl<C, S> Adapter<C, S>
+where
+    C: ContextData,
+{
+    /// Constructs a builder to configure the tokio runtime of the adapter.
+    ///
+    /// By default, the adapter runs a current thread runtime with all drivers enabled,
+    /// which is the same as `Adapter::from`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use std::path::PathBuf;
+    /// # use once_cell::sync::OnceCell;
+    /// # use uka_shiori::dll::Adapter;
+    /// # use uka_shiori::runtime::{box_handler, BoxHandlerV3, Context, ContextData};
+    /// # use uka_shiori::types::v3;
+    /// #
+    /// struct Data;
+    /// impl ContextData for Data {
+    ///     type Error = v3::ShioriError;
+    ///     fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+    ///         Ok(Self)
+    ///     }
+    /// }
+    ///
+    /// static SHIORI: OnceCell<Adapter<Data, BoxHandlerV3<Data>>> = OnceCell::new();
+    ///
+    /// let shiori = SHIORI.get_or_try_init(|| {
+    ///     Adapter::builder()
+    ///         .multi_thread()
+    ///         .worker_threads(2)
+    ///         .thread_name("ghost-worker")
+    ///         .build(box_handler(|_ctx: Context<Data>, _req: v3::Request| async {
+    ///             unimplemented!("your handler")
+    ///         }))
+    /// });
+    /// assert!(shiori.is_ok());
+    /// ```
+    pub fn builder() -> Builder<C, S> {
+        Builder {
+            runtime: RuntimeConfig::CurrentThread,
+            worker_threads: None,
+            thread_name: None,
+            _shiori: PhantomData,
+        }
+    }
+
+    fn new(shiori: Shiori<C, S>, runtime: AdapterRuntime) -> Self {
         Adapter {
             shiori,
-            runtime: runtime::Builder::new_current_thread()
-                .enable_all()
-                .build()
-                .expect("failed to create tokio runtime"),
+            runtime,
+            crash_report: false,
+            crash_report_dir: Mutex::new(None),
         }
     }
+
+    /// Wrap the future to catch panics, writing a crash report if it is enabled and the SHIORI is loaded.
+    fn catch_unwind<F: Future>(&self, future: F) -> CatchUnwind<F> {
+        let dir = self
+            .crash_report_dir
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        catch_unwind(future, dir)
+    }
+}
+
+/// Error that can occur when building the Adapter.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to create tokio runtime: {0}")]
+    FailedCreateRuntime(#[from] std::io::Error),
+
+    #[error("the number of worker threads must be greater than 0")]
+    InvalidWorkerThreads,
+
+    #[error("`{0}` is not applied to {1}")]
+    UnsupportedOption(&'static str, &'static str),
+}
+
+enum RuntimeConfig {
+    CurrentThread,
+    MultiThread,
+    Runtime(Runtime),
+    Handle(Handle),
+}
+
+/// Builder for Adapter.
+///
+/// `worker_threads` is applied only to the multi-threaded runtime, and `thread_name` only when the adapter creates its own runtime.
+/// Setting them with a runtime they are not applied to is an error on `build`.
+pub struct Builder<C, S> {
+    runtime: RuntimeConfig,
+    worker_threads: Option<usize>,
+    thread_name: Option<String>,
+    _shiori: PhantomData<fn() -> (C, S)>,
+}
+
+impl<C, S> Builder<C, S>
+where
+    C: ContextData<Error = S::Error>,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+{
+    /// Use a runtime that runs all tasks on the thread calling the SHIORI DLL (default).
+    pub fn current_thread(self) -> Self {
+        Self {
+            runtime: RuntimeConfig::CurrentThread,
+            ..self
+        }
+    }
+
+    /// Use a multi-threaded runtime, so that spawned tasks keep running between requests.
+    pub fn multi_thread(self) -> Self {
+        Self {
+            runtime: RuntimeConfig::MultiThread,
+            ..self
+        }
+    }
+
+    /// Set the number of worker threads of the multi-threaded runtime.
+    pub fn worker_threads(self, worker_threads: usize) -> Self {
+        Self {
+            worker_threads: Some(worker_threads),
+            ..self
+        }
+    }
+
+    /// Set the name of the threads spawned by the runtime.
+    ///
+    /// With the current thread runtime, it names the threads of `spawn_blocking`.
+    pub fn thread_name(self, thread_name: impl Into<String>) -> Self {
+        Self {
+            thread_name: Some(thread_name.into()),
+            ..self
+        }
+    }
+
+    /// Use the existing runtime, the adapter owns it and drops it with the adapter.
+    pub fn runtime(self, runtime: Runtime) -> Self {
+        Self {
+            runtime: RuntimeConfig::Runtime(runtime),
+            ..self
+        }
+    }
+
+    /// Use the runtime shared with others through the handle.
+    ///
+    /// The handle must belong to a multi-threaded runtime, or the runtime must be driven by another thread,
+    /// because `Handle::block_on` does not drive the IO and timer drivers of a current thread runtime.
+    pub fn handle(self, handle: Handle) -> Self {
+        Self {
+            runtime: RuntimeConfig::Handle(handle),
+            ..self
+        }
+    }
+
+    /// Build the Adapter with the SHIORI runtime or the service.
+    ///
+    /// Returns an error if `worker_threads` is 0, or if `worker_threads` or `thread_name` is set
+    /// with a runtime they are not applied to.
+    pub fn build(self, shiori: impl Into<Shiori<C, S>>) -> Result<Adapter<C, S>, Error> {
+        let unsupported = match self.runtime {
+            RuntimeConfig::Runtime(_) => Some("an existing runtime"),
+            RuntimeConfig::Handle(_) => Some("a shared runtime handle"),
+            RuntimeConfig::CurrentThread | RuntimeConfig::MultiThread => None,
+        };
+        if let Some(runtime) = unsupported {
+            if self.worker_threads.is_some() {
+                return Err(Error::UnsupportedOption("worker_threads", runtime));
+            }
+            if self.thread_name.is_some() {
+                return Err(Error::UnsupportedOption("thread_name", runtime));
+            }
+        }
+        match (&self.runtime, self.worker_threads) {
+            (_, Some(0)) => return Err(Error::InvalidWorkerThreads),
+            (RuntimeConfig::CurrentThread, Some(_)) => {
+                return Err(Error::UnsupportedOption(
+                    "worker_threads",
+                    "the current thread runtime",
+                ))
+            }
+            _ => {}
+        }
+
+        let mut builder = match self.runtime {
+            RuntimeConfig::Runtime(runtime) => {
+                return Ok(Adapter::new(shiori.into(), AdapterRuntime::Owned(runtime)))
+            }
+            RuntimeConfig::Handle(handle) => {
+                return Ok(Adapter::new(shiori.into(), AdapterRuntime::Shared(handle)))
+            }
+            RuntimeConfig::CurrentThread => runtime::Builder::new_current_thread(),
+            RuntimeConfig::MultiThread => runtime::Builder::new_multi_thread(),
+        };
+        builder.enable_all();
+        if let Some(worker_threads) = self.worker_threads {
+            builder.worker_threads(worker_threads);
+        }
+        if let Some(thread_name) = self.thread_name {
+            builder.thread_name(thread_name);
+        }
+
+        Ok(Adapter::new(
+            shiori.into(),
+            AdapterRuntime::Owned(builder.build()?),
+        ))
+    }
+}
+
+/// The runtime that the adapter owns or shares.
+enum AdapterRuntime {
+    Owned(Runtime),
+    Shared(Handle),
+}
+
+impl AdapterRuntime {
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        match self {
+            AdapterRuntime::Owned(runtime) => runtime.block_on(future),
+            AdapterRuntime::Shared(handle) => handle.block_on(future),
+        }
+    }
+}
+
+/// Build `500 Internal Server Error` response for the request whose handler panicked.
+///
+/// SHIORI/3.0 responses report the panic message with `ErrorLevel` and `ErrorDescription` headers.
+fn panic_response(v2_version: Option<v2::Version>, message: String) -> types::Response {
+    match v2_version {
+        Some(version) => v2::Response::builder()
+            .version(version)
+            .status_code(v2::StatusCode::INTERNAL_SERVER_ERROR)
+            .build()
+            .expect("failed to build error response")
+            .into(),
+        None => error_response(v3::ShioriError::new(format!("panicked: {message}"))).into(),
+    }
 }
 
 trait BytesExt {
@@ -358,6 +690,27 @@ mod tests {
         assert!(res);
     }
 
+    #[test]
+    fn test_adapter_reload() {
+        let adapter = Adapter::from(handler(|_ctx: Context<Data>, _req: v3::Request| async {
+            unimplemented!()
+        }));
+
+        let res = adapter.reload();
+        assert!(!res);
+
+        let path = std::env::temp_dir();
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let res = adapter.reload();
+        assert!(res);
+
+        let res = adapter.unload();
+        assert!(res);
+    }
+
     #[test]
     fn test_adapter_request() {
         let adapter = Adapter::from(handler(
@@ -442,4 +795,334 @@ mod tests {
         assert_eq!(res.version(), v3::Version::SHIORI_30);
         assert_eq!(res.status_code(), v3::StatusCode::BAD_REQUEST);
     }
+
+    #[test]
+    fn test_adapter_request_v2() {
+        let adapter = Adapter::from(handler(
+            |_ctx: Context<Data>, req: v3::Request| async move {
+                assert_eq!(req.method(), v3::Method::GET);
+                assert_eq!(req.version(), v3::Version::SHIORI_30);
+
+                Ok(v3::Response::builder()
+                    .status_code(v3::StatusCode::OK)
+                    .version(v3::Version::SHIORI_30)
+                    .header(v3::HeaderName::VALUE, "hello")
+                    .build()
+                    .expect("failed to build response"))
+            },
+        ));
+
+        let path = std::env::temp_dir();
+        let path = path.join("マルチバイトディレクトリ");
+        let _ = fs::create_dir(path.clone());
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let req = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_22)
+            .header(v2::HeaderName::EVENT, "OnBoot")
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v2::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.version(), v2::Version::SHIORI_22);
+        assert_eq!(res.status_code(), v2::StatusCode::OK);
+        assert_eq!(
+            res.sentence().and_then(|v| v.text().ok()).as_deref(),
+            Some("hello")
+        );
+    }
+
+    struct PanicData;
+    impl ContextData for PanicData {
+        type Error = v3::ShioriError;
+        fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+            panic!("failed to read dictionary")
+        }
+    }
+
+    #[test]
+    fn test_adapter_load_with_panic() {
+        let adapter = Adapter::from(handler(
+            |_ctx: Context<PanicData>, _req: v3::Request| async { unimplemented!() },
+        ));
+
+        let path = std::env::temp_dir();
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(!res);
+    }
+
+    #[test]
+    fn test_adapter_request_with_panic() {
+        let adapter = Adapter::from(handler(
+            |_ctx: Context<Data>, req: v3::Request| async move {
+                if req.id().is_some() {
+                    panic!("handler panicked");
+                }
+                Ok(v3::Response::builder()
+                    .status_code(v3::StatusCode::OK)
+                    .version(v3::Version::SHIORI_30)
+                    .build()
+                    .expect("failed to build response"))
+            },
+        ));
+
+        let path = std::env::temp_dir();
+        let path = path.join("マルチバイトディレクトリ");
+        let _ = fs::create_dir(path.clone());
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .header(v3::HeaderName::ID, "OnBoot")
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v3::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.status_code(), v3::StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(
+            res.error_descriptions()
+                .expect("failed to decode ErrorDescription"),
+            vec!["panicked: handler panicked".to_string()]
+        );
+
+        // the adapter keeps working after the panic
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v3::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.status_code(), v3::StatusCode::OK);
+    }
+
+    #[test]
+    fn test_adapter_request_v2_with_panic() {
+        let adapter = Adapter::from(handler(|_ctx: Context<Data>, _req: v3::Request| async {
+            unimplemented!()
+        }));
+
+        let path = std::env::temp_dir();
+        let path = path.join("マルチバイトディレクトリ");
+        let _ = fs::create_dir(path.clone());
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let req = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_22)
+            .header(v2::HeaderName::EVENT, "OnBoot")
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v2::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.version(), v2::Version::SHIORI_22);
+        assert_eq!(res.status_code(), v2::StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    #[test]
+    fn test_adapter_with_crash_report() {
+        let adapter = Adapter::from(handler(|_ctx: Context<Data>, _req: v3::Request| async {
+            unimplemented!()
+        }))
+        .with_crash_report();
+
+        let path = std::env::temp_dir().join("uka_shiori_crash_report");
+        let _ = fs::create_dir(path.clone());
+        let report = path.join(crate::dll::panic::CRASH_REPORT_FILE_NAME);
+        let _ = fs::remove_file(&report);
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        // panics outside the adapter are not reported
+        let _ = std::panic::catch_unwind(|| panic!("unrelated panic"));
+        assert!(!report.exists());
+
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let _ =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let content = fs::read_to_string(&report).expect("failed to read crash report");
+        assert!(content.contains("location: "), "{content}");
+        assert!(content.contains("backtrace:"), "{content}");
+
+        // panics after unload are not reported
+        assert!(adapter.unload());
+        assert!(adapter.crash_report_dir.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_adapter_builder_multi_thread() {
+        let adapter = Adapter::builder()
+            .multi_thread()
+            .worker_threads(1)
+            .thread_name("ghost-worker")
+            .build(handler(|_ctx: Context<Data>, _req: v3::Request| async {
+                let name =
+                    tokio::spawn(async { std::thread::current().name().map(|v| v.to_string()) })
+                        .await
+                        .expect("failed to join task");
+                assert_eq!(name.as_deref(), Some("ghost-worker"));
+
+                Ok(v3::Response::builder()
+                    .status_code(v3::StatusCode::OK)
+                    .version(v3::Version::SHIORI_30)
+                    .build()
+                    .expect("failed to build response"))
+            }))
+            .expect("failed to build adapter");
+
+        let path = std::env::temp_dir();
+        let path = path.join("マルチバイトディレクトリ");
+        let _ = fs::create_dir(path.clone());
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v3::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.status_code(), v3::StatusCode::OK);
+    }
+
+    #[test]
+    fn test_adapter_builder_with_invalid_options() {
+        use crate::runtime::{box_handler, BoxHandlerV3};
+
+        fn build(
+            builder: Builder<Data, BoxHandlerV3<Data>>,
+        ) -> Result<Adapter<Data, BoxHandlerV3<Data>>, Error> {
+            builder.build(box_handler(
+                |_ctx: Context<Data>, _req: v3::Request| async { unimplemented!() },
+            ))
+        }
+        let runtime = || {
+            runtime::Builder::new_current_thread()
+                .build()
+                .expect("failed to create tokio runtime")
+        };
+
+        assert!(matches!(
+            build(Adapter::builder().multi_thread().worker_threads(0)),
+            Err(Error::InvalidWorkerThreads)
+        ));
+        assert!(matches!(
+            build(Adapter::builder().current_thread().worker_threads(2)),
+            Err(Error::UnsupportedOption("worker_threads", _))
+        ));
+        assert!(matches!(
+            build(Adapter::builder().runtime(runtime()).worker_threads(2)),
+            Err(Error::UnsupportedOption("worker_threads", _))
+        ));
+        assert!(matches!(
+            build(Adapter::builder().runtime(runtime()).thread_name("ghost")),
+            Err(Error::UnsupportedOption("thread_name", _))
+        ));
+        assert!(matches!(
+            build(
+                Adapter::builder()
+                    .handle(runtime().handle().clone())
+                    .thread_name("ghost")
+            ),
+            Err(Error::UnsupportedOption("thread_name", _))
+        ));
+        assert!(build(Adapter::builder().current_thread().thread_name("ghost")).is_ok());
+    }
+
+    #[test]
+    fn test_adapter_builder_with_handle() {
+        let runtime = runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .enable_all()
+            .build()
+            .expect("failed to create tokio runtime");
+        let adapter = Adapter::builder()
+            .handle(runtime.handle().clone())
+            .build(handler(|_ctx: Context<Data>, _req: v3::Request| async {
+                tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                Ok(v3::Response::builder()
+                    .status_code(v3::StatusCode::OK)
+                    .version(v3::Version::SHIORI_30)
+                    .build()
+                    .expect("failed to build response"))
+            }))
+            .expect("failed to build adapter");
+
+        let path = std::env::temp_dir();
+        let path = path.join("マルチバイトディレクトリ");
+        let _ = fs::create_dir(path.clone());
+
+        let bytes = ManuallyDrop::new(path.into_os_string().to_vec());
+        let res = unsafe { adapter.load(bytes.as_ptr() as isize, bytes.len()) };
+        assert!(res);
+
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()
+            .expect("failed to build request");
+        let bytes = ManuallyDrop::new(req.to_vec());
+        let len = bytes.len();
+        let h =
+            unsafe { adapter.request(bytes.as_ptr() as isize, &len as *const usize as *mut usize) };
+
+        let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+        let bytes = unsafe { ptr.as_slice() };
+        let res = v3::Response::parse(bytes).expect("failed to parse response");
+        assert_eq!(res.status_code(), v3::StatusCode::OK);
+    }
 }
@@ -0,0 +1,113 @@
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::RefCell;
+use std::fs;
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Once;
+use std::task::{Context, Poll};
+use std::time::SystemTime;
+
+/// The file name of the crash report written into the ghost directory.
+pub(crate) const CRASH_REPORT_FILE_NAME: &str = "crash_report.txt";
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    /// The directory where the crash report is written, set only while `CatchUnwind` polls its future on this thread.
+    static CRASH_REPORT_DIR: RefCell<Option<PathBuf>> = const { RefCell::new(None) };
+}
+
+/// `CatchUnwind<F>` is a future that catches panics while polling the inner future.
+pub(crate) struct CatchUnwind<F> {
+    inner: Pin<Box<F>>,
+    crash_report_dir: Option<PathBuf>,
+}
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, String>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let previous = CRASH_REPORT_DIR.with(|dir| dir.replace(this.crash_report_dir.clone()));
+        let inner = this.inner.as_mut();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| inner.poll(cx)));
+        CRASH_REPORT_DIR.with(|dir| *dir.borrow_mut() = previous);
+
+        match result {
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(panic_message(payload.as_ref()))),
+        }
+    }
+}
+
+/// Wrap the future so that a panic is returned as `Err` with the panic message instead of unwinding.
+///
+/// If `crash_report_dir` is set, a panic caught while polling the future is also written as a crash report into the directory.
+pub(crate) fn catch_unwind<F: Future>(
+    future: F,
+    crash_report_dir: Option<PathBuf>,
+) -> CatchUnwind<F> {
+    CatchUnwind {
+        inner: Box::pin(future),
+        crash_report_dir,
+    }
+}
+
+/// Extract the message from the panic payload.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Install the panic hook that writes crash reports.
+///
+/// The panic hook is installed only once per process and chained to the previous hook.
+/// It writes a report only for the panics caught by `CatchUnwind` with a crash report directory,
+/// so panics elsewhere in the host process are passed to the previous hook untouched.
+pub(crate) fn install_crash_report_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            write_crash_report(info);
+            previous(info);
+        }));
+    });
+}
+
+fn write_crash_report(info: &PanicHookInfo<'_>) {
+    let Some(dir) = CRASH_REPORT_DIR
+        .try_with(|dir| dir.try_borrow().ok().and_then(|dir| dir.clone()))
+        .ok()
+        .flatten()
+    else {
+        return;
+    };
+
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let thread = std::thread::current();
+    let location = info
+        .location()
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "unknown location".to_string());
+    let report = format!(
+        "time: {timestamp}\nthread: {}\nlocation: {location}\nmessage: {}\n\nbacktrace:\n{}\n",
+        thread.name().unwrap_or("<unnamed>"),
+        panic_message(info.payload()),
+        Backtrace::force_capture(),
+    );
+
+    // The panic hook must not panic, so the failure to write the report is ignored.
+    let _ = fs::write(dir.join(CRASH_REPORT_FILE_NAME), report);
+}
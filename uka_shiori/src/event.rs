@@ -0,0 +1,519 @@
+//! Typed model of the well-known SHIORI events.
+//!
+//! SHIORI/3.0 requests carry the event name in the `ID` header and the event arguments in `ReferenceN` headers.
+//! [`Event`] maps the documented events into a typed enum, so that handlers do not have to decode the references by position.
+//!
+//! # Examples
+//!
+//! ```rust
+//! # use uka_shiori::event::{Event, Mouse};
+//! # use uka_shiori::types::v3::{HeaderName, Method, Request, Version};
+//! #
+//! let request = Request::builder()
+//!     .method(Method::GET)
+//!     .version(Version::SHIORI_30)
+//!     .header(HeaderName::ID, "OnMouseDoubleClick")
+//!     .reference(0, "120")
+//!     .reference(1, "240")
+//!     .reference(2, "0")
+//!     .reference(3, "0")
+//!     .reference(4, "Head")
+//!     .reference(5, "0")
+//!     .build()
+//!     .unwrap();
+//! match Event::try_from(&request).unwrap() {
+//!     Event::OnMouseDoubleClick(Mouse { scope, collision, .. }) => {
+//!         assert_eq!(scope, 0);
+//!         assert_eq!(collision, "Head");
+//!     }
+//!     _ => unreachable!(),
+//! }
+//! ```
+use crate::types::v3::{HeaderName, HeaderValueError, Request};
+use std::str::FromStr;
+
+/// Error that can occur when converting a request into an event.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("`ID` header not found")]
+    MissingId,
+
+    #[error("`Reference{0}` header not found")]
+    MissingReference(usize),
+
+    #[error("{1} in `{0}` header")]
+    FailedDecode(HeaderName, #[source] HeaderValueError),
+
+    #[error("invalid value in `Reference{0}` header: {1:?}")]
+    InvalidReference(usize, String),
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Event is a SHIORI event identified by the `ID` header.
+///
+/// Events that are not modeled here are converted to [`Event::Unknown`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Event {
+    /// OnBoot: the ghost is started.
+    OnBoot(Boot),
+    /// OnFirstBoot: the ghost is started for the first time.
+    OnFirstBoot(FirstBoot),
+    /// OnClose: the ghost is closed.
+    OnClose(Close),
+    /// OnGhostChanged: the ghost is switched from another ghost.
+    OnGhostChanged(GhostChanged),
+    /// OnShellChanged: the shell is changed.
+    OnShellChanged(ShellChanged),
+    /// OnSurfaceChange: the surfaces are changed.
+    OnSurfaceChange(SurfaceChange),
+    /// OnSecondChange: raised every second.
+    OnSecondChange(TimeChange),
+    /// OnMinuteChange: raised every minute.
+    OnMinuteChange(TimeChange),
+    /// OnMouseClick: the ghost is clicked.
+    OnMouseClick(Mouse),
+    /// OnMouseDoubleClick: the ghost is double-clicked.
+    OnMouseDoubleClick(Mouse),
+    /// OnMouseMove: the mouse cursor is moved on the ghost.
+    OnMouseMove(Mouse),
+    /// OnChoiceSelect: a choice is selected.
+    OnChoiceSelect(ChoiceSelect),
+    /// OnCommunicate: the ghost is spoken to by the user or another ghost.
+    OnCommunicate(Communicate),
+    /// OnKeyPress: a key is pressed while the ghost is active.
+    OnKeyPress(KeyPress),
+    /// OnNotifyOSInfo: the information of the OS is notified.
+    OnNotifyOSInfo(OsInfo),
+    /// OnTranslate: the script is about to be displayed and can be translated.
+    OnTranslate(Translate),
+    /// Any other event.
+    Unknown {
+        /// The value of the `ID` header.
+        id: String,
+    },
+}
+
+impl Event {
+    /// Returns the event name, which is the value of the `ID` header.
+    pub fn id(&self) -> &str {
+        match self {
+            Event::OnBoot(_) => "OnBoot",
+            Event::OnFirstBoot(_) => "OnFirstBoot",
+            Event::OnClose(_) => "OnClose",
+            Event::OnGhostChanged(_) => "OnGhostChanged",
+            Event::OnShellChanged(_) => "OnShellChanged",
+            Event::OnSurfaceChange(_) => "OnSurfaceChange",
+            Event::OnSecondChange(_) => "OnSecondChange",
+            Event::OnMinuteChange(_) => "OnMinuteChange",
+            Event::OnMouseClick(_) => "OnMouseClick",
+            Event::OnMouseDoubleClick(_) => "OnMouseDoubleClick",
+            Event::OnMouseMove(_) => "OnMouseMove",
+            Event::OnChoiceSelect(_) => "OnChoiceSelect",
+            Event::OnCommunicate(_) => "OnCommunicate",
+            Event::OnKeyPress(_) => "OnKeyPress",
+            Event::OnNotifyOSInfo(_) => "OnNotifyOSInfo",
+            Event::OnTranslate(_) => "OnTranslate",
+            Event::Unknown { id } => id,
+        }
+    }
+}
+
+impl TryFrom<&Request> for Event {
+    type Error = Error;
+
+    fn try_from(request: &Request) -> Result<Self> {
+        let id = request
+            .id()
+            .ok_or(Error::MissingId)?
+            .text_with_charset(request.charset())
+            .map_err(|e| Error::FailedDecode(HeaderName::ID, e))?;
+        let r = References(request);
+
+        Ok(match id.as_str() {
+            "OnBoot" => Event::OnBoot(Boot {
+                shell_name: r.required(0)?,
+                halted_ghost: match r.optional(6)?.as_deref() {
+                    Some("halt") => r.optional(7)?,
+                    _ => None,
+                },
+            }),
+            "OnFirstBoot" => Event::OnFirstBoot(FirstBoot {
+                vanish_count: r.parse(0)?.unwrap_or_default(),
+            }),
+            "OnClose" => Event::OnClose(Close {
+                reason: r.optional(0)?,
+            }),
+            "OnGhostChanged" => Event::OnGhostChanged(GhostChanged {
+                previous_ghost: r.required(0)?,
+                previous_script: r.optional(1)?,
+            }),
+            "OnShellChanged" => Event::OnShellChanged(ShellChanged {
+                shell_name: r.required(0)?,
+            }),
+            "OnSurfaceChange" => Event::OnSurfaceChange(SurfaceChange {
+                sakura_surface: r.parse_required(0)?,
+                kero_surface: r.parse_required(1)?,
+            }),
+            "OnSecondChange" => Event::OnSecondChange(TimeChange::from_references(&r)?),
+            "OnMinuteChange" => Event::OnMinuteChange(TimeChange::from_references(&r)?),
+            "OnMouseClick" => Event::OnMouseClick(Mouse::from_references(&r)?),
+            "OnMouseDoubleClick" => Event::OnMouseDoubleClick(Mouse::from_references(&r)?),
+            "OnMouseMove" => Event::OnMouseMove(Mouse::from_references(&r)?),
+            "OnChoiceSelect" => Event::OnChoiceSelect(ChoiceSelect {
+                choice_id: r.required(0)?,
+            }),
+            "OnCommunicate" => Event::OnCommunicate(Communicate {
+                sender: r.required(0)?,
+                sentence: r.optional(1)?.unwrap_or_default(),
+                extra: r.rest(2)?,
+            }),
+            "OnKeyPress" => Event::OnKeyPress(KeyPress {
+                key: r.required(0)?,
+                key_code: r.parse(1)?,
+            }),
+            "OnNotifyOSInfo" => Event::OnNotifyOSInfo(OsInfo {
+                os_name: r.required(0)?,
+                os_version: r.required(1)?,
+                cpu_name: r.optional(2)?,
+                cpu_clock: r.parse(3)?,
+                cpu_features: r.optional(4)?,
+            }),
+            "OnTranslate" => Event::OnTranslate(Translate {
+                script: r.optional(0)?.unwrap_or_default(),
+            }),
+            _ => Event::Unknown { id },
+        })
+    }
+}
+
+/// Arguments of `OnBoot`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Boot {
+    /// Reference0: the name of the shell.
+    pub shell_name: String,
+    /// Reference7: the name of the ghost that was halted, if Reference6 is `halt`.
+    pub halted_ghost: Option<String>,
+}
+
+/// Arguments of `OnFirstBoot`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FirstBoot {
+    /// Reference0: the number of times the ghost has been vanished.
+    pub vanish_count: u32,
+}
+
+/// Arguments of `OnClose`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Close {
+    /// Reference0: the reason of the close, e.g. `user` or `system`.
+    pub reason: Option<String>,
+}
+
+/// Arguments of `OnGhostChanged`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GhostChanged {
+    /// Reference0: the name of the previous ghost.
+    pub previous_ghost: String,
+    /// Reference1: the script that the previous ghost spoke last.
+    pub previous_script: Option<String>,
+}
+
+/// Arguments of `OnShellChanged`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShellChanged {
+    /// Reference0: the name of the new shell.
+    pub shell_name: String,
+}
+
+/// Arguments of `OnSurfaceChange`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SurfaceChange {
+    /// Reference0: the surface ID of the sakura side.
+    pub sakura_surface: i32,
+    /// Reference1: the surface ID of the kero side.
+    pub kero_surface: i32,
+}
+
+/// Arguments of `OnSecondChange` and `OnMinuteChange`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeChange {
+    /// Reference0: the continuous uptime of the OS in hours.
+    pub uptime_hours: u32,
+    /// Reference1: whether the ghost is partially off the screen.
+    pub is_off_screen: bool,
+    /// Reference2: whether the characters overlap each other.
+    pub is_overlapped: bool,
+    /// Reference3: whether the ghost can talk now.
+    pub can_talk: bool,
+    /// Reference4: the idle time of the user in seconds.
+    pub idle_seconds: Option<u32>,
+}
+
+impl TimeChange {
+    fn from_references(r: &References) -> Result<Self> {
+        Ok(TimeChange {
+            uptime_hours: r.parse(0)?.unwrap_or_default(),
+            is_off_screen: r.flag(1)?,
+            is_overlapped: r.flag(2)?,
+            can_talk: r.flag(3)?,
+            idle_seconds: r.parse(4)?,
+        })
+    }
+}
+
+/// Arguments of `OnMouseClick`, `OnMouseDoubleClick` and `OnMouseMove`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mouse {
+    /// Reference0: the x coordinate relative to the surface.
+    pub x: i32,
+    /// Reference1: the y coordinate relative to the surface.
+    pub y: i32,
+    /// Reference2: the amount of the wheel rotation.
+    pub wheel: i32,
+    /// Reference3: the scope of the character, `0` for sakura and `1` for kero.
+    pub scope: usize,
+    /// Reference4: the name of the collision area, or empty if none.
+    pub collision: String,
+    /// Reference5: the button, `0` for left, `1` for right and `2` for middle.
+    pub button: Option<u32>,
+    /// Reference6: the input device, e.g. `mouse` or `touch`.
+    pub device: Option<String>,
+}
+
+impl Mouse {
+    fn from_references(r: &References) -> Result<Self> {
+        Ok(Mouse {
+            x: r.parse_required(0)?,
+            y: r.parse_required(1)?,
+            wheel: r.parse(2)?.unwrap_or_default(),
+            scope: r.parse_required(3)?,
+            collision: r.optional(4)?.unwrap_or_default(),
+            button: r.parse(5)?,
+            device: r.optional(6)?,
+        })
+    }
+}
+
+/// Arguments of `OnChoiceSelect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChoiceSelect {
+    /// Reference0: the ID of the selected choice.
+    pub choice_id: String,
+}
+
+/// Arguments of `OnCommunicate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Communicate {
+    /// Reference0: the name of the speaker, `user` if the user speaks.
+    pub sender: String,
+    /// Reference1: the sentence.
+    pub sentence: String,
+    /// Reference2 and later: the additional information.
+    pub extra: Vec<String>,
+}
+
+/// Arguments of `OnKeyPress`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyPress {
+    /// Reference0: the name of the key.
+    pub key: String,
+    /// Reference1: the virtual key code.
+    pub key_code: Option<u32>,
+}
+
+/// Arguments of `OnNotifyOSInfo`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OsInfo {
+    /// Reference0: the name of the OS.
+    pub os_name: String,
+    /// Reference1: the version of the OS.
+    pub os_version: String,
+    /// Reference2: the name of the CPU.
+    pub cpu_name: Option<String>,
+    /// Reference3: the clock of the CPU in MHz.
+    pub cpu_clock: Option<u32>,
+    /// Reference4: the features of the CPU.
+    pub cpu_features: Option<String>,
+}
+
+/// Arguments of `OnTranslate`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Translate {
+    /// Reference0: the script to be translated.
+    pub script: String,
+}
+
+/// Accessor that decodes the ReferenceN headers of a request with its charset.
+struct References<'a>(&'a Request);
+
+impl References<'_> {
+    fn optional(&self, n: usize) -> Result<Option<String>> {
+        self.0
+            .reference(n)
+            .map(|v| {
+                v.text_with_charset(self.0.charset())
+                    .map_err(|e| Error::FailedDecode(HeaderName::reference(n), e))
+            })
+            .transpose()
+    }
+
+    fn required(&self, n: usize) -> Result<String> {
+        self.optional(n)?.ok_or(Error::MissingReference(n))
+    }
+
+    fn parse<T: FromStr>(&self, n: usize) -> Result<Option<T>> {
+        self.optional(n)?
+            .filter(|v| !v.trim().is_empty())
+            .map(|v| {
+                v.trim()
+                    .parse::<T>()
+                    .map_err(|_| Error::InvalidReference(n, v))
+            })
+            .transpose()
+    }
+
+    fn parse_required<T: FromStr>(&self, n: usize) -> Result<T> {
+        self.parse(n)?.ok_or(Error::MissingReference(n))
+    }
+
+    fn flag(&self, n: usize) -> Result<bool> {
+        Ok(self.parse::<u32>(n)?.is_some_and(|v| v != 0))
+    }
+
+    fn rest(&self, from: usize) -> Result<Vec<String>> {
+        self.0
+            .references()
+            .filter(|(n, _)| *n >= from)
+            .map(|(n, v)| {
+                v.text_with_charset(self.0.charset())
+                    .map_err(|e| Error::FailedDecode(HeaderName::reference(n), e))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::v3::{Charset, Method, RequestBuilder, Version};
+
+    fn builder(id: &str) -> RequestBuilder {
+        Request::builder()
+            .method(Method::GET)
+            .version(Version::SHIORI_30)
+            .header(HeaderName::ID, id)
+    }
+
+    #[test]
+    fn test_event_on_boot() -> anyhow::Result<()> {
+        let request = builder("OnBoot")
+            .reference(0, "master")
+            .reference(6, "halt")
+            .reference(7, "Emily")
+            .build()?;
+        assert_eq!(
+            Event::try_from(&request)?,
+            Event::OnBoot(Boot {
+                shell_name: "master".to_string(),
+                halted_ghost: Some("Emily".to_string()),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_on_second_change() -> anyhow::Result<()> {
+        let request = builder("OnSecondChange")
+            .reference(0, "12")
+            .reference(1, "0")
+            .reference(2, "1")
+            .reference(3, "1")
+            .reference(4, "30")
+            .build()?;
+        let event = Event::try_from(&request)?;
+        assert_eq!(event.id(), "OnSecondChange");
+        assert_eq!(
+            event,
+            Event::OnSecondChange(TimeChange {
+                uptime_hours: 12,
+                is_off_screen: false,
+                is_overlapped: true,
+                can_talk: true,
+                idle_seconds: Some(30),
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_on_communicate_with_charset() -> anyhow::Result<()> {
+        let request = builder("OnCommunicate")
+            .charset(Charset::UTF8)
+            .reference(0, "user")
+            .reference(1, "こんにちは")
+            .reference(3, "extra2")
+            .reference(2, "extra1")
+            .build()?;
+        assert_eq!(
+            Event::try_from(&request)?,
+            Event::OnCommunicate(Communicate {
+                sender: "user".to_string(),
+                sentence: "こんにちは".to_string(),
+                extra: vec!["extra1".to_string(), "extra2".to_string()],
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_unknown() -> anyhow::Result<()> {
+        let request = builder("OnSomethingNew").reference(0, "foo").build()?;
+        let event = Event::try_from(&request)?;
+        assert_eq!(
+            event,
+            Event::Unknown {
+                id: "OnSomethingNew".to_string()
+            }
+        );
+        assert_eq!(event.id(), "OnSomethingNew");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_failed_missing_id() -> anyhow::Result<()> {
+        let request = Request::builder()
+            .method(Method::GET)
+            .version(Version::SHIORI_30)
+            .build()?;
+        assert!(matches!(Event::try_from(&request), Err(Error::MissingId)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_event_failed_invalid_reference() -> anyhow::Result<()> {
+        let request = builder("OnMouseClick")
+            .reference(0, "left")
+            .reference(1, "10")
+            .reference(3, "0")
+            .build()?;
+        assert!(matches!(
+            Event::try_from(&request),
+            Err(Error::InvalidReference(0, v)) if v == "left"
+        ));
+
+        let request = builder("OnShellChanged").build()?;
+        assert!(matches!(
+            Event::try_from(&request),
+            Err(Error::MissingReference(0))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,3 +1,6 @@
 pub mod dll;
+pub mod event;
 pub mod runtime;
 pub mod types;
+
+pub use uka_macro::shiori;
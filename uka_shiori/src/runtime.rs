@@ -1,9 +1,32 @@
+mod compat;
 mod context;
+pub mod extract;
+pub mod layer;
+mod resource;
+mod router;
 mod service;
 mod shiori;
+mod state;
+pub mod store;
+mod task;
+#[cfg(test)]
+pub(crate) mod test_support;
+#[cfg(feature = "tower")]
+mod tower;
 
 pub use context::{Context, ContextData};
+pub use extract::{FromRequest, Handler};
+pub use layer::{Layer, ServiceBuilder};
+pub use resource::{ResourceValue, Resources, Site};
+pub use router::Router;
 pub use service::{
-    box_handler, handler, BoxAsyncFn, BoxHandler, BoxHandlerV3, Service, ShioriHandler,
+    box_handler, handler, BoxAsyncFn, BoxHandler, BoxHandlerV2, BoxHandlerV3, Service,
+    ShioriHandler,
 };
 pub use shiori::Shiori;
+pub use state::{Slot, StateMap};
+pub use store::Store;
+pub use task::Tasks;
+pub use tokio_util::sync::CancellationToken;
+#[cfg(feature = "tower")]
+pub use tower::{FromTower, TowerService};
@@ -0,0 +1,384 @@
+use crate::types::{v2, v3};
+use v3::IntoResponse;
+
+/// Convert a SHIORI/2.x request into the equivalent SHIORI/3.0 request.
+///
+/// The SHIORI/2.x commands are mapped to the IDs of SHIORI/3.0 as follows:
+///
+/// | SHIORI/2.x                          | SHIORI/3.0                                       |
+/// |-------------------------------------|--------------------------------------------------|
+/// | `GET Version`                       | `GET` `ID: version`                              |
+/// | `GET Sentence` with `Event`         | `GET` `ID: <Event>`                              |
+/// | `GET Sentence` with `Sentence`      | `GET` `ID: OnCommunicate` (Reference0: Sender, Reference1: Sentence) |
+/// | `GET Sentence`                      | `GET` `ID: OnAITalk`                             |
+/// | `GET String`                        | `GET` `ID: <ID>`                                 |
+/// | `TEACH`                             | `GET` `ID: OnTeach` (Reference0: Word)           |
+/// | `TRANSLATE Sentence`                | `GET` `ID: OnTranslate` (Reference0: Sentence)   |
+/// | `NOTIFY OwnerGhostName`             | `NOTIFY` `ID: ownerghostname` (Reference0: Ghost) |
+/// | `NOTIFY OtherGhostName`             | `NOTIFY` `ID: otherghostname` (ReferenceN: Ghost) |
+///
+/// `GET Word` and `GET Status` have no equivalent in SHIORI/3.0, so `None` is returned.
+pub(crate) fn to_v3_request(request: v2::Request) -> Option<v3::Request> {
+    let v2::Request {
+        method,
+        headers,
+        charset,
+        ..
+    } = request;
+
+    let (v3_method, id, consumed, references) = match method {
+        v2::Method::GET_VERSION => (v3::Method::GET, b"version".to_vec(), vec![], vec![]),
+        v2::Method::GET_SENTENCE => match (
+            headers.get(&v2::HeaderName::SENTENCE),
+            headers.get(&v2::HeaderName::EVENT),
+        ) {
+            (Some(sentence), _) => (
+                v3::Method::GET,
+                b"OnCommunicate".to_vec(),
+                vec![v2::HeaderName::SENTENCE],
+                vec![
+                    headers
+                        .get(&v2::HeaderName::SENDER)
+                        .map(|v| v.as_bytes())
+                        .unwrap_or_default(),
+                    sentence.as_bytes(),
+                ],
+            ),
+            (None, Some(event)) => (
+                v3::Method::GET,
+                event.as_bytes(),
+                vec![v2::HeaderName::EVENT],
+                vec![],
+            ),
+            (None, None) => (v3::Method::GET, b"OnAITalk".to_vec(), vec![], vec![]),
+        },
+        v2::Method::GET_STRING => (
+            v3::Method::GET,
+            headers
+                .get(&v2::HeaderName::ID)
+                .map(|v| v.as_bytes())
+                .unwrap_or_default(),
+            vec![v2::HeaderName::ID],
+            vec![],
+        ),
+        v2::Method::TEACH => (
+            v3::Method::GET,
+            b"OnTeach".to_vec(),
+            vec![v2::HeaderName::WORD],
+            headers
+                .get(&v2::HeaderName::WORD)
+                .map(|v| vec![v.as_bytes()])
+                .unwrap_or_default(),
+        ),
+        v2::Method::TRANSLATE_SENTENCE => (
+            v3::Method::GET,
+            b"OnTranslate".to_vec(),
+            vec![v2::HeaderName::SENTENCE],
+            headers
+                .get(&v2::HeaderName::SENTENCE)
+                .map(|v| vec![v.as_bytes()])
+                .unwrap_or_default(),
+        ),
+        v2::Method::NOTIFY_OWNER_GHOST_NAME => (
+            v3::Method::NOTIFY,
+            b"ownerghostname".to_vec(),
+            vec![v2::HeaderName::GHOST],
+            headers
+                .get(&v2::HeaderName::GHOST)
+                .map(|v| vec![v.as_bytes()])
+                .unwrap_or_default(),
+        ),
+        v2::Method::NOTIFY_OTHER_GHOST_NAME => (
+            v3::Method::NOTIFY,
+            b"otherghostname".to_vec(),
+            vec![v2::HeaderName::GHOST],
+            headers
+                .get_all(&v2::HeaderName::GHOST)
+                .into_iter()
+                .map(|v| v.as_bytes())
+                .collect(),
+        ),
+        _ => return None,
+    };
+
+    let mut map = v3::HeaderMap::new();
+    map.insert(v3::HeaderName::ID, id.into());
+    // the synthesized references override the original ones at the same index
+    let overridden = references.len();
+    for (i, reference) in references.into_iter().enumerate() {
+        map.insert(v3::HeaderName::reference(i), reference.into());
+    }
+    for (name, value) in headers {
+        if consumed.contains(&name) || name.reference_index().is_some_and(|i| i < overridden) {
+            continue;
+        }
+        match v3::HeaderName::from_bytes(&name.to_vec()) {
+            Ok(name) => map.insert(name, value),
+            Err(e) => log::warn!(
+                "skipped the header `{name}` that cannot be converted to SHIORI/3.0: {e}"
+            ),
+        }
+    }
+
+    Some(v3::Request {
+        method: v3_method,
+        version: v3::Version::SHIORI_30,
+        headers: map,
+        charset,
+    })
+}
+
+/// Convert a SHIORI/3.0 response into the SHIORI/2.x response for the `method` of the original request.
+///
+/// `Value` is renamed to the header that carries the result of the command
+/// (`Sentence` for `GET Sentence`, `TEACH` and `TRANSLATE Sentence`, `String` for `GET String`),
+/// and `Reference0` of `GET Sentence` is renamed to `To`.
+pub(crate) fn to_v2_response(
+    method: v2::Method,
+    version: v2::Version,
+    response: v3::Response,
+) -> v2::Response {
+    let status_code = to_v2_status_code(response.status_code());
+    let v3::Response {
+        status_code: v3_status_code,
+        reason_phrase,
+        headers,
+        charset,
+        ..
+    } = response;
+    // the custom reason phrase is kept only if the status code is passed through as it is
+    let reason_phrase = reason_phrase.filter(|_| v3_status_code.as_u16() == status_code.as_u16());
+
+    let value_name = match method {
+        v2::Method::GET_SENTENCE | v2::Method::TEACH | v2::Method::TRANSLATE_SENTENCE => {
+            v2::HeaderName::SENTENCE
+        }
+        v2::Method::GET_STRING => v2::HeaderName::STRING,
+        _ => v2::HeaderName::VALUE,
+    };
+
+    let headers = headers
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let name = if name == v3::HeaderName::VALUE {
+                value_name.clone()
+            } else if name == v3::HeaderName::REFERENCE0 && method == v2::Method::GET_SENTENCE {
+                v2::HeaderName::TO
+            } else {
+                match v2::HeaderName::from_bytes(&name.to_vec()) {
+                    Ok(name) => name,
+                    Err(e) => {
+                        log::warn!(
+                            "skipped the header `{name}` that cannot be converted to SHIORI/2.x: {e}"
+                        );
+                        return None;
+                    }
+                }
+            };
+            Some((name, value))
+        })
+        .collect::<v2::HeaderMap>();
+
+    v2::Response {
+        version,
+        status_code,
+        reason_phrase,
+        headers,
+        charset,
+    }
+}
+
+/// Convert an error into the SHIORI/2.x response.
+pub(crate) fn to_v2_error_response(version: v2::Version, error: v3::ShioriError) -> v2::Response {
+    let response = error.into_response();
+    v2::Response::builder()
+        .version(version)
+        .status_code(to_v2_status_code(response.status_code()))
+        .build()
+        .expect("failed to build error response")
+}
+
+/// Returns the SHIORI/2.x response for commands that the SHIORI does not handle.
+pub(crate) fn no_content(version: v2::Version) -> v2::Response {
+    v2::Response::builder()
+        .version(version)
+        .status_code(v2::StatusCode::NO_CONTENT)
+        .build()
+        .expect("failed to build no content response")
+}
+
+/// `310 Communicate` is not defined in SHIORI/2.x, so it is mapped to `200 OK`.
+/// The other status codes are passed through as they are.
+fn to_v2_status_code(status_code: v3::StatusCode) -> v2::StatusCode {
+    match status_code {
+        v3::StatusCode::COMMUNICATE => v2::StatusCode::OK,
+        _ => v2::StatusCode::from_u16(status_code.as_u16())
+            .unwrap_or(v2::StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_v3_request_get_sentence_with_event() -> anyhow::Result<()> {
+        let request = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_22)
+            .header(v2::HeaderName::SENDER, "SSP")
+            .header(v2::HeaderName::EVENT, "OnMinuteChange")
+            .header(v2::HeaderName::REFERENCE0, "1")
+            .build()?;
+        let request = to_v3_request(request).expect("convertible request");
+
+        assert_eq!(request.method(), v3::Method::GET);
+        assert_eq!(request.version(), v3::Version::SHIORI_30);
+        assert_eq!(
+            request.id().map(|v| v.text()).transpose()?.as_deref(),
+            Some("OnMinuteChange")
+        );
+        assert_eq!(
+            request.sender().map(|v| v.text()).transpose()?.as_deref(),
+            Some("SSP")
+        );
+        assert_eq!(
+            request
+                .reference0()
+                .map(|v| v.text())
+                .transpose()?
+                .as_deref(),
+            Some("1")
+        );
+        assert!(request
+            .headers()
+            .get(&v3::HeaderName::from_static("Event")?)
+            .is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_v3_request_communicate() -> anyhow::Result<()> {
+        let request = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_23)
+            .header(v2::HeaderName::SENDER, "Sakura")
+            .header(v2::HeaderName::SENTENCE, "hello")
+            .build()?;
+        let request = to_v3_request(request).expect("convertible request");
+
+        assert_eq!(
+            request.id().map(|v| v.text()).transpose()?.as_deref(),
+            Some("OnCommunicate")
+        );
+        assert_eq!(
+            request
+                .reference0()
+                .map(|v| v.text())
+                .transpose()?
+                .as_deref(),
+            Some("Sakura")
+        );
+        assert_eq!(
+            request
+                .reference1()
+                .map(|v| v.text())
+                .transpose()?
+                .as_deref(),
+            Some("hello")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_v3_request_communicate_overrides_references() -> anyhow::Result<()> {
+        let request = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_23)
+            .header(v2::HeaderName::SENDER, "Sakura")
+            .header(v2::HeaderName::SENTENCE, "hello")
+            .header(v2::HeaderName::REFERENCE0, "original0")
+            .header(v2::HeaderName::REFERENCE1, "original1")
+            .header(v2::HeaderName::REFERENCE2, "original2")
+            .build()?;
+        let request = to_v3_request(request).expect("convertible request");
+
+        let texts = |name: &v3::HeaderName| -> anyhow::Result<Vec<String>> {
+            Ok(request
+                .headers()
+                .get_all(name)
+                .into_iter()
+                .map(|v| v.text())
+                .collect::<Result<_, _>>()?)
+        };
+        assert_eq!(texts(&v3::HeaderName::REFERENCE0)?, ["Sakura"]);
+        assert_eq!(texts(&v3::HeaderName::REFERENCE1)?, ["hello"]);
+        assert_eq!(texts(&v3::HeaderName::REFERENCE2)?, ["original2"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_v3_request_get_word_is_not_convertible() -> anyhow::Result<()> {
+        let request = v2::Request::builder()
+            .method(v2::Method::GET_WORD)
+            .version(v2::Version::SHIORI_20)
+            .header(v2::HeaderName::TYPE, "\\ms")
+            .build()?;
+        assert!(to_v3_request(request).is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_v2_response_get_sentence() -> anyhow::Result<()> {
+        let response = v3::Response::builder()
+            .version(v3::Version::SHIORI_30)
+            .status_code(v3::StatusCode::OK)
+            .header(v3::HeaderName::VALUE, "\\h\\s0hoge\\e")
+            .header(v3::HeaderName::REFERENCE0, "Sakura")
+            .build()?;
+        let response = to_v2_response(v2::Method::GET_SENTENCE, v2::Version::SHIORI_23, response);
+
+        assert_eq!(response.version(), v2::Version::SHIORI_23);
+        assert_eq!(response.status_code(), v2::StatusCode::OK);
+        assert_eq!(
+            response
+                .sentence()
+                .map(|v| v.text())
+                .transpose()?
+                .as_deref(),
+            Some("\\h\\s0hoge\\e")
+        );
+        assert_eq!(
+            response.to().map(|v| v.text()).transpose()?.as_deref(),
+            Some("Sakura")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_v2_response_passes_through_unknown_status_code() -> anyhow::Result<()> {
+        let response = v3::Response::builder()
+            .version(v3::Version::SHIORI_30)
+            .status_code(v3::StatusCode::from_u16(599)?)
+            .reason_phrase("Dictionary Broken")
+            .build()?;
+        let response = to_v2_response(v2::Method::GET_SENTENCE, v2::Version::SHIORI_26, response);
+        assert_eq!(response.status_code().as_u16(), 599);
+        assert_eq!(response.reason_phrase(), Some("Dictionary Broken"));
+
+        let response = v3::Response::builder()
+            .version(v3::Version::SHIORI_30)
+            .status_code(v3::StatusCode::COMMUNICATE)
+            .build()?;
+        let response = to_v2_response(v2::Method::GET_SENTENCE, v2::Version::SHIORI_26, response);
+        assert_eq!(response.status_code(), v2::StatusCode::OK);
+        assert_eq!(response.reason_phrase(), Some("OK"));
+
+        Ok(())
+    }
+}
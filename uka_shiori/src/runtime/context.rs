@@ -1,5 +1,9 @@
+use crate::runtime::state::StateMap;
+use crate::runtime::task::Tasks;
+use std::future::Future;
 use std::ops::Deref;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// The `ContextData` trait represents shared data that is accessible during the handling of each request.
@@ -57,6 +61,22 @@ pub trait ContextData: Sized + Send + Sync {
     /// The argument `path` is passed as the ghost directory path.
     /// The ghost directory path in the case of `path` is the directory where the DLL files are located.
     fn new(path: PathBuf) -> Result<Self, Self::Error>;
+
+    /// Constructs a new instance asynchronously when the SHIORI is loaded.
+    ///
+    /// Override this to await async IO while loading, e.g. reading dictionaries with `tokio::fs`.
+    /// By default, it calls `new`.
+    fn init(path: PathBuf) -> impl Future<Output = Result<Self, Self::Error>> {
+        async move { Self::new(path) }
+    }
+
+    /// Tear down the instance asynchronously when the SHIORI is unloaded.
+    ///
+    /// It is called after the background tasks have finished or have been aborted,
+    /// so it is the place to flush the state asynchronously. By default, it does nothing.
+    fn shutdown(&self) -> impl Future<Output = Result<(), Self::Error>> {
+        async { Ok(()) }
+    }
 }
 
 /// `Context<T>` is the wrapper for data of type `T` that implements the `ContextData` trait.
@@ -66,20 +86,69 @@ pub trait ContextData: Sized + Send + Sync {
 /// access to shared data during request processing.
 pub struct Context<T: ContextData> {
     inner: Arc<T>,
+    tasks: Tasks,
+    state: StateMap,
+    reload: Arc<AtomicBool>,
 }
 
 impl<T: ContextData> Context<T> {
+    /// Constructs a new context.
+    ///
+    /// The background tasks of the context are spawned on the runtime of the caller.
+    /// The context created by `Shiori::load` spawns them on the dedicated driver thread instead.
     pub fn new(value: T) -> Self {
+        Self::with_parts(
+            value,
+            Tasks::current(),
+            StateMap::new(),
+            Arc::new(AtomicBool::new(false)),
+        )
+    }
+
+    pub(crate) fn with_parts(
+        value: T,
+        tasks: Tasks,
+        state: StateMap,
+        reload: Arc<AtomicBool>,
+    ) -> Self {
         Self {
             inner: Arc::new(value),
+            tasks,
+            state,
+            reload,
         }
     }
+
+    /// Returns the spawner of the background tasks that keep running between requests.
+    pub fn tasks(&self) -> &Tasks {
+        &self.tasks
+    }
+
+    /// Returns the mutable state shared between requests.
+    ///
+    /// Unlike the context data, the state is kept when the context data is reloaded.
+    pub fn state(&self) -> &StateMap {
+        &self.state
+    }
+
+    /// Request `Shiori` to reload the context data.
+    ///
+    /// The reload does not happen immediately, because the request in progress holds the context.
+    /// It is performed by `Shiori::reload` after the response of the request in progress,
+    /// or before the next request when it is requested from a background task, e.g. a file watcher.
+    /// It has no effect on a context that is not created by `Shiori::load`.
+    pub fn request_reload(&self) {
+        self.reload.store(true, Ordering::Release);
+    }
 }
 
 impl<T: ContextData> Clone for Context<T> {
     fn clone(&self) -> Self {
         Context {
             inner: self.inner.clone(),
+            tasks: self.tasks.clone(),
+            state: self.state.clone(),
+            reload: self.reload.clone(),
         }
     }
 }
@@ -0,0 +1,521 @@
+use crate::event::{
+    Boot, ChoiceSelect, Close, Communicate, Event, FirstBoot, GhostChanged, KeyPress, Mouse,
+    OsInfo, ShellChanged, SurfaceChange, TimeChange, Translate,
+};
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::service::{box_handler, BoxHandlerV3};
+use crate::types::v3;
+use std::any::type_name;
+use std::future::Future;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// `FromRequest<C>` extracts a handler argument from the context and the request.
+///
+/// Handlers registered to [`Router`](crate::runtime::Router) can take any combination of the types
+/// implementing this trait as arguments, instead of `(Context<C>, v3::Request)`.
+/// When the extraction fails, the handler is not called and the error is answered.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use uka_shiori::runtime::{Context, ContextData, Router, Service};
+/// # use uka_shiori::runtime::extract::{Id, Reference};
+/// # use uka_shiori::types::v3;
+/// #
+/// # struct Data;
+/// # impl ContextData for Data {
+/// #     type Error = v3::ShioriError;
+/// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+/// # }
+/// async fn on_communicate(
+///     Id(id): Id,
+///     Reference(sender): Reference<0>,
+///     level: v3::SecurityLevel,
+/// ) -> Result<v3::Response, v3::ShioriError> {
+///     assert_eq!(id, "OnCommunicate");
+///     assert_eq!(sender, "user");
+///     assert_eq!(level, v3::SecurityLevel::LOCAL);
+///     v3::Response::builder()
+///         .version(v3::Version::SHIORI_30)
+///         .status_code(v3::StatusCode::OK)
+///         .build()
+///         .map_err(v3::ShioriError::from)
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), v3::ShioriError> {
+/// let router = Router::<Data>::new().route("OnCommunicate", on_communicate);
+///
+/// let request = v3::Request::builder()
+///     .method(v3::Method::GET)
+///     .version(v3::Version::SHIORI_30)
+///     .header(v3::HeaderName::ID, "OnCommunicate")
+///     .reference(0, "user")
+///     .security_level(v3::SecurityLevel::LOCAL)
+///     .build()?;
+/// let response = router.call(Context::from(Data), request).await?;
+/// assert_eq!(response.status_code(), v3::StatusCode::OK);
+/// # Ok(())
+/// # }
+/// ```
+pub trait FromRequest<C: ContextData>: Sized {
+    /// Extract the value from the context and the request.
+    fn from_request(
+        context: &Context<C>,
+        request: &v3::Request,
+    ) -> impl Future<Output = Result<Self, v3::ShioriError>>;
+}
+
+/// `Handler<C, Args>` is an async function whose arguments are extracted with [`FromRequest`].
+///
+/// It is implemented for the functions that take up to 8 extractors and
+/// return a future of any [`v3::IntoResponse`], such as `Result<v3::Response, v3::ShioriError>`,
+/// `String` or `Option<String>`. The `ShioriError` returned by the function is passed to the layers.
+/// `(Context<C>, v3::Request)` are extractors too, so the plain handlers are also `Handler`.
+pub trait Handler<C: ContextData, Args> {
+    /// Convert the function into a boxed handler that extracts the arguments for each request.
+    fn into_service(self) -> BoxHandlerV3<C>;
+}
+
+macro_rules! impl_handler {
+    ($($ty:ident),*) => {
+        impl<C, F, Fut, R, $($ty,)*> Handler<C, ($($ty,)*)> for F
+        where
+            C: ContextData + 'static,
+            F: Fn($($ty,)*) -> Fut + Send + Sync + 'static,
+            Fut: Future<Output = R> + 'static,
+            R: v3::IntoResponse,
+            $($ty: FromRequest<C> + 'static,)*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn into_service(self) -> BoxHandlerV3<C> {
+                let f = Arc::new(self);
+                box_handler(move |context: Context<C>, request: v3::Request| {
+                    let f = f.clone();
+                    async move {
+                        $(let $ty = $ty::from_request(&context, &request).await?;)*
+                        v3::IntoResponse::into_result(f($($ty,)*).await)
+                    }
+                })
+            }
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(T1);
+impl_handler!(T1, T2);
+impl_handler!(T1, T2, T3);
+impl_handler!(T1, T2, T3, T4);
+impl_handler!(T1, T2, T3, T4, T5);
+impl_handler!(T1, T2, T3, T4, T5, T6);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7);
+impl_handler!(T1, T2, T3, T4, T5, T6, T7, T8);
+
+fn bad_request(e: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> v3::ShioriError {
+    v3::ShioriError::new(e).with_status_code(v3::StatusCode::BAD_REQUEST)
+}
+
+/// Decode the header with the charset of the request.
+fn header_text(request: &v3::Request, name: v3::HeaderName) -> Result<String, v3::ShioriError> {
+    match request.headers().get(&name) {
+        Some(value) => value
+            .text_with_charset(request.charset())
+            .map_err(|e| bad_request(format!("{e} in `{name}` header"))),
+        None => Err(bad_request(format!("`{name}` header not found"))),
+    }
+}
+
+impl<C: ContextData> FromRequest<C> for Context<C> {
+    async fn from_request(
+        context: &Context<C>,
+        _request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        Ok(context.clone())
+    }
+}
+
+impl<C: ContextData> FromRequest<C> for v3::Request {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        Ok(request.clone())
+    }
+}
+
+/// Extracts `None` instead of failing when the inner extractor fails.
+impl<C: ContextData, T: FromRequest<C>> FromRequest<C> for Option<T> {
+    async fn from_request(
+        context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        Ok(T::from_request(context, request).await.ok())
+    }
+}
+
+/// Requests without `SecurityLevel` header are treated as `external`,
+/// and requests with an unparseable `SecurityLevel` header are treated as `unknown`,
+/// so that a handler checking `is_local` does not trust them.
+impl<C: ContextData> FromRequest<C> for v3::SecurityLevel {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        Ok(request
+            .security_level()
+            .unwrap_or(v3::SecurityLevel::EXTERNAL))
+    }
+}
+
+/// `Id` extracts the `ID` header, decoded with the charset of the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Id(pub String);
+
+impl<C: ContextData> FromRequest<C> for Id {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        header_text(request, v3::HeaderName::ID).map(Self)
+    }
+}
+
+/// `Sender` extracts the `Sender` header, decoded with the charset of the request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sender(pub String);
+
+impl<C: ContextData> FromRequest<C> for Sender {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        header_text(request, v3::HeaderName::SENDER).map(Self)
+    }
+}
+
+/// `Reference<N>` extracts the `ReferenceN` header, decoded with the charset of the request.
+///
+/// Use `Option<Reference<N>>` for the optional references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference<const N: usize>(pub String);
+
+impl<C: ContextData, const N: usize> FromRequest<C> for Reference<N> {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        header_text(request, v3::HeaderName::reference(N)).map(Self)
+    }
+}
+
+macro_rules! impl_deref_str {
+    ($($ty:ty),*) => {
+        $(
+            impl Deref for $ty {
+                type Target = str;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+        )*
+    };
+}
+
+impl_deref_str!(Id, Sender);
+
+impl<const N: usize> Deref for Reference<N> {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// `State<T>` extracts a clone of the value of type `T` in [`Context::state`].
+///
+/// The extraction fails with `500 Internal Server Error` if the value is not inserted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct State<T>(pub T);
+
+impl<C, T> FromRequest<C> for State<T>
+where
+    C: ContextData,
+    T: Clone + Send + Sync + 'static,
+{
+    async fn from_request(
+        context: &Context<C>,
+        _request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        match context.state().get::<T>().await {
+            Some(value) => Ok(Self(value)),
+            None => Err(v3::ShioriError::new(format!(
+                "state `{}` not found",
+                type_name::<T>()
+            ))),
+        }
+    }
+}
+
+impl<T> Deref for State<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<C: ContextData> FromRequest<C> for Event {
+    async fn from_request(
+        _context: &Context<C>,
+        request: &v3::Request,
+    ) -> Result<Self, v3::ShioriError> {
+        Event::try_from(request).map_err(bad_request)
+    }
+}
+
+macro_rules! impl_from_request_for_event {
+    ($($ty:ty => $($variant:ident)|+;)*) => {
+        $(
+            impl<C: ContextData> FromRequest<C> for $ty {
+                async fn from_request(
+                    context: &Context<C>,
+                    request: &v3::Request,
+                ) -> Result<Self, v3::ShioriError> {
+                    match Event::from_request(context, request).await? {
+                        $(Event::$variant(event))|+ => Ok(event),
+                        event => Err(bad_request(format!(
+                            "`{}` cannot be extracted from `{}` event",
+                            stringify!($ty),
+                            event.id()
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_request_for_event! {
+    Boot => OnBoot;
+    FirstBoot => OnFirstBoot;
+    Close => OnClose;
+    GhostChanged => OnGhostChanged;
+    ShellChanged => OnShellChanged;
+    SurfaceChange => OnSurfaceChange;
+    TimeChange => OnSecondChange | OnMinuteChange;
+    Mouse => OnMouseClick | OnMouseDoubleClick | OnMouseMove;
+    ChoiceSelect => OnChoiceSelect;
+    Communicate => OnCommunicate;
+    KeyPress => OnKeyPress;
+    OsInfo => OnNotifyOSInfo;
+    Translate => OnTranslate;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{ok, value, Data};
+    use crate::runtime::{Router, Service};
+    use rstest::rstest;
+
+    async fn call(
+        router: &Router<Data>,
+        context: Context<Data>,
+        request: v3::RequestBuilder,
+    ) -> Result<v3::Response, v3::ShioriError> {
+        use v3::IntoResponse;
+
+        let request = request
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()?;
+        Ok(match router.call(context, request).await {
+            Ok(response) => response,
+            Err(e) => e.into_response(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_extract_headers() -> Result<(), v3::ShioriError> {
+        let router = Router::new().route(
+            "OnCommunicate",
+            |Id(id): Id,
+             sender: Sender,
+             Reference(from): Reference<0>,
+             Reference(text): Reference<1>,
+             missing: Option<Reference<2>>| async move {
+                assert!(missing.is_none());
+                ok(format!("{id},{},{from},{text}", &*sender))
+            },
+        );
+
+        let request = v3::Request::builder()
+            .charset(v3::Charset::SHIFT_JIS)
+            .header(v3::HeaderName::ID, "OnCommunicate")
+            .header(v3::HeaderName::SENDER, "SSP")
+            .reference(0, "user")
+            .reference(1, "こんにちは");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(response.status_code(), v3::StatusCode::OK);
+        assert_eq!(
+            value(&response).as_deref(),
+            Some("OnCommunicate,SSP,user,こんにちは")
+        );
+
+        let request = v3::Request::builder()
+            .header(v3::HeaderName::ID, "OnCommunicate")
+            .reference(0, "user");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(response.status_code(), v3::StatusCode::BAD_REQUEST);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extract_security_level_and_state() -> Result<(), v3::ShioriError> {
+        let router = Router::new().route(
+            "OnBoot",
+            |level: v3::SecurityLevel, State(name): State<String>| async move {
+                ok(format!("{level},{name}"))
+            },
+        );
+        let request = || {
+            v3::Request::builder()
+                .header(v3::HeaderName::ID, "OnBoot")
+                .security_level(v3::SecurityLevel::EXTERNAL)
+        };
+
+        let context = Context::from(Data);
+        let response = call(&router, context.clone(), request()).await?;
+        assert_eq!(
+            response.status_code(),
+            v3::StatusCode::INTERNAL_SERVER_ERROR
+        );
+
+        context.state().insert("ユーザ".to_string()).await;
+        let response = call(&router, context, request()).await?;
+        assert_eq!(value(&response).as_deref(), Some("external,ユーザ"));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::local(Some("local"), "local")]
+    #[case::external(Some("external"), "external")]
+    #[case::garbage(Some("remote"), "unknown")]
+    #[case::missing(None, "external")]
+    #[tokio::test]
+    async fn test_extract_security_level(
+        #[case] header: Option<&str>,
+        #[case] expected: &str,
+    ) -> Result<(), v3::ShioriError> {
+        let router = Router::new().route("OnBoot", |level: v3::SecurityLevel| async move {
+            ok(level.to_string())
+        });
+        let request = v3::Request::builder().header(v3::HeaderName::ID, "OnBoot");
+        let request = match header {
+            Some(value) => request.header(v3::HeaderName::SECURITY_LEVEL, value),
+            None => request,
+        };
+
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(value(&response).as_deref(), Some(expected));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extract_events() -> Result<(), v3::ShioriError> {
+        let router = Router::new()
+            .route_prefix("OnMouse", |mouse: Mouse| async move { ok(mouse.collision) })
+            .fallback(|event: Event| async move { ok(event.id()) });
+
+        let request = v3::Request::builder()
+            .header(v3::HeaderName::ID, "OnMouseClick")
+            .reference(0, "120")
+            .reference(1, "240")
+            .reference(2, "0")
+            .reference(3, "0")
+            .reference(4, "Head")
+            .reference(5, "0");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(value(&response).as_deref(), Some("Head"));
+
+        let request = v3::Request::builder().header(v3::HeaderName::ID, "OnMouseWheel");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(response.status_code(), v3::StatusCode::BAD_REQUEST);
+
+        let request = v3::Request::builder().header(v3::HeaderName::ID, "OnUserEvent");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(value(&response).as_deref(), Some("OnUserEvent"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_plain_handler_is_handler() -> Result<(), v3::ShioriError> {
+        async fn plain(
+            _ctx: Context<Data>,
+            req: v3::Request,
+        ) -> Result<v3::Response, v3::ShioriError> {
+            ok(req.id().and_then(|v| v.text().ok()).unwrap_or_default())
+        }
+        let router = Router::new().route("OnClose", plain);
+
+        let request = v3::Request::builder().header(v3::HeaderName::ID, "OnClose");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(value(&response).as_deref(), Some("OnClose"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_handler_returns_into_response() -> Result<(), v3::ShioriError> {
+        use v3::IntoResponse;
+
+        let router = Router::new()
+            .route("OnBoot", |req: v3::Request| async move {
+                v3::Response::script(&req, "\\h\\s0こんにちは\\e")
+            })
+            .route("OnClose", |Id(id): Id| async move { id })
+            .route("OnSecondChange", || async {})
+            .route("OnMinuteChange", |Id(id): Id| async move {
+                (id == "OnHourChange").then_some("hour")
+            })
+            .route("OnUserEvent", || async {
+                Err::<String, _>(bad_request("rejected"))
+            });
+
+        let request = v3::Request::builder()
+            .charset(v3::Charset::SHIFT_JIS)
+            .header(v3::HeaderName::ID, "OnBoot");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(response.charset(), v3::Charset::SHIFT_JIS);
+        assert_eq!(value(&response).as_deref(), Some("\\h\\s0こんにちは\\e"));
+
+        let request = v3::Request::builder().header(v3::HeaderName::ID, "OnClose");
+        let response = call(&router, Context::from(Data), request).await?;
+        assert_eq!(value(&response).as_deref(), Some("OnClose"));
+
+        for id in ["OnSecondChange", "OnMinuteChange"] {
+            let request = v3::Request::builder().header(v3::HeaderName::ID, id);
+            let response = call(&router, Context::from(Data), request).await?;
+            assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+        }
+
+        // ShioriError is returned to the caller rather than answered
+        let request = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .header(v3::HeaderName::ID, "OnUserEvent")
+            .build()?;
+        let error = router
+            .call(Context::from(Data), request)
+            .await
+            .expect_err("handler returns an error");
+        assert_eq!(
+            error.into_response().status_code(),
+            v3::StatusCode::BAD_REQUEST
+        );
+        Ok(())
+    }
+}
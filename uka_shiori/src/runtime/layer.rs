@@ -0,0 +1,201 @@
+mod lint;
+mod log;
+mod map_error;
+mod security;
+mod timeout;
+
+use crate::types::v3;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+pub use self::log::{LogLayer, LogService};
+pub use lint::{LintLayer, LintService};
+pub(crate) use map_error::error_response;
+pub use map_error::{MapErrorLayer, MapErrorService};
+pub use security::{SecurityLevelLayer, SecurityLevelService};
+pub use timeout::{TimeoutLayer, TimeoutService};
+
+/// The future returned by the services of the built-in layers.
+pub type ResponseFuture = Pin<Box<dyn Future<Output = Result<v3::Response, v3::ShioriError>>>>;
+
+/// `Layer<S>` decorates a service `S` with another service, such as logging or timeout.
+///
+/// Layers are composed with [`ServiceBuilder`] so that the behavior shared between handlers
+/// does not have to live inside each handler.
+pub trait Layer<S> {
+    /// The service produced by wrapping the inner service.
+    type Service;
+
+    /// Wrap the inner service with this layer.
+    fn layer(&self, inner: S) -> Self::Service;
+}
+
+/// `Identity` is the layer that returns the inner service as it is.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Identity;
+
+impl<S> Layer<S> for Identity {
+    type Service = S;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        inner
+    }
+}
+
+/// `Stack<Inner, Outer>` is the composition of two layers, `Outer` wraps the service produced by `Inner`.
+#[derive(Debug, Clone)]
+pub struct Stack<Inner, Outer> {
+    inner: Inner,
+    outer: Outer,
+}
+
+impl<S, Inner, Outer> Layer<S> for Stack<Inner, Outer>
+where
+    Inner: Layer<S>,
+    Outer: Layer<Inner::Service>,
+{
+    type Service = Outer::Service;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        self.outer.layer(self.inner.layer(inner))
+    }
+}
+
+/// `ServiceBuilder<L>` composes layers and applies them to a service.
+///
+/// Layers are applied in the order they are added, so the first layer is the outermost one
+/// and sees the request first and the response last.
+/// The built service can be passed to `Shiori` and `Adapter` like any other service.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use std::time::Duration;
+/// # use uka_shiori::runtime::{box_handler, Context, ContextData, Service, ServiceBuilder, Shiori};
+/// # use uka_shiori::runtime::layer::{MapErrorLayer, SecurityLevelLayer};
+/// # use uka_shiori::types::v3;
+/// #
+/// # struct Data;
+/// # impl ContextData for Data {
+/// #     type Error = v3::ShioriError;
+/// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+/// # }
+/// let service = ServiceBuilder::new()
+///     .log()
+///     .layer(MapErrorLayer::default())
+///     .timeout(Duration::from_secs(5))
+///     .layer(SecurityLevelLayer::local_only())
+///     .service(box_handler(|_ctx: Context<Data>, _req: v3::Request| async {
+///         v3::Response::builder()
+///             .version(v3::Version::SHIORI_30)
+///             .status_code(v3::StatusCode::NO_CONTENT)
+///             .build()
+///             .map_err(v3::ShioriError::from)
+///     }));
+/// let shiori = Shiori::from(service);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ServiceBuilder<L> {
+    layer: L,
+}
+
+impl ServiceBuilder<Identity> {
+    /// Constructs a builder without any layers.
+    pub fn new() -> Self {
+        Self { layer: Identity }
+    }
+}
+
+impl Default for ServiceBuilder<Identity> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L> ServiceBuilder<L> {
+    /// Add a layer inside the layers already added.
+    pub fn layer<T>(self, layer: T) -> ServiceBuilder<Stack<T, L>> {
+        ServiceBuilder {
+            layer: Stack {
+                inner: layer,
+                outer: self.layer,
+            },
+        }
+    }
+
+    /// Add [`LogLayer`] that logs each request and its result.
+    pub fn log(self) -> ServiceBuilder<Stack<LogLayer, L>> {
+        self.layer(LogLayer::new())
+    }
+
+    /// Add [`LintLayer`] that logs the problems of the SakuraScript in the responses.
+    pub fn lint(self) -> ServiceBuilder<Stack<LintLayer, L>> {
+        self.layer(LintLayer::new())
+    }
+
+    /// Add [`TimeoutLayer`] that fails requests that take longer than `timeout`.
+    pub fn timeout(self, timeout: Duration) -> ServiceBuilder<Stack<TimeoutLayer, L>> {
+        self.layer(TimeoutLayer::new(timeout))
+    }
+
+    /// Wrap the service with the layers.
+    pub fn service<S>(&self, service: S) -> L::Service
+    where
+        L: Layer<S>,
+    {
+        self.layer.layer(service)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{request, respond_with, Data};
+    use crate::runtime::{Context, Service};
+    use std::sync::{Arc, Mutex};
+
+    /// Records the order in which the layers see the request.
+    struct Record(Arc<Mutex<Vec<&'static str>>>, &'static str);
+    struct RecordService<S>(Arc<Mutex<Vec<&'static str>>>, &'static str, S);
+
+    impl<S> Layer<S> for Record {
+        type Service = RecordService<S>;
+
+        fn layer(&self, inner: S) -> Self::Service {
+            RecordService(self.0.clone(), self.1, inner)
+        }
+    }
+
+    impl<S> Service<Data, v3::Request> for RecordService<S>
+    where
+        S: Service<Data, v3::Request>,
+    {
+        type Response = S::Response;
+        type Error = S::Error;
+        type Future = S::Future;
+
+        fn call(&self, context: Context<Data>, request: v3::Request) -> Self::Future {
+            self.0.lock().unwrap().push(self.1);
+            self.2.call(context, request)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_builder_applies_layers_in_order() -> Result<(), v3::ShioriError> {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let service = ServiceBuilder::new()
+            .layer(Record(records.clone(), "first"))
+            .layer(Record(records.clone(), "second"))
+            .service(respond_with(v3::StatusCode::NO_CONTENT));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+        assert_eq!(*records.lock().unwrap(), vec!["first", "second"]);
+        Ok(())
+    }
+}
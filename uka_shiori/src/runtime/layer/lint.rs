@@ -0,0 +1,197 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::{Layer, ResponseFuture};
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::sync::Arc;
+use uka_sakura::{Diagnostic, Linter, Rule};
+
+/// `LintLayer` checks the SakuraScript in the `Value` header of each response and logs the problems.
+///
+/// This is a debug aid for development: the response is passed through as it is,
+/// and the diagnostics are logged at `Warn` level by default.
+/// The rules are configured with [`uka_sakura::Linter`].
+///
+/// Only the responses to events, whose ID starts with `On`, are complete scripts.
+/// The responses to resources such as `version` or `craftman` are linted without [`Rule::MissingEnd`],
+/// and `OnTranslate`, which often returns a part of the script as it is, is not linted by default.
+#[derive(Debug, Clone)]
+pub struct LintLayer {
+    linter: Arc<Linter>,
+    level: log::Level,
+    skipped_ids: Vec<String>,
+}
+
+impl LintLayer {
+    /// Constructs a layer with the default rules.
+    pub fn new() -> Self {
+        Self::with_linter(Linter::new())
+    }
+
+    /// Constructs a layer with the linter.
+    pub fn with_linter(linter: Linter) -> Self {
+        Self {
+            linter: Arc::new(linter),
+            level: log::Level::Warn,
+            skipped_ids: vec!["OnTranslate".to_string()],
+        }
+    }
+
+    /// Set the level to log the diagnostics.
+    pub fn level(self, level: log::Level) -> Self {
+        Self { level, ..self }
+    }
+
+    /// Do not lint the responses to the event ID.
+    pub fn skip_id(mut self, id: impl Into<String>) -> Self {
+        self.skipped_ids.push(id.into());
+        self
+    }
+
+    fn lint(&self, id: &str, response: &v3::Response) -> Vec<Diagnostic> {
+        if self.skipped_ids.iter().any(|v| v == id) {
+            return Vec::new();
+        }
+        let mut diagnostics = lint_response(&self.linter, response);
+        if !id.starts_with("On") {
+            diagnostics.retain(|d| d.rule != Rule::MissingEnd);
+        }
+        diagnostics
+    }
+}
+
+impl Default for LintLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for LintLayer {
+    type Service = LintService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LintService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// `LintService<S>` is the service produced by [`LintLayer`].
+pub struct LintService<S> {
+    inner: S,
+    layer: LintLayer,
+}
+
+impl<C, S> Service<C, v3::Request> for LintService<S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+    S::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let layer = self.layer.clone();
+        let id = request
+            .id()
+            .and_then(|v| v.text_with_charset(request.charset()).ok())
+            .unwrap_or_default();
+        let future = self.inner.call(context, request);
+
+        Box::pin(async move {
+            let result = future.await;
+            if let Ok(response) = &result {
+                for diagnostic in layer.lint(&id, response) {
+                    log::log!(layer.level, "{id}: {diagnostic}");
+                }
+            }
+            result
+        })
+    }
+}
+
+/// Lint the `Value` header of the response, decoded with the charset of the response.
+pub(crate) fn lint_response(linter: &Linter, response: &v3::Response) -> Vec<Diagnostic> {
+    match response
+        .value()
+        .map(|v| v.text_with_charset(response.charset()))
+    {
+        Some(Ok(script)) => linter.lint(&script),
+        Some(Err(e)) => {
+            log::debug!("failed to decode `Value` header to lint: {e}");
+            Vec::new()
+        }
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::box_handler;
+    use crate::runtime::test_support::{logger, ok, request, status, value, Data};
+    use rstest::rstest;
+
+    #[test]
+    fn test_lint_response() -> Result<(), v3::ShioriError> {
+        let linter = Linter::new().surfaces([0]);
+        assert!(lint_response(&linter, &status(v3::StatusCode::OK)?).is_empty());
+        assert!(lint_response(&linter, &ok(r"\0\s[0]こんにちは\e")?).is_empty());
+
+        let rules = lint_response(&linter, &ok(r"\0\s[3]\![raise,OnTest]")?)
+            .into_iter()
+            .map(|d| d.rule)
+            .collect::<Vec<_>>();
+        assert_eq!(rules, vec![Rule::MissingEnd, Rule::UnknownSurface]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_lint_service_passes_response_through() -> Result<(), v3::ShioriError> {
+        let service = LintLayer::new().level(log::Level::Debug).layer(box_handler(
+            |_ctx: Context<Data>, _req: v3::Request| async { ok(r"\0\![raise,OnTest") },
+        ));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::OK);
+        assert_eq!(value(&response).as_deref(), Some(r"\0\![raise,OnTest"));
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::event("OnBoot", r"\0\s[0]こんにちは", &["OnBoot: error[missing-end]"])]
+    #[case::event_with_end("OnBoot", r"\0\s[0]こんにちは\e", &[])]
+    #[case::resource("version", "1.0.0", &[])]
+    #[case::resource_with_syntax_error("sakura.recommendsites", r"\![raise", &["sakura.recommendsites: error[syntax]"])]
+    #[case::translate("OnTranslate", r"\0こんにちは", &[])]
+    #[case::skipped("OnSecondChange", r"\0こんにちは", &[])]
+    #[tokio::test]
+    async fn test_lint_service_logs_diagnostics(
+        #[case] id: &str,
+        #[case] script: &'static str,
+        #[case] expected: &[&str],
+    ) -> Result<(), v3::ShioriError> {
+        logger::init();
+        let service = LintLayer::new()
+            .skip_id("OnSecondChange")
+            .layer(box_handler(
+                move |_ctx: Context<Data>, _req: v3::Request| async move { ok(script) },
+            ));
+
+        service
+            .call(Context::from(Data), request(v3::Method::GET, id))
+            .await?;
+
+        let records = logger::take();
+        assert_eq!(records.len(), expected.len(), "{records:?}");
+        for ((level, message), expected) in records.iter().zip(expected) {
+            assert_eq!(*level, log::Level::Warn);
+            assert!(message.starts_with(expected), "{message}");
+        }
+        Ok(())
+    }
+}
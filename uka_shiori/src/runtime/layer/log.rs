@@ -0,0 +1,141 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::{Layer, ResponseFuture};
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::time::Instant;
+
+/// `LogLayer` logs each request with its ID, the status code of the response and the elapsed time.
+///
+/// Successful requests are logged at the configured level (`Debug` by default) and failed requests at `Error`.
+#[derive(Debug, Clone, Copy)]
+pub struct LogLayer {
+    level: log::Level,
+}
+
+impl LogLayer {
+    /// Constructs a layer that logs at `Debug` level.
+    pub fn new() -> Self {
+        Self {
+            level: log::Level::Debug,
+        }
+    }
+
+    /// Set the level to log successful requests.
+    pub fn level(self, level: log::Level) -> Self {
+        Self { level }
+    }
+}
+
+impl Default for LogLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S> Layer<S> for LogLayer {
+    type Service = LogService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LogService {
+            inner,
+            level: self.level,
+        }
+    }
+}
+
+/// `LogService<S>` is the service produced by [`LogLayer`].
+pub struct LogService<S> {
+    inner: S,
+    level: log::Level,
+}
+
+impl<C, S> Service<C, v3::Request> for LogService<S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+    S::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let level = self.level;
+        let method = request.method();
+        let id = request
+            .id()
+            .and_then(|v| v.text_with_charset(request.charset()).ok())
+            .unwrap_or_default();
+        let start = Instant::now();
+        let future = self.inner.call(context, request);
+
+        Box::pin(async move {
+            let result = future.await;
+            let elapsed = start.elapsed();
+            match &result {
+                Ok(response) => log::log!(
+                    level,
+                    "{method} {id}: {} ({elapsed:?})",
+                    response.status_code()
+                ),
+                Err(err) => log::error!("{method} {id}: {err} ({elapsed:?})"),
+            }
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::box_handler;
+    use crate::runtime::test_support::{logger, request, respond_with, Data};
+
+    #[tokio::test]
+    async fn test_log_service_logs_success() -> Result<(), v3::ShioriError> {
+        logger::init();
+        let service = LogLayer::new()
+            .level(log::Level::Info)
+            .layer(respond_with(v3::StatusCode::NO_CONTENT));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+
+        let records = logger::take();
+        assert_eq!(records.len(), 1);
+        let (level, message) = &records[0];
+        assert_eq!(*level, log::Level::Info);
+        assert!(
+            message.starts_with("GET OnBoot: 204 No Content ("),
+            "unexpected log: {message}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_log_service_logs_error() -> Result<(), v3::ShioriError> {
+        logger::init();
+        let service = LogLayer::new().layer(box_handler(
+            |_ctx: Context<Data>, _req: v3::Request| async {
+                Err::<v3::Response, _>(v3::ShioriError::new("broken dictionary"))
+            },
+        ));
+
+        assert!(service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await
+            .is_err());
+
+        let records = logger::take();
+        assert_eq!(records.len(), 1);
+        let (level, message) = &records[0];
+        assert_eq!(*level, log::Level::Error);
+        assert!(
+            message.starts_with("GET OnBoot: ") && message.contains("broken dictionary"),
+            "unexpected log: {message}"
+        );
+        Ok(())
+    }
+}
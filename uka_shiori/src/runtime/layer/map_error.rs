@@ -0,0 +1,138 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::{Layer, ResponseFuture};
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::sync::Arc;
+
+type MapFn = Arc<dyn Fn(v3::ShioriError) -> v3::Response + Send + Sync>;
+
+/// `MapErrorLayer` converts the errors of the inner service into responses.
+///
+/// By default, the error is answered with its status code (`500 Internal Server Error` if not set)
+/// and reported to the baseware with `ErrorLevel` and `ErrorDescription` headers.
+#[derive(Clone)]
+pub struct MapErrorLayer {
+    f: MapFn,
+}
+
+impl MapErrorLayer {
+    /// Constructs a layer that converts errors with the function.
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(v3::ShioriError) -> v3::Response + Send + Sync + 'static,
+    {
+        Self { f: Arc::new(f) }
+    }
+}
+
+impl Default for MapErrorLayer {
+    fn default() -> Self {
+        Self::new(error_response)
+    }
+}
+
+impl<S> Layer<S> for MapErrorLayer {
+    type Service = MapErrorService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MapErrorService {
+            inner,
+            f: self.f.clone(),
+        }
+    }
+}
+
+/// `MapErrorService<S>` is the service produced by [`MapErrorLayer`].
+pub struct MapErrorService<S> {
+    inner: S,
+    f: MapFn,
+}
+
+impl<C, S> Service<C, v3::Request> for MapErrorService<S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+    S::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let f = self.f.clone();
+        let future = self.inner.call(context, request);
+
+        Box::pin(async move { Ok(future.await.unwrap_or_else(|err| f(err))) })
+    }
+}
+
+/// Converts the error into a response that reports the error to the baseware.
+pub(crate) fn error_response(err: v3::ShioriError) -> v3::Response {
+    use v3::IntoResponse;
+
+    let description = err
+        .to_string()
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>();
+    v3::Response::builder()
+        .version(err.version().unwrap_or(v3::Version::SHIORI_30))
+        .status_code(
+            err.status_code()
+                .unwrap_or(v3::StatusCode::INTERNAL_SERVER_ERROR),
+        )
+        .charset(v3::Charset::UTF8)
+        .error(v3::ErrorLevel::ERROR, description)
+        .build()
+        .unwrap_or_else(|_| err.into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::box_handler;
+    use crate::runtime::test_support::{request, status, Data};
+
+    #[tokio::test]
+    async fn test_map_error_service_default() -> Result<(), v3::ShioriError> {
+        let service = MapErrorLayer::default().layer(box_handler(
+            |_ctx: Context<Data>, _req: v3::Request| async {
+                Err(v3::ShioriError::new("dictionary\nnot found")
+                    .with_status_code(v3::StatusCode::BAD_REQUEST))
+            },
+        ));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::BAD_REQUEST);
+        assert_eq!(
+            response.error_levels().expect("valid error levels"),
+            vec![v3::ErrorLevel::ERROR]
+        );
+        assert_eq!(
+            response
+                .error_descriptions()
+                .expect("valid error descriptions"),
+            vec!["dictionary not found".to_string()]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_map_error_service_custom() -> Result<(), v3::ShioriError> {
+        let service =
+            MapErrorLayer::new(|_err| status(v3::StatusCode::NO_CONTENT).expect("valid response"))
+                .layer(box_handler(
+                    |_ctx: Context<Data>, _req: v3::Request| async {
+                        Err(v3::ShioriError::new("failed"))
+                    },
+                ));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+        Ok(())
+    }
+}
@@ -0,0 +1,192 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::{Layer, ResponseFuture};
+use crate::runtime::service::Service;
+use crate::types::v3;
+
+/// `SecurityLevelLayer` answers the requests whose `SecurityLevel` is not allowed without calling the inner service.
+///
+/// Requests without `SecurityLevel` header are treated as `external` by default,
+/// and requests with an unparseable `SecurityLevel` header are treated as `unknown`.
+/// Rejected requests are answered with `204 No Content` by default.
+#[derive(Debug, Clone)]
+pub struct SecurityLevelLayer {
+    allowed: Vec<v3::SecurityLevel>,
+    exempt_ids: Vec<String>,
+    status_code: v3::StatusCode,
+    missing: v3::SecurityLevel,
+}
+
+impl SecurityLevelLayer {
+    /// Constructs a layer that allows only the given security levels.
+    pub fn new<I>(allowed: I) -> Self
+    where
+        I: IntoIterator<Item = v3::SecurityLevel>,
+    {
+        Self {
+            allowed: allowed.into_iter().collect(),
+            exempt_ids: Vec::new(),
+            status_code: v3::StatusCode::NO_CONTENT,
+            missing: v3::SecurityLevel::EXTERNAL,
+        }
+    }
+
+    /// Constructs a layer that allows only `local` requests.
+    pub fn local_only() -> Self {
+        Self::new([v3::SecurityLevel::LOCAL])
+    }
+
+    /// Allow the event ID regardless of its security level.
+    pub fn allow_id(mut self, id: impl Into<String>) -> Self {
+        self.exempt_ids.push(id.into());
+        self
+    }
+
+    /// Set the status code to answer the rejected requests.
+    pub fn reject_with(self, status_code: v3::StatusCode) -> Self {
+        Self {
+            status_code,
+            ..self
+        }
+    }
+
+    /// Set the security level assumed for requests without `SecurityLevel` header.
+    ///
+    /// Defaults to `external`, so that such requests are rejected unless `external` is allowed.
+    pub fn missing_as(self, level: v3::SecurityLevel) -> Self {
+        Self {
+            missing: level,
+            ..self
+        }
+    }
+
+    fn is_allowed(&self, request: &v3::Request) -> bool {
+        let level = request.security_level().unwrap_or(self.missing);
+        if self.allowed.contains(&level) {
+            return true;
+        }
+
+        request
+            .id()
+            .and_then(|v| v.text_with_charset(request.charset()).ok())
+            .is_some_and(|id| self.exempt_ids.contains(&id))
+    }
+}
+
+impl<S> Layer<S> for SecurityLevelLayer {
+    type Service = SecurityLevelService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SecurityLevelService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// `SecurityLevelService<S>` is the service produced by [`SecurityLevelLayer`].
+pub struct SecurityLevelService<S> {
+    inner: S,
+    layer: SecurityLevelLayer,
+}
+
+impl<C, S> Service<C, v3::Request> for SecurityLevelService<S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+    S::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        if self.layer.is_allowed(&request) {
+            return Box::pin(self.inner.call(context, request));
+        }
+
+        log::debug!(
+            "rejected request with security level {:?}",
+            request.security_level()
+        );
+        let response = v3::Response::builder()
+            .version(v3::Version::SHIORI_30)
+            .status_code(self.layer.status_code)
+            .build()
+            .map_err(v3::ShioriError::from);
+        Box::pin(async move { response })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{respond_with, Data};
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::without_security_level(None, "OnBoot", v3::StatusCode::BAD_REQUEST)]
+    #[case::local(Some(v3::SecurityLevel::LOCAL), "OnBoot", v3::StatusCode::OK)]
+    #[case::external(
+        Some(v3::SecurityLevel::EXTERNAL),
+        "OnBoot",
+        v3::StatusCode::BAD_REQUEST
+    )]
+    #[case::unknown(
+        Some(v3::SecurityLevel::UNKNOWN),
+        "OnBoot",
+        v3::StatusCode::BAD_REQUEST
+    )]
+    #[case::exempt(Some(v3::SecurityLevel::EXTERNAL), "OnMusicPlay", v3::StatusCode::OK)]
+    #[tokio::test]
+    async fn test_security_level_service(
+        #[case] level: Option<v3::SecurityLevel>,
+        #[case] id: &str,
+        #[case] expected: v3::StatusCode,
+    ) -> Result<(), v3::ShioriError> {
+        let service = SecurityLevelLayer::local_only()
+            .allow_id("OnMusicPlay")
+            .reject_with(v3::StatusCode::BAD_REQUEST)
+            .layer(respond_with(v3::StatusCode::OK));
+
+        let builder = v3::Request::builder()
+            .method(v3::Method::NOTIFY)
+            .version(v3::Version::SHIORI_30)
+            .header(v3::HeaderName::ID, id);
+        let request = match level {
+            Some(level) => builder.security_level(level),
+            None => builder,
+        }
+        .build()?;
+        let response = service.call(Context::from(Data), request).await?;
+        assert_eq!(response.status_code(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::missing_as_local(None, v3::SecurityLevel::LOCAL, v3::StatusCode::OK)]
+    #[case::missing_as_external(None, v3::SecurityLevel::EXTERNAL, v3::StatusCode::NO_CONTENT)]
+    #[case::garbage(Some("remote"), v3::SecurityLevel::LOCAL, v3::StatusCode::NO_CONTENT)]
+    #[tokio::test]
+    async fn test_security_level_service_fallback(
+        #[case] header: Option<&str>,
+        #[case] missing: v3::SecurityLevel,
+        #[case] expected: v3::StatusCode,
+    ) -> Result<(), v3::ShioriError> {
+        let service = SecurityLevelLayer::local_only()
+            .missing_as(missing)
+            .layer(respond_with(v3::StatusCode::OK));
+
+        let builder = v3::Request::builder()
+            .method(v3::Method::NOTIFY)
+            .version(v3::Version::SHIORI_30)
+            .header(v3::HeaderName::ID, "OnBoot");
+        let request = match header {
+            Some(value) => builder.header(v3::HeaderName::SECURITY_LEVEL, value),
+            None => builder,
+        }
+        .build()?;
+        let response = service.call(Context::from(Data), request).await?;
+        assert_eq!(response.status_code(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::{Layer, ResponseFuture};
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::time::Duration;
+
+/// `TimeoutLayer` fails the request with `500 Internal Server Error` if the inner service does not respond in time.
+///
+/// The baseware blocks while waiting for the response, so a slow handler freezes the ghost.
+#[derive(Debug, Clone, Copy)]
+pub struct TimeoutLayer {
+    timeout: Duration,
+}
+
+impl TimeoutLayer {
+    /// Constructs a layer that fails requests that take longer than `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+impl<S> Layer<S> for TimeoutLayer {
+    type Service = TimeoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TimeoutService {
+            inner,
+            timeout: self.timeout,
+        }
+    }
+}
+
+/// `TimeoutService<S>` is the service produced by [`TimeoutLayer`].
+pub struct TimeoutService<S> {
+    inner: S,
+    timeout: Duration,
+}
+
+impl<C, S> Service<C, v3::Request> for TimeoutService<S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError>,
+    S::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let timeout = self.timeout;
+        let future = self.inner.call(context, request);
+
+        Box::pin(async move {
+            tokio::time::timeout(timeout, future)
+                .await
+                .unwrap_or_else(|elapsed| {
+                    Err(v3::ShioriError::from(elapsed)
+                        .with_status_code(v3::StatusCode::INTERNAL_SERVER_ERROR)
+                        .context(format!("request timed out after {timeout:?}")))
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::box_handler;
+    use crate::runtime::test_support::{request, status, Data};
+
+    #[tokio::test]
+    async fn test_timeout_service() -> Result<(), v3::ShioriError> {
+        let service = TimeoutLayer::new(Duration::from_millis(50)).layer(box_handler(
+            |_ctx: Context<Data>, req: v3::Request| async move {
+                if req.id().is_some_and(|id| id.as_bytes() == b"OnSlow") {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                }
+                status(v3::StatusCode::NO_CONTENT)
+            },
+        ));
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+
+        let err = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnSlow"))
+            .await
+            .expect_err("request must time out");
+        assert_eq!(
+            err.status_code(),
+            Some(v3::StatusCode::INTERNAL_SERVER_ERROR)
+        );
+        assert!(err.to_string().starts_with("request timed out after 50ms"));
+        Ok(())
+    }
+}
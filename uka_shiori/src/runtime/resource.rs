@@ -0,0 +1,369 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// `Site` is an entry of the site list resources such as `sakura.recommendsites` and `sakura.portalsites`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Site {
+    /// The name of the site displayed in the menu.
+    pub name: String,
+    /// The URL of the site.
+    pub url: String,
+    /// The path of the banner image relative to the ghost directory.
+    pub banner: Option<String>,
+    /// The script that is played when the site is selected.
+    pub talk: Option<String>,
+}
+
+impl Site {
+    /// Constructs a new Site with the name and the URL.
+    pub fn new(name: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            url: url.into(),
+            banner: None,
+            talk: None,
+        }
+    }
+
+    /// Constructs a separator line of the menu.
+    pub fn separator() -> Self {
+        Self::new("-", "")
+    }
+
+    /// Set the path of the banner image.
+    pub fn banner(self, banner: impl Into<String>) -> Self {
+        Self {
+            banner: Some(banner.into()),
+            ..self
+        }
+    }
+
+    /// Set the script that is played when the site is selected.
+    pub fn talk(self, talk: impl Into<String>) -> Self {
+        Self {
+            talk: Some(talk.into()),
+            ..self
+        }
+    }
+
+    fn columns(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.url.clone(),
+            self.banner.clone().unwrap_or_default(),
+            self.talk.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// `ResourceValue` is the value answered to the `GET` request of the resource.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum ResourceValue {
+    /// A plain string.
+    Text(String),
+    /// A list of sites, encoded with `\x01` between the columns and `\x02` between the sites.
+    Sites(Vec<Site>),
+}
+
+impl From<&str> for ResourceValue {
+    fn from(value: &str) -> Self {
+        ResourceValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for ResourceValue {
+    fn from(value: String) -> Self {
+        ResourceValue::Text(value)
+    }
+}
+
+impl From<Vec<Site>> for ResourceValue {
+    fn from(value: Vec<Site>) -> Self {
+        ResourceValue::Sites(value)
+    }
+}
+
+type ComputeFn<C> = Box<dyn Fn(&Context<C>) -> Option<ResourceValue> + Send + Sync>;
+
+enum Resource<C>
+where
+    C: ContextData,
+{
+    Static(ResourceValue),
+    Computed(ComputeFn<C>),
+}
+
+/// `Resources<C>` is a table of the resources that the baseware requests with `GET`,
+/// such as `version`, `craftman` and `sakura.recommendsites`.
+///
+/// It can be used as a service by itself, answering `204 No Content` to unknown requests,
+/// or it can be mounted on a [`Router`](crate::runtime::Router) with `Router::resources`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use uka_shiori::runtime::{Context, ContextData, Resources, Service, Site};
+/// # use uka_shiori::types::v3;
+/// #
+/// # struct Data;
+/// # impl ContextData for Data {
+/// #     type Error = v3::ShioriError;
+/// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+/// # }
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), v3::ShioriError> {
+/// let resources = Resources::new()
+///     .version("1.0.0")
+///     .craftman("uka")
+///     .recommend_sites(vec![Site::new("uka", "https://example.com/")]);
+///
+/// let request = v3::Request::builder()
+///     .method(v3::Method::GET)
+///     .version(v3::Version::SHIORI_30)
+///     .header(v3::HeaderName::ID, "version")
+///     .build()?;
+/// let response = resources.call(Context::from(Data), request).await?;
+/// assert_eq!(response.value().unwrap().text().unwrap(), "1.0.0");
+/// # Ok(())
+/// # }
+/// ```
+pub struct Resources<C>
+where
+    C: ContextData,
+{
+    entries: HashMap<String, Resource<C>>,
+}
+
+impl<C> Resources<C>
+where
+    C: ContextData,
+{
+    /// Constructs an empty resource table.
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Register a static value for the resource ID.
+    pub fn resource(mut self, id: impl Into<String>, value: impl Into<ResourceValue>) -> Self {
+        self.entries
+            .insert(id.into(), Resource::Static(value.into()));
+        self
+    }
+
+    /// Register a function that computes the value for the resource ID on each request.
+    ///
+    /// If the function returns `None`, the resource is answered as `204 No Content`.
+    pub fn computed<F, V>(mut self, id: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(&Context<C>) -> Option<V> + Send + Sync + 'static,
+        V: Into<ResourceValue>,
+    {
+        self.entries.insert(
+            id.into(),
+            Resource::Computed(Box::new(move |ctx| f(ctx).map(Into::into))),
+        );
+        self
+    }
+
+    /// Set `version` resource, the version of the SHIORI.
+    pub fn version(self, value: impl Into<String>) -> Self {
+        self.resource("version", value.into())
+    }
+
+    /// Set `name` resource, the name of the SHIORI.
+    pub fn name(self, value: impl Into<String>) -> Self {
+        self.resource("name", value.into())
+    }
+
+    /// Set `craftman` resource, the author name in ASCII.
+    pub fn craftman(self, value: impl Into<String>) -> Self {
+        self.resource("craftman", value.into())
+    }
+
+    /// Set `craftmanw` resource, the author name that may contain multibyte characters.
+    pub fn craftmanw(self, value: impl Into<String>) -> Self {
+        self.resource("craftmanw", value.into())
+    }
+
+    /// Set `homeurl` resource, the URL used for network update.
+    pub fn homeurl(self, value: impl Into<String>) -> Self {
+        self.resource("homeurl", value.into())
+    }
+
+    /// Set `sakura.recommendsites` resource.
+    pub fn recommend_sites(self, sites: Vec<Site>) -> Self {
+        self.resource("sakura.recommendsites", sites)
+    }
+
+    /// Set `kero.recommendsites` resource.
+    pub fn kero_recommend_sites(self, sites: Vec<Site>) -> Self {
+        self.resource("kero.recommendsites", sites)
+    }
+
+    /// Set `sakura.portalsites` resource.
+    pub fn portal_sites(self, sites: Vec<Site>) -> Self {
+        self.resource("sakura.portalsites", sites)
+    }
+
+    /// Set `<scope>.menu.<item>.caption` resource, e.g. `sakura.menu.recommendsites.caption`.
+    pub fn menu_caption(self, scope: &str, item: &str, caption: impl Into<String>) -> Self {
+        self.resource(format!("{scope}.menu.{item}.caption"), caption.into())
+    }
+
+    /// Returns true if the resource ID is registered.
+    pub fn contains(&self, id: &str) -> bool {
+        self.entries.contains_key(id)
+    }
+
+    /// Answers the request if it is a `GET` request for a registered resource.
+    pub(crate) fn respond(
+        &self,
+        context: &Context<C>,
+        request: &v3::Request,
+    ) -> Option<Result<v3::Response, v3::ShioriError>> {
+        if request.method() != v3::Method::GET {
+            return None;
+        }
+        let id = request
+            .id()
+            .and_then(|v| v.text_with_charset(request.charset()).ok())?;
+        let value = match self.entries.get(&id)? {
+            Resource::Static(value) => Some(value.clone()),
+            Resource::Computed(f) => f(context),
+        };
+
+        let builder = v3::Response::builder()
+            .version(v3::Version::SHIORI_30)
+            .charset(request.charset());
+        let builder = match value {
+            Some(ResourceValue::Text(text)) => builder
+                .status_code(v3::StatusCode::OK)
+                .header(v3::HeaderName::VALUE, text),
+            Some(ResourceValue::Sites(sites)) => builder.status_code(v3::StatusCode::OK).table(
+                v3::HeaderName::VALUE,
+                sites.iter().map(Site::columns).collect(),
+            ),
+            None => builder.status_code(v3::StatusCode::NO_CONTENT),
+        };
+        Some(builder.build().map_err(v3::ShioriError::from))
+    }
+}
+
+impl<C> Default for Resources<C>
+where
+    C: ContextData,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Service<C, v3::Request> for Resources<C>
+where
+    C: ContextData,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = Pin<Box<dyn Future<Output = Result<v3::Response, v3::ShioriError>>>>;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let result = self.respond(&context, &request).unwrap_or_else(|| {
+            v3::Response::builder()
+                .version(v3::Version::SHIORI_30)
+                .status_code(v3::StatusCode::NO_CONTENT)
+                .build()
+                .map_err(v3::ShioriError::from)
+        });
+        Box::pin(async move { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::request;
+    use std::path::PathBuf;
+
+    struct Data {
+        talk_count: usize,
+    }
+    impl ContextData for Data {
+        type Error = v3::ShioriError;
+
+        fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+            Ok(Self { talk_count: 0 })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resources_static_and_computed() -> Result<(), v3::ShioriError> {
+        let resources = Resources::new()
+            .version("1.0.0")
+            .craftmanw("uka")
+            .menu_caption("sakura", "recommendsites", "Recommend")
+            .computed("talkcount", |ctx: &Context<Data>| {
+                Some(ctx.talk_count.to_string())
+            })
+            .computed("nothing", |_: &Context<Data>| None::<String>);
+        let context = Context::from(Data { talk_count: 3 });
+
+        for (id, expected) in [
+            ("version", "1.0.0"),
+            ("craftmanw", "uka"),
+            ("sakura.menu.recommendsites.caption", "Recommend"),
+            ("talkcount", "3"),
+        ] {
+            let response = resources
+                .call(context.clone(), request(v3::Method::GET, id))
+                .await?;
+            assert_eq!(response.status_code(), v3::StatusCode::OK);
+            assert_eq!(
+                response.value().map(|v| v.text()).transpose()?,
+                Some(expected.to_string())
+            );
+        }
+
+        for request in [
+            request(v3::Method::GET, "nothing"),
+            request(v3::Method::GET, "homeurl"),
+            request(v3::Method::NOTIFY, "version"),
+        ] {
+            let response = resources.call(context.clone(), request).await?;
+            assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+            assert!(response.value().is_none());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_resources_sites() -> Result<(), v3::ShioriError> {
+        let resources = Resources::new().recommend_sites(vec![
+            Site::new("a", "http://a/").banner("a.png"),
+            Site::separator(),
+            Site::new("b", "http://b/").talk("\\0b\\e"),
+        ]);
+        let response = resources
+            .call(
+                Context::from(Data { talk_count: 0 }),
+                request(v3::Method::GET, "sakura.recommendsites"),
+            )
+            .await?;
+
+        assert_eq!(
+            response.value().map(|v| v.as_bytes()),
+            Some(
+                b"a\x01http://a/\x01a.png\x01\x02-\x01\x01\x01\x02b\x01http://b/\x01\x01\\0b\\e"
+                    .to_vec()
+            )
+        );
+        Ok(())
+    }
+}
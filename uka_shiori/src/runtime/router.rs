@@ -0,0 +1,417 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::extract::Handler;
+use crate::runtime::resource::Resources;
+use crate::runtime::service::{BoxHandlerV3, Service};
+use crate::types::v3;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+/// `Router<C>` is a service that dispatches SHIORI/3.0 requests to handlers by the `ID` header.
+///
+/// Handlers are async functions that take `(Context<C>, v3::Request)`, or any combination of the extractors
+/// implementing [`FromRequest`](crate::runtime::extract::FromRequest).
+///
+/// Handlers are resolved in the following order:
+///
+/// 1. the handler registered for the ID with the method of the request (`get` / `notify`)
+/// 2. the handler registered for the ID with any method (`route`)
+/// 3. the resources for `GET` requests (`resources`)
+/// 4. the prefix and pattern handlers, in the order they were registered (`route_prefix` / `route_pattern`)
+/// 5. the fallback handler, which answers `204 No Content` by default
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use uka_shiori::runtime::{Context, ContextData, Router, Service};
+/// # use uka_shiori::types::v3;
+/// #
+/// # struct Data;
+/// # impl ContextData for Data {
+/// #     type Error = v3::ShioriError;
+/// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+/// # }
+/// # async fn on_boot(_ctx: Context<Data>, _req: v3::Request) -> Result<v3::Response, v3::ShioriError> {
+/// #     unimplemented!()
+/// # }
+/// # async fn on_mouse(_ctx: Context<Data>, _req: v3::Request) -> Result<v3::Response, v3::ShioriError> {
+/// #     unimplemented!()
+/// # }
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), v3::ShioriError> {
+/// let router = Router::new()
+///     .route("OnBoot", on_boot)
+///     .route_prefix("OnMouse", on_mouse);
+///
+/// let request = v3::Request::builder()
+///     .method(v3::Method::NOTIFY)
+///     .version(v3::Version::SHIORI_30)
+///     .header(v3::HeaderName::ID, "OnNotifyOSInfo")
+///     .build()?;
+/// let response = router.call(Context::from(Data), request).await?;
+/// assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Router<C>
+where
+    C: ContextData,
+{
+    routes: HashMap<String, Endpoint<C>>,
+    patterns: Vec<(Pattern, BoxHandlerV3<C>)>,
+    resources: Option<Resources<C>>,
+    fallback: Option<BoxHandlerV3<C>>,
+}
+
+struct Endpoint<C>
+where
+    C: ContextData,
+{
+    any: Option<BoxHandlerV3<C>>,
+    get: Option<BoxHandlerV3<C>>,
+    notify: Option<BoxHandlerV3<C>>,
+}
+
+impl<C> Default for Endpoint<C>
+where
+    C: ContextData,
+{
+    fn default() -> Self {
+        Self {
+            any: None,
+            get: None,
+            notify: None,
+        }
+    }
+}
+
+enum Pattern {
+    Prefix(String),
+    Glob(String),
+}
+
+impl Pattern {
+    fn is_match(&self, id: &str) -> bool {
+        match self {
+            Pattern::Prefix(prefix) => id.starts_with(prefix.as_str()),
+            Pattern::Glob(pattern) => glob_match(pattern, id),
+        }
+    }
+}
+
+impl<C> Router<C>
+where
+    C: ContextData,
+{
+    /// Constructs an empty router that answers `204 No Content` to all requests.
+    pub fn new() -> Self {
+        Self {
+            routes: HashMap::new(),
+            patterns: Vec::new(),
+            resources: None,
+            fallback: None,
+        }
+    }
+
+    /// Register a handler for the ID with any method.
+    pub fn route<H, Args>(mut self, id: impl Into<String>, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.routes.entry(id.into()).or_default().any = Some(handler.into_service());
+        self
+    }
+
+    /// Register a handler for the ID with `GET` method.
+    pub fn get<H, Args>(mut self, id: impl Into<String>, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.routes.entry(id.into()).or_default().get = Some(handler.into_service());
+        self
+    }
+
+    /// Register a handler for the ID with `NOTIFY` method.
+    pub fn notify<H, Args>(mut self, id: impl Into<String>, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.routes.entry(id.into()).or_default().notify = Some(handler.into_service());
+        self
+    }
+
+    /// Register a handler for the IDs that start with the prefix, e.g. `OnMouse`.
+    pub fn route_prefix<H, Args>(mut self, prefix: impl Into<String>, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.patterns
+            .push((Pattern::Prefix(prefix.into()), handler.into_service()));
+        self
+    }
+
+    /// Register a handler for the IDs that match the pattern.
+    ///
+    /// `*` in the pattern matches any sequence of characters, e.g. `On*Click` matches `OnMouseClick`.
+    pub fn route_pattern<H, Args>(mut self, pattern: impl Into<String>, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.patterns
+            .push((Pattern::Glob(pattern.into()), handler.into_service()));
+        self
+    }
+
+    /// Set the resource table that answers `GET` requests for the registered resource IDs.
+    pub fn resources(mut self, resources: Resources<C>) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    /// Set the handler for the requests that do not match any route.
+    ///
+    /// If it is not set, `204 No Content` is answered.
+    pub fn fallback<H, Args>(mut self, handler: H) -> Self
+    where
+        H: Handler<C, Args>,
+    {
+        self.fallback = Some(handler.into_service());
+        self
+    }
+
+    fn find(&self, request: &v3::Request) -> Route<'_, C> {
+        let Some(id) = request
+            .id()
+            .and_then(|v| v.text_with_charset(request.charset()).ok())
+        else {
+            return Route::Fallback;
+        };
+
+        if let Some(endpoint) = self.routes.get(&id) {
+            let by_method = match request.method() {
+                v3::Method::GET => endpoint.get.as_ref(),
+                v3::Method::NOTIFY => endpoint.notify.as_ref(),
+            };
+            if let Some(handler) = by_method.or(endpoint.any.as_ref()) {
+                return Route::Handler(handler);
+            }
+        }
+
+        if let Some(resources) = &self.resources {
+            if request.method() == v3::Method::GET && resources.contains(&id) {
+                return Route::Resources(resources);
+            }
+        }
+
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| pattern.is_match(&id))
+            .map(|(_, handler)| Route::Handler(handler))
+            .unwrap_or(Route::Fallback)
+    }
+}
+
+enum Route<'a, C>
+where
+    C: ContextData,
+{
+    Handler(&'a BoxHandlerV3<C>),
+    Resources(&'a Resources<C>),
+    Fallback,
+}
+
+impl<C> Default for Router<C>
+where
+    C: ContextData,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> Service<C, v3::Request> for Router<C>
+where
+    C: ContextData,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = Pin<Box<dyn Future<Output = Result<v3::Response, v3::ShioriError>>>>;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let handler = match self.find(&request) {
+            Route::Handler(handler) => Some(handler),
+            Route::Resources(resources) => return resources.call(context, request),
+            Route::Fallback => self.fallback.as_ref(),
+        };
+        match handler {
+            Some(handler) => handler.call(context, request),
+            None => Box::pin(async {
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::NO_CONTENT)
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            }),
+        }
+    }
+}
+
+/// Match the text with the pattern that `*` matches any sequence of characters.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+
+    let parts = parts.collect::<Vec<_>>();
+    let Some((last, middle)) = parts.split_last() else {
+        // no `*` in the pattern
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{ok, request, Data};
+    use rstest::rstest;
+
+    fn respond(
+        value: &'static str,
+    ) -> impl Fn(
+        Context<Data>,
+        v3::Request,
+    ) -> std::future::Ready<Result<v3::Response, v3::ShioriError>>
+           + Send
+           + Sync
+           + 'static {
+        move |_ctx, _req| std::future::ready(ok(value))
+    }
+
+    async fn call(router: &Router<Data>, request: v3::Request) -> Option<String> {
+        let response = router
+            .call(Context::from(Data), request)
+            .await
+            .expect("response");
+        response.value().map(|v| v.text().expect("ascii value"))
+    }
+
+    #[tokio::test]
+    async fn test_router_dispatch_by_id_and_method() {
+        let router = Router::new()
+            .route("OnBoot", respond("boot"))
+            .get("OnClose", respond("get close"))
+            .notify("OnClose", respond("notify close"))
+            .route("OnSecondChange", respond("any second"))
+            .notify("OnSecondChange", respond("notify second"));
+
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnBoot")).await,
+            Some("boot".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnClose")).await,
+            Some("get close".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::NOTIFY, "OnClose")).await,
+            Some("notify close".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnSecondChange")).await,
+            Some("any second".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::NOTIFY, "OnSecondChange")).await,
+            Some("notify second".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_dispatch_by_prefix_and_pattern() {
+        let router = Router::new()
+            .route("OnMouseMove", respond("move"))
+            .route_pattern("On*DoubleClick", respond("double click"))
+            .route_prefix("OnMouse", respond("mouse"));
+
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnMouseMove")).await,
+            Some("move".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnMouseDoubleClick")).await,
+            Some("double click".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnMouseClick")).await,
+            Some("mouse".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_fallback() -> Result<(), v3::ShioriError> {
+        let router = Router::new().route("OnBoot", respond("boot"));
+        let response = router
+            .call(Context::from(Data), request(v3::Method::GET, "OnClose"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+
+        let without_id = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()?;
+        let response = router.call(Context::from(Data), without_id).await?;
+        assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+
+        let router = router.fallback(respond("fallback"));
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "OnClose")).await,
+            Some("fallback".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_router_resources() {
+        let router = Router::new()
+            .route("name", respond("route"))
+            .route_prefix("version", respond("prefix"))
+            .resources(Resources::new().name("resource").version("1.0.0"));
+
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "name")).await,
+            Some("route".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::GET, "version")).await,
+            Some("1.0.0".to_string())
+        );
+        assert_eq!(
+            call(&router, request(v3::Method::NOTIFY, "version")).await,
+            Some("prefix".to_string())
+        );
+    }
+
+    #[rstest]
+    #[case::exact("OnBoot", "OnBoot", true)]
+    #[case::exact_mismatch("OnBoot", "OnBootEx", false)]
+    #[case::wildcard_suffix("OnMouse*", "OnMouseClick", true)]
+    #[case::wildcard_middle("On*Click", "OnMouseDoubleClick", true)]
+    #[case::wildcard_middle_mismatch("On*Click", "OnMouseMove", false)]
+    #[case::wildcard_overlap("On*nOn", "OnOn", false)]
+    #[case::multiple_wildcards("On*Mouse*", "OnNotifyMouseWheel", true)]
+    #[case::only_wildcard("*", "anything", true)]
+    fn test_glob_match(#[case] pattern: &str, #[case] text: &str, #[case] expected: bool) {
+        assert_eq!(glob_match(pattern, text), expected);
+    }
+}
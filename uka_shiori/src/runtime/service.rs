@@ -1,5 +1,5 @@
 use crate::runtime::context::{Context, ContextData};
-use crate::types::v3;
+use crate::types::{v2, v3};
 use std::future::Future;
 use std::pin::Pin;
 
@@ -75,6 +75,8 @@ pub type BoxAsyncFn<Ctx, Req, Res, Err> =
 
 pub type BoxHandler<Ctx, Req, Res, Err> = ShioriHandler<BoxAsyncFn<Ctx, Req, Res, Err>>;
 
+pub type BoxHandlerV2<Ctx> = BoxHandler<Ctx, v2::Request, v2::Response, v3::ShioriError>;
+
 pub type BoxHandlerV3<Ctx> = BoxHandler<Ctx, v3::Request, v3::Response, v3::ShioriError>;
 
 /// Provides a convenient way to construct a `ShioriHandler` from a function, and wraps the function in a `Box`.
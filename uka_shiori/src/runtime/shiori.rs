@@ -1,11 +1,21 @@
+use crate::runtime::compat;
 use crate::runtime::context::{Context, ContextData};
-use crate::runtime::service::Service;
-use crate::types::v3;
+use crate::runtime::service::{BoxHandlerV2, Service};
+use crate::runtime::state::StateMap;
+use crate::runtime::task::Driver;
+use crate::types;
+use crate::types::{v2, v3};
 use std::future::Future;
 use std::ops::Deref;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// The default grace period to wait for the background tasks on unload.
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(3);
+
 /// `Shiori<C, S>` represents the SHIORI runtime.
 ///
 /// The runtime is responsible for managing and executing services that handle
@@ -19,6 +29,25 @@ where
 
     /// The service that handles incoming SHIORI protocol requests.
     service: S,
+
+    /// The service that handles incoming SHIORI/2.x protocol requests.
+    ///
+    /// If it is not set, SHIORI/2.x requests are converted to SHIORI/3.0 requests and handled by `service`.
+    legacy_service: Option<BoxHandlerV2<C>>,
+
+    /// The driver of the background tasks, running while the context is loaded.
+    driver: Mutex<Option<Driver>>,
+
+    /// How long to wait for the background tasks to finish on unload.
+    grace_period: Duration,
+
+    /// The path the context is loaded from.
+    ///
+    /// The lock is held during `load`, `unload` and `reload` so that they do not run concurrently.
+    path: tokio::sync::Mutex<Option<PathBuf>>,
+
+    /// Set by `Context::request_reload` to reload the context after the request in progress.
+    reload_requested: Arc<AtomicBool>,
 }
 
 impl<C, S, Fut> Shiori<C, S>
@@ -27,32 +56,118 @@ where
     S: Service<C, v3::Request, Response = v3::Response, Error = v3::ShioriError, Future = Fut>,
     Fut: Future<Output = Result<S::Response, S::Error>>,
 {
+    /// Set the service that handles SHIORI/2.x protocol requests.
+    ///
+    /// By default, SHIORI/2.x requests are converted to SHIORI/3.0 requests and handled by the SHIORI/3.0 service,
+    /// and the responses are converted back to SHIORI/2.x.
+    pub fn with_legacy_service(mut self, service: BoxHandlerV2<C>) -> Self {
+        self.legacy_service = Some(service);
+        self
+    }
+
+    /// Set how long `unload` waits for the background tasks to finish after cancelling them.
+    ///
+    /// The tasks still running after the grace period are aborted. The default is 3 seconds.
+    pub fn with_grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
     /// Initialize the SHIORI runtime by loading context data from the provided path.
     ///
     /// In accordance with SHIORI protocol, if there are any associated data files, they should be
     /// located at this path. This method must be called before processing any requests.
+    ///
+    /// The context data is constructed with `ContextData::init`.
+    /// This also starts the thread that drives the background tasks spawned with `Context::tasks`.
     pub async fn load(&self, path: PathBuf) -> Result<(), S::Error> {
+        let mut loaded_path = self.path.lock().await;
         let mut context = self.context.write().await;
         match context.deref() {
             Some(_) => Err(S::Error::new("context already loaded")),
             None => {
-                let data = C::new(path)?;
-                context.replace(data.into());
+                let data = C::init(path.clone()).await?;
+                let driver = Driver::start().map_err(S::Error::from)?;
+                self.reload_requested.store(false, Ordering::Release);
+                context.replace(Context::with_parts(
+                    data,
+                    driver.tasks().clone(),
+                    StateMap::new(),
+                    self.reload_requested.clone(),
+                ));
+                self.driver
+                    .lock()
+                    .unwrap_or_else(|e| e.into_inner())
+                    .replace(driver);
+                loaded_path.replace(path);
 
                 Ok(())
             }
         }
     }
 
+    /// Reload the context data from the path it was loaded from, without unloading the SHIORI.
+    ///
+    /// The new context data is constructed with `ContextData::init` while the requests are still
+    /// processed with the old one, and then it is swapped in once the requests in progress have finished.
+    /// If `init` fails, the old context is kept and the error is returned.
+    ///
+    /// The state returned by `Context::state` is carried over to the new context.
+    /// The background tasks of the old context are cancelled in the same way as `unload`,
+    /// and then `ContextData::shutdown` of the old context is awaited and its result is returned.
+    ///
+    /// A handler must not await this method, because the request in progress holds the context.
+    /// Use `Context::request_reload` instead.
+    pub async fn reload(&self) -> Result<(), S::Error> {
+        let loaded_path = self.path.lock().await;
+        let Some(path) = loaded_path.clone() else {
+            return Err(S::Error::new("context not loaded"));
+        };
+
+        let data = C::init(path).await?;
+        let driver = Driver::start().map_err(S::Error::from)?;
+        let state = match self.context.read().await.deref() {
+            Some(ctx) => ctx.state().clone(),
+            None => StateMap::new(),
+        };
+        let new_context = Context::with_parts(
+            data,
+            driver.tasks().clone(),
+            state,
+            self.reload_requested.clone(),
+        );
+
+        let old_context = self.context.write().await.replace(new_context);
+        let old_driver = self
+            .driver
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .replace(driver);
+        if let Some(old_driver) = old_driver {
+            old_driver.shutdown(self.grace_period).await;
+        }
+        match old_context {
+            Some(ctx) => C::shutdown(&ctx).await,
+            None => Ok(()),
+        }
+    }
+
     /// Unload the context, removing all the context data.
     ///
+    /// The background tasks are cancelled and given the grace period to finish before they are aborted,
+    /// and then `ContextData::shutdown` is awaited. The context is removed even if `shutdown` fails.
     /// After this method is called, no requests can be processed until the context is loaded again.
     pub async fn unload(&self) -> Result<(), S::Error> {
+        let mut loaded_path = self.path.lock().await;
         let mut context = self.context.write().await;
-        match context.deref() {
-            Some(_) => {
-                context.take();
-                Ok(())
+        match context.take() {
+            Some(ctx) => {
+                loaded_path.take();
+                let driver = self.driver.lock().unwrap_or_else(|e| e.into_inner()).take();
+                if let Some(driver) = driver {
+                    driver.shutdown(self.grace_period).await;
+                }
+                C::shutdown(&ctx).await
             }
             None => Err(S::Error::new("context not loaded")),
         }
@@ -62,19 +177,81 @@ where
     ///
     /// This method accepts a request, passes it to the service for processing, and returns the
     /// service's response. The context must be loaded before this method is called.
+    ///
+    /// If a reload has been requested with `Context::request_reload`, the context is reloaded
+    /// before the request is processed, or after it if the reload is requested while processing it.
     pub async fn request(&self, request: v3::Request) -> S::Response {
         use v3::IntoResponse;
 
-        let ctx = self.context.read().await;
-        let result = match ctx.deref() {
-            Some(ctx) => self.service.call(ctx.clone(), request).await,
-            None => Err(v3::ShioriError::new("context not loaded")),
+        self.reload_if_requested().await;
+        let result = {
+            let ctx = self.context.read().await;
+            match ctx.deref() {
+                Some(ctx) => self.service.call(ctx.clone(), request).await,
+                None => Err(v3::ShioriError::new("context not loaded")),
+            }
         };
+        self.reload_if_requested().await;
+
         match result {
             Ok(resp) => resp,
             Err(err) => err.into_response(),
         }
     }
+
+    /// Process a SHIORI protocol request of any version.
+    ///
+    /// SHIORI/3.0 requests are passed to the service. SHIORI/2.x requests are passed to the legacy service if it is set,
+    /// otherwise they are converted to SHIORI/3.0 and the response is converted back to SHIORI/2.x.
+    ///
+    /// `GET Version` is the handshake by which the baseware determines the protocol version,
+    /// so without the legacy service it is answered with the SHIORI/3.0 response to switch the baseware to SHIORI/3.0.
+    pub async fn dispatch(&self, request: types::Request) -> types::Response {
+        match request {
+            types::Request::V2(request) => self.request_v2(request).await,
+            types::Request::V3(request) => self.request(request).await.into(),
+        }
+    }
+
+    async fn request_v2(&self, request: v2::Request) -> types::Response {
+        let method = request.method();
+        let version = request.version();
+
+        if let Some(service) = &self.legacy_service {
+            self.reload_if_requested().await;
+            let result = {
+                let ctx = self.context.read().await;
+                match ctx.deref() {
+                    Some(ctx) => service.call(ctx.clone(), request).await,
+                    None => Err(v3::ShioriError::new("context not loaded")),
+                }
+            };
+            self.reload_if_requested().await;
+
+            return match result {
+                Ok(resp) => resp.into(),
+                Err(err) => compat::to_v2_error_response(version, err).into(),
+            };
+        }
+
+        match compat::to_v3_request(request) {
+            Some(request) if method == v2::Method::GET_VERSION => {
+                self.request(request).await.into()
+            }
+            Some(request) => {
+                compat::to_v2_response(method, version, self.request(request).await).into()
+            }
+            None => compat::no_content(version).into(),
+        }
+    }
+
+    async fn reload_if_requested(&self) {
+        if self.reload_requested.swap(false, Ordering::AcqRel) {
+            if let Err(e) = self.reload().await {
+                log::error!("failed reload: {e}");
+            }
+        }
+    }
 }
 
 impl<C, S> From<S> for Shiori<C, S>
@@ -86,6 +263,11 @@ where
         Self {
             context: RwLock::new(None),
             service: value,
+            legacy_service: None,
+            driver: Mutex::new(None),
+            grace_period: DEFAULT_GRACE_PERIOD,
+            path: tokio::sync::Mutex::new(None),
+            reload_requested: Arc::new(AtomicBool::new(false)),
         }
     }
 }
@@ -93,7 +275,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::runtime::handler;
+    use crate::runtime::{box_handler, handler, BoxHandlerV3};
     use crate::types::v3;
 
     struct ShioriContext {
@@ -238,4 +420,360 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_dispatch_v2_request_with_compatibility() -> Result<(), v3::ShioriError> {
+        let shiori = Shiori::from(handler(
+            |_ctx: Context<ShioriContext>, req: v3::Request| async move {
+                assert_eq!(req.method(), v3::Method::GET);
+                assert_eq!(
+                    req.id().map(|v| v.text()).transpose()?.as_deref(),
+                    Some("OnBoot")
+                );
+
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::OK)
+                    .header(v3::HeaderName::VALUE, "\\h\\s0hello\\e")
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+
+        shiori.load(PathBuf::from(".")).await?;
+
+        let req = v2::Request::builder()
+            .method(v2::Method::GET_SENTENCE)
+            .version(v2::Version::SHIORI_22)
+            .header(v2::HeaderName::EVENT, "OnBoot")
+            .build()?;
+        let resp = match shiori.dispatch(req.into()).await {
+            types::Response::V2(resp) => resp,
+            types::Response::V3(_) => panic!("expected SHIORI/2.x response"),
+        };
+        assert_eq!(resp.version(), v2::Version::SHIORI_22);
+        assert_eq!(resp.status_code(), v2::StatusCode::OK);
+        assert_eq!(
+            resp.sentence().map(|v| v.text()).transpose()?.as_deref(),
+            Some("\\h\\s0hello\\e")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_v2_get_version_answers_v3() -> Result<(), v3::ShioriError> {
+        let shiori = Shiori::from(handler(
+            |_ctx: Context<ShioriContext>, req: v3::Request| async move {
+                assert_eq!(
+                    req.id().map(|v| v.text()).transpose()?.as_deref(),
+                    Some("version")
+                );
+
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::OK)
+                    .header(v3::HeaderName::VALUE, "1.0.0")
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+
+        shiori.load(PathBuf::from(".")).await?;
+
+        let req = v2::Request::builder()
+            .method(v2::Method::GET_VERSION)
+            .version(v2::Version::SHIORI_26)
+            .build()?;
+        let resp = shiori.dispatch(req.into()).await;
+        assert!(
+            matches!(resp, types::Response::V3(resp) if resp.status_code() == v3::StatusCode::OK)
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_v2_request_with_legacy_service() -> Result<(), v3::ShioriError> {
+        let shiori = Shiori::from(handler(
+            |_ctx: Context<ShioriContext>, _req: v3::Request| async { unimplemented!() },
+        ))
+        .with_legacy_service(box_handler(
+            |_ctx: Context<ShioriContext>, req: v2::Request| async move {
+                assert_eq!(req.method(), v2::Method::GET_WORD);
+
+                v2::Response::builder()
+                    .version(req.version())
+                    .status_code(v2::StatusCode::OK)
+                    .header(v2::HeaderName::WORD, "hoge")
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+
+        shiori.load(PathBuf::from(".")).await?;
+
+        let req = v2::Request::builder()
+            .method(v2::Method::GET_WORD)
+            .version(v2::Version::SHIORI_20)
+            .header(v2::HeaderName::TYPE, "\\ms")
+            .build()?;
+        let resp = match shiori.dispatch(req.into()).await {
+            types::Response::V2(resp) => resp,
+            types::Response::V3(_) => panic!("expected SHIORI/2.x response"),
+        };
+        assert_eq!(resp.version(), v2::Version::SHIORI_20);
+        assert_eq!(
+            resp.word().map(|v| v.text()).transpose()?.as_deref(),
+            Some("hoge")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_background_tasks_are_cancelled_on_unload() -> Result<(), v3::ShioriError> {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let shiori = Shiori::from(handler(
+            move |ctx: Context<ShioriContext>, _req: v3::Request| {
+                let tx = tx.clone();
+                async move {
+                    ctx.tasks().spawn(|token| async move {
+                        let _ = tx.send("started");
+                        token.cancelled().await;
+                        let _ = tx.send("cancelled");
+                    });
+                    v3::Response::builder()
+                        .version(v3::Version::SHIORI_30)
+                        .status_code(v3::StatusCode::NO_CONTENT)
+                        .build()
+                        .map_err(v3::ShioriError::from)
+                }
+            },
+        ))
+        .with_grace_period(std::time::Duration::from_secs(1));
+
+        shiori.load(PathBuf::from(".")).await?;
+        let req = v3::Request::builder()
+            .method(v3::Method::NOTIFY)
+            .version(v3::Version::SHIORI_30)
+            .build()?;
+        shiori.request(req).await;
+        assert_eq!(rx.recv().await, Some("started"));
+
+        shiori.unload().await?;
+        assert_eq!(rx.try_recv(), Ok("cancelled"));
+
+        Ok(())
+    }
+
+    static SHUTDOWN_CALLED: std::sync::atomic::AtomicBool =
+        std::sync::atomic::AtomicBool::new(false);
+
+    struct AsyncContext {
+        entries: usize,
+    }
+    impl ContextData for AsyncContext {
+        type Error = v3::ShioriError;
+
+        fn new(_path: PathBuf) -> Result<Self, v3::ShioriError> {
+            unreachable!("init is overridden")
+        }
+
+        async fn init(path: PathBuf) -> Result<Self, v3::ShioriError> {
+            let mut dir = tokio::fs::read_dir(path).await?;
+            let mut entries = 0;
+            while dir.next_entry().await?.is_some() {
+                entries += 1;
+            }
+            Ok(Self { entries })
+        }
+
+        async fn shutdown(&self) -> Result<(), v3::ShioriError> {
+            tokio::task::yield_now().await;
+            SHUTDOWN_CALLED.store(true, std::sync::atomic::Ordering::SeqCst);
+            Err(v3::ShioriError::new("failed to save"))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_init_and_shutdown() -> Result<(), v3::ShioriError> {
+        let shiori = Shiori::from(handler(
+            |ctx: Context<AsyncContext>, _req: v3::Request| async move {
+                assert!(ctx.entries > 0);
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::NO_CONTENT)
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+
+        let result = shiori.load(PathBuf::from("./not_found")).await;
+        assert!(result.is_err());
+
+        shiori.load(PathBuf::from(".")).await?;
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .build()?;
+        let resp = shiori.request(req).await;
+        assert_eq!(resp.status_code(), v3::StatusCode::NO_CONTENT);
+
+        let result = shiori.unload().await;
+        assert_eq!(result.unwrap_err().to_string(), "failed to save");
+        assert!(SHUTDOWN_CALLED.load(std::sync::atomic::Ordering::SeqCst));
+
+        // the context is removed even if shutdown failed
+        let result = shiori.unload().await;
+        assert_eq!(result.unwrap_err().to_string(), "context not loaded");
+
+        Ok(())
+    }
+
+    static GENERATION: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+    /// Counts how many times the context data is constructed.
+    struct GenerationContext {
+        generation: usize,
+    }
+    impl ContextData for GenerationContext {
+        type Error = v3::ShioriError;
+
+        fn new(_path: PathBuf) -> Result<Self, v3::ShioriError> {
+            Ok(Self {
+                generation: GENERATION.fetch_add(1, Ordering::SeqCst),
+            })
+        }
+    }
+
+    fn generation_shiori() -> Shiori<GenerationContext, BoxHandlerV3<GenerationContext>> {
+        Shiori::from(box_handler(
+            |ctx: Context<GenerationContext>, req: v3::Request| async move {
+                match req.id().map(|v| v.text()).transpose()?.as_deref() {
+                    Some("OnShioriReload") => ctx.request_reload(),
+                    Some("OnSlow") => tokio::time::sleep(Duration::from_millis(50)).await,
+                    _ => {}
+                }
+
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::OK)
+                    .header(v3::HeaderName::VALUE, ctx.generation.to_string())
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ))
+    }
+
+    async fn generation_of(
+        shiori: &Shiori<GenerationContext, BoxHandlerV3<GenerationContext>>,
+        id: &str,
+    ) -> Result<String, v3::ShioriError> {
+        let req = v3::Request::builder()
+            .method(v3::Method::GET)
+            .version(v3::Version::SHIORI_30)
+            .header(v3::HeaderName::ID, id)
+            .build()?;
+        let resp = shiori.request(req).await;
+        assert_eq!(resp.status_code(), v3::StatusCode::OK);
+        Ok(resp
+            .value()
+            .map(|v| v.text())
+            .transpose()?
+            .unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_reload() -> Result<(), v3::ShioriError> {
+        let shiori = generation_shiori();
+        shiori.load(PathBuf::from(".")).await?;
+        let before = generation_of(&shiori, "OnBoot").await?;
+
+        shiori.reload().await?;
+        let after = generation_of(&shiori, "OnBoot").await?;
+        assert_ne!(before, after);
+
+        shiori.unload().await?;
+        let result = shiori.reload().await;
+        assert_eq!(result.unwrap_err().to_string(), "context not loaded");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_reload_waits_for_requests_in_progress() -> Result<(), v3::ShioriError> {
+        let shiori = generation_shiori();
+        shiori.load(PathBuf::from(".")).await?;
+        let before = generation_of(&shiori, "OnBoot").await?;
+
+        let (in_progress, reloaded) = tokio::join!(generation_of(&shiori, "OnSlow"), async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            shiori.reload().await
+        });
+        reloaded?;
+        assert_eq!(in_progress?, before);
+        assert_ne!(generation_of(&shiori, "OnBoot").await?, before);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_request_reload_from_handler() -> Result<(), v3::ShioriError> {
+        let shiori = generation_shiori();
+        shiori.load(PathBuf::from(".")).await?;
+        let before = generation_of(&shiori, "OnBoot").await?;
+
+        // the request that asks for the reload is answered with the old context
+        assert_eq!(generation_of(&shiori, "OnShioriReload").await?, before);
+        assert_ne!(generation_of(&shiori, "OnBoot").await?, before);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_state_survives_reload() -> Result<(), v3::ShioriError> {
+        let shiori = Shiori::from(handler(
+            |ctx: Context<ShioriContext>, _req: v3::Request| async move {
+                let count = ctx
+                    .state()
+                    .key::<u32>("talk_count")
+                    .update_or_default(|v| {
+                        *v += 1;
+                        *v
+                    })
+                    .await;
+
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::OK)
+                    .header(v3::HeaderName::VALUE, count.to_string())
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+        let count = || async {
+            let req = v3::Request::builder()
+                .method(v3::Method::GET)
+                .version(v3::Version::SHIORI_30)
+                .build()?;
+            let resp = shiori.request(req).await;
+            resp.value()
+                .map(|v| v.text())
+                .transpose()
+                .map_err(v3::ShioriError::from)
+        };
+
+        shiori.load(PathBuf::from(".")).await?;
+        assert_eq!(count().await?.as_deref(), Some("1"));
+        shiori.reload().await?;
+        assert_eq!(count().await?.as_deref(), Some("2"));
+
+        // the state is cleared on unload
+        shiori.unload().await?;
+        shiori.load(PathBuf::from(".")).await?;
+        assert_eq!(count().await?.as_deref(), Some("1"));
+
+        Ok(())
+    }
 }
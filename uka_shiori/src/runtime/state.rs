@@ -0,0 +1,350 @@
+use std::any::{Any, TypeId};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock as SyncRwLock};
+use tokio::sync::{OwnedRwLockReadGuard, OwnedRwLockWriteGuard, RwLock};
+
+type SlotValue = Arc<dyn Any + Send + Sync>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct SlotId {
+    type_id: TypeId,
+    key: Option<Cow<'static, str>>,
+}
+
+/// `StateMap` is the container of the mutable ghost state, such as talk counters, the user name and affection.
+///
+/// Each value is stored in its own slot, identified by its type, or by its type and a key.
+/// The slots are guarded by async-aware read-write locks, so handlers can mutate the state safely
+/// even when the runtime is multi-threaded, and a lock can be held across `.await`.
+///
+/// `StateMap` is obtained from [`Context::state`](crate::runtime::Context::state) and shared between clones.
+/// The state survives `Shiori::reload`, and it is cleared when the SHIORI is unloaded.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_shiori::runtime::StateMap;
+/// #
+/// #[derive(Clone, Default)]
+/// struct UserName(String);
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let state = StateMap::new();
+///
+/// // by type
+/// state.insert(UserName("ユーザ".to_string())).await;
+/// assert_eq!(state.get::<UserName>().await.map(|v| v.0).as_deref(), Some("ユーザ"));
+///
+/// // by key
+/// let talk_count = state.key::<u32>("talk_count");
+/// talk_count.update_or_default(|v| *v += 1).await;
+/// assert_eq!(talk_count.get().await, Some(1));
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct StateMap {
+    slots: Arc<SyncRwLock<HashMap<SlotId, SlotValue>>>,
+}
+
+impl StateMap {
+    /// Constructs an empty state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the slot of the value identified by the type `T`.
+    pub fn slot<T: Send + Sync + 'static>(&self) -> Slot<'_, T> {
+        Slot::new(self, None)
+    }
+
+    /// Returns the slot of the value identified by the type `T` and the key.
+    ///
+    /// The same key with different types refers to different slots.
+    pub fn key<T: Send + Sync + 'static>(&self, key: impl Into<Cow<'static, str>>) -> Slot<'_, T> {
+        Slot::new(self, Some(key.into()))
+    }
+
+    /// Insert the value of type `T`, returning the previous value if any.
+    pub async fn insert<T: Send + Sync + 'static>(&self, value: T) -> Option<T> {
+        self.slot().insert(value).await
+    }
+
+    /// Returns a clone of the value of type `T`.
+    pub async fn get<T: Clone + Send + Sync + 'static>(&self) -> Option<T> {
+        self.slot().get().await
+    }
+
+    /// Lock the value of type `T` for reading.
+    pub async fn read<T: Send + Sync + 'static>(&self) -> Option<OwnedRwLockReadGuard<T>> {
+        self.slot().read().await
+    }
+
+    /// Lock the value of type `T` for writing.
+    pub async fn write<T: Send + Sync + 'static>(&self) -> Option<OwnedRwLockWriteGuard<T>> {
+        self.slot().write().await
+    }
+
+    /// Update the value of type `T` with the function, returning its result.
+    ///
+    /// Returns `None` without calling the function if there is no value.
+    pub async fn update<T, F, R>(&self, f: F) -> Option<R>
+    where
+        T: Send + Sync + 'static,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.slot().update(f).await
+    }
+
+    /// Update the value of type `T` with the function, inserting the default value if there is no value.
+    pub async fn update_or_default<T, F, R>(&self, f: F) -> R
+    where
+        T: Default + Send + Sync + 'static,
+        F: FnOnce(&mut T) -> R,
+    {
+        self.slot().update_or_default(f).await
+    }
+
+    /// Remove the value of type `T`, returning true if it existed.
+    pub fn remove<T: Send + Sync + 'static>(&self) -> bool {
+        self.slot::<T>().remove()
+    }
+
+    /// Returns true if there is the value of type `T`.
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.slot::<T>().contains()
+    }
+
+    /// Returns the number of the values.
+    pub fn len(&self) -> usize {
+        self.slots.read().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// `Slot<'a, T>` is the handle to a value of type `T` in [`StateMap`].
+///
+/// The slot is looked up on each call, so the handle does not keep the value alive.
+pub struct Slot<'a, T> {
+    state: &'a StateMap,
+    id: SlotId,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<'a, T: Send + Sync + 'static> Slot<'a, T> {
+    fn new(state: &'a StateMap, key: Option<Cow<'static, str>>) -> Self {
+        Self {
+            state,
+            id: SlotId {
+                type_id: TypeId::of::<T>(),
+                key,
+            },
+            _value: PhantomData,
+        }
+    }
+
+    fn lock(&self) -> Option<Arc<RwLock<T>>> {
+        let slots = self.state.slots.read().unwrap_or_else(|e| e.into_inner());
+        slots.get(&self.id).cloned().map(downcast)
+    }
+
+    fn lock_or_insert_with(&self, f: impl FnOnce() -> T) -> Arc<RwLock<T>> {
+        let mut slots = self.state.slots.write().unwrap_or_else(|e| e.into_inner());
+        let value = slots
+            .entry(self.id.clone())
+            .or_insert_with(|| Arc::new(RwLock::new(f())));
+        downcast(value.clone())
+    }
+
+    /// Insert the value, returning the previous value if any.
+    ///
+    /// If the value is locked, this waits until the lock is released.
+    pub async fn insert(&self, value: T) -> Option<T> {
+        let lock = {
+            let mut slots = self.state.slots.write().unwrap_or_else(|e| e.into_inner());
+            match slots.get(&self.id) {
+                Some(lock) => downcast(lock.clone()),
+                None => {
+                    slots.insert(self.id.clone(), Arc::new(RwLock::new(value)));
+                    return None;
+                }
+            }
+        };
+        let mut previous = lock.write().await;
+        Some(std::mem::replace(&mut *previous, value))
+    }
+
+    /// Returns a clone of the value.
+    pub async fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        match self.lock() {
+            Some(lock) => {
+                let value = lock.read().await;
+                Some(value.clone())
+            }
+            None => None,
+        }
+    }
+
+    /// Lock the value for reading.
+    pub async fn read(&self) -> Option<OwnedRwLockReadGuard<T>> {
+        match self.lock() {
+            Some(lock) => Some(lock.read_owned().await),
+            None => None,
+        }
+    }
+
+    /// Lock the value for writing.
+    pub async fn write(&self) -> Option<OwnedRwLockWriteGuard<T>> {
+        match self.lock() {
+            Some(lock) => Some(lock.write_owned().await),
+            None => None,
+        }
+    }
+
+    /// Update the value with the function, returning its result.
+    ///
+    /// Returns `None` without calling the function if there is no value.
+    pub async fn update<F, R>(&self, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        match self.lock() {
+            Some(lock) => {
+                let mut value = lock.write().await;
+                Some(f(&mut value))
+            }
+            None => None,
+        }
+    }
+
+    /// Update the value with the function, inserting the default value if there is no value.
+    pub async fn update_or_default<F, R>(&self, f: F) -> R
+    where
+        T: Default,
+        F: FnOnce(&mut T) -> R,
+    {
+        let lock = self.lock_or_insert_with(T::default);
+        let mut value = lock.write().await;
+        f(&mut value)
+    }
+
+    /// Remove the value, returning true if it existed.
+    ///
+    /// The guards already acquired keep the removed value alive until they are dropped.
+    pub fn remove(&self) -> bool {
+        let mut slots = self.state.slots.write().unwrap_or_else(|e| e.into_inner());
+        slots.remove(&self.id).is_some()
+    }
+
+    /// Returns true if there is the value.
+    pub fn contains(&self) -> bool {
+        let slots = self.state.slots.read().unwrap_or_else(|e| e.into_inner());
+        slots.contains_key(&self.id)
+    }
+}
+
+fn downcast<T: Send + Sync + 'static>(value: SlotValue) -> Arc<RwLock<T>> {
+    // The slot id contains the type id of `T`, so the value is always `RwLock<T>`.
+    value
+        .downcast::<RwLock<T>>()
+        .unwrap_or_else(|_| unreachable!("the slot holds a value of another type"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Affection(i32);
+
+    #[tokio::test]
+    async fn test_insert_and_get_by_type() {
+        let state = StateMap::new();
+        assert!(state.is_empty());
+        assert_eq!(state.get::<Affection>().await, None);
+
+        assert_eq!(state.insert(Affection(10)).await, None);
+        assert_eq!(state.insert(Affection(20)).await, Some(Affection(10)));
+        assert_eq!(state.get::<Affection>().await, Some(Affection(20)));
+        assert_eq!(state.len(), 1);
+
+        assert!(state.contains::<Affection>());
+        assert!(state.remove::<Affection>());
+        assert!(!state.remove::<Affection>());
+        assert!(!state.contains::<Affection>());
+    }
+
+    #[tokio::test]
+    async fn test_keys_are_separated_by_type() {
+        let state = StateMap::new();
+        state.key::<u32>("count").insert(1).await;
+        state.key::<String>("count").insert("one".to_string()).await;
+        state.insert(2u32).await;
+
+        assert_eq!(state.key::<u32>("count").get().await, Some(1));
+        assert_eq!(
+            state.key::<String>("count").get().await.as_deref(),
+            Some("one")
+        );
+        assert_eq!(state.get::<u32>().await, Some(2));
+        assert_eq!(state.key::<u32>("other").get().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_update() {
+        let state = StateMap::new();
+        assert_eq!(state.update(|v: &mut Affection| v.0 += 1).await, None);
+
+        let value = state
+            .update_or_default(|v: &mut Affection| {
+                v.0 += 1;
+                v.0
+            })
+            .await;
+        assert_eq!(value, 1);
+
+        state.update(|v: &mut Affection| v.0 *= 10).await;
+        assert_eq!(
+            *state.read::<Affection>().await.expect("inserted"),
+            Affection(10)
+        );
+
+        let mut guard = state.write::<Affection>().await.expect("inserted");
+        guard.0 = -1;
+        drop(guard);
+        assert_eq!(state.get::<Affection>().await, Some(Affection(-1)));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_update_from_multiple_threads() {
+        let state = StateMap::new();
+        let tasks = (0..64)
+            .map(|_| {
+                let state = state.clone();
+                tokio::spawn(async move {
+                    for _ in 0..10 {
+                        state
+                            .key::<u32>("talk_count")
+                            .update_or_default(|v| *v += 1)
+                            .await;
+                        tokio::task::yield_now().await;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.expect("task must not panic");
+        }
+
+        assert_eq!(state.key::<u32>("talk_count").get().await, Some(640));
+    }
+}
@@ -0,0 +1,598 @@
+#[cfg(feature = "json")]
+mod json;
+mod text;
+
+use crate::runtime::task::Tasks;
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock, Weak};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::task::JoinHandle;
+
+#[cfg(feature = "json")]
+pub use json::JsonFormat;
+pub use text::TextFormat;
+
+/// The base name of the store file, the extension is given by the format.
+const DEFAULT_FILE_STEM: &str = "uka_savedata";
+
+/// The values of the store, ordered by key.
+pub type Values = BTreeMap<String, String>;
+
+type Migration = Box<dyn Fn(&mut Values) + Send + Sync>;
+
+/// The stores that are open in the process, keyed by the absolute path of the store file.
+static OPEN_STORES: Mutex<BTreeMap<PathBuf, Weak<Inner>>> = Mutex::new(BTreeMap::new());
+
+/// The sequence number that makes the name of the temporary file unique for each save.
+static SAVE_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Error that can occur when loading or saving the store.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("failed to access the store file: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("invalid store file: {0}")]
+    InvalidFormat(String),
+
+    #[cfg(feature = "json")]
+    #[error("invalid JSON store file: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unsupported store version {found}, the latest version is {latest}")]
+    UnsupportedVersion { found: u32, latest: u32 },
+
+    #[error("the store file is already open with version {open}, not {requested}")]
+    VersionMismatch { open: u32, requested: u32 },
+}
+
+/// `Snapshot` is the content of the store file, the values with the schema version.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    /// The schema version of the values. The file without the version is version 0.
+    pub version: u32,
+
+    /// The values of the store.
+    pub values: Values,
+}
+
+/// `Format` encodes and decodes the store file.
+///
+/// [`TextFormat`] is the default, and [`JsonFormat`] is available with the `json` feature.
+pub trait Format: Send + Sync + 'static {
+    /// The extension of the store file, without the leading dot.
+    fn extension(&self) -> &'static str;
+
+    /// Encode the snapshot into the content of the store file.
+    fn encode(&self, snapshot: &Snapshot) -> Result<Vec<u8>, Error>;
+
+    /// Decode the content of the store file.
+    fn decode(&self, bytes: &[u8]) -> Result<Snapshot, Error>;
+}
+
+/// `Store` is the key/value store of the ghost variables, saved into the ghost directory.
+///
+/// The store is loaded when the context data is constructed, and it is shared between clones.
+/// Opening a store file that is already open in the process returns the store sharing its values,
+/// so the context data constructed by `Shiori::reload` keeps the changes that the old context has not saved yet.
+/// It is saved atomically: the content is written into a temporary file first, which then replaces the store file,
+/// so the save data is not corrupted even if the process is killed while saving.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::path::PathBuf;
+/// # use std::time::Duration;
+/// # use uka_shiori::runtime::{Context, ContextData};
+/// # use uka_shiori::runtime::store::Store;
+/// # use uka_shiori::types::v3;
+/// #
+/// struct Data {
+///     store: Store,
+/// }
+///
+/// impl ContextData for Data {
+///     type Error = v3::ShioriError;
+///
+///     fn new(path: PathBuf) -> Result<Self, Self::Error> {
+///         let store = Store::builder()
+///             .version(2)
+///             // version 1 stored the user name as `name`
+///             .migration(1, |values| {
+///                 if let Some(name) = values.remove("name") {
+///                     values.insert("user_name".to_string(), name);
+///                 }
+///             })
+///             .open(path)?;
+///         Ok(Self { store })
+///     }
+///
+///     async fn shutdown(&self) -> Result<(), Self::Error> {
+///         self.store.save().await?;
+///         Ok(())
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), v3::ShioriError> {
+/// # let dir = std::env::temp_dir().join("uka_shiori_store_doc");
+/// # std::fs::create_dir_all(&dir)?;
+/// let ctx = Context::from(Data::new(dir)?);
+/// ctx.store.autosave(ctx.tasks(), Duration::from_secs(60));
+///
+/// let count = ctx.store.get_as::<u32>("talk_count").unwrap_or_default();
+/// ctx.store.set("talk_count", count + 1);
+/// # ctx.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Store {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    path: PathBuf,
+    format: Box<dyn Format>,
+    version: u32,
+    values: RwLock<Values>,
+    dirty: AtomicBool,
+    save_lock: tokio::sync::Mutex<()>,
+}
+
+impl Store {
+    /// Constructs a builder of the store.
+    pub fn builder() -> Builder {
+        Builder::new()
+    }
+
+    /// Load the store from the directory with the default settings.
+    ///
+    /// The directory is usually the ghost directory passed to `ContextData::new`.
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, Error> {
+        Builder::new().open(dir)
+    }
+
+    /// Returns the path of the store file.
+    pub fn path(&self) -> &Path {
+        &self.inner.path
+    }
+
+    /// Returns the schema version of the store.
+    pub fn version(&self) -> u32 {
+        self.inner.version
+    }
+
+    /// Returns the value of the key.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.read().get(key).cloned()
+    }
+
+    /// Returns the value of the key parsed as `T`.
+    ///
+    /// Returns `None` if there is no value or it cannot be parsed.
+    pub fn get_as<T: FromStr>(&self, key: &str) -> Option<T> {
+        self.read().get(key).and_then(|v| v.parse().ok())
+    }
+
+    /// Set the value of the key, returning the previous value if any.
+    pub fn set(&self, key: impl Into<String>, value: impl ToString) -> Option<String> {
+        let mut values = self.write();
+        self.inner.dirty.store(true, Ordering::Release);
+        values.insert(key.into(), value.to_string())
+    }
+
+    /// Remove the value of the key, returning it if any.
+    pub fn remove(&self, key: &str) -> Option<String> {
+        let mut values = self.write();
+        let previous = values.remove(key);
+        if previous.is_some() {
+            self.inner.dirty.store(true, Ordering::Release);
+        }
+        previous
+    }
+
+    /// Returns true if there is the value of the key.
+    pub fn contains(&self, key: &str) -> bool {
+        self.read().contains_key(key)
+    }
+
+    /// Returns the keys in order.
+    pub fn keys(&self) -> Vec<String> {
+        self.read().keys().cloned().collect()
+    }
+
+    /// Returns the number of the values.
+    pub fn len(&self) -> usize {
+        self.read().len()
+    }
+
+    /// Returns true if there are no values.
+    pub fn is_empty(&self) -> bool {
+        self.read().is_empty()
+    }
+
+    /// Returns true if there are changes that have not been saved.
+    pub fn is_dirty(&self) -> bool {
+        self.inner.dirty.load(Ordering::Acquire)
+    }
+
+    /// Save the store into the file atomically.
+    pub async fn save(&self) -> Result<(), Error> {
+        let _saving = self.inner.save_lock.lock().await;
+
+        let snapshot = {
+            let values = self.write();
+            self.inner.dirty.store(false, Ordering::Release);
+            Snapshot {
+                version: self.inner.version,
+                values: values.clone(),
+            }
+        };
+
+        let result = self.write_file(&snapshot).await;
+        if result.is_err() {
+            self.inner.dirty.store(true, Ordering::Release);
+        }
+        result
+    }
+
+    /// Save the store only if there are changes that have not been saved.
+    pub async fn save_if_dirty(&self) -> Result<(), Error> {
+        match self.is_dirty() {
+            true => self.save().await,
+            false => Ok(()),
+        }
+    }
+
+    /// Spawn a background task that saves the changes periodically.
+    ///
+    /// The task saves the remaining changes once more when the SHIORI is unloaded.
+    /// The failures are logged and the task keeps running.
+    pub fn autosave(&self, tasks: &Tasks, interval: Duration) -> JoinHandle<()> {
+        let store = self.clone();
+        tasks.spawn(move |token| async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.tick().await;
+            loop {
+                tokio::select! {
+                    _ = token.cancelled() => break,
+                    _ = ticker.tick() => {
+                        if let Err(e) = store.save_if_dirty().await {
+                            log::error!("failed autosave {}: {e}", store.path().display());
+                        }
+                    }
+                }
+            }
+            if let Err(e) = store.save_if_dirty().await {
+                log::error!("failed save {}: {e}", store.path().display());
+            }
+        })
+    }
+
+    async fn write_file(&self, snapshot: &Snapshot) -> Result<(), Error> {
+        let bytes = self.inner.format.encode(snapshot)?;
+
+        let mut temp_name = self.inner.path.as_os_str().to_os_string();
+        temp_name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            SAVE_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        ));
+        let temp_path = PathBuf::from(temp_name);
+
+        let mut file = tokio::fs::File::create(&temp_path).await?;
+        file.write_all(&bytes).await?;
+        file.sync_all().await?;
+        drop(file);
+
+        tokio::fs::rename(&temp_path, &self.inner.path).await?;
+        Ok(())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, Values> {
+        self.inner.values.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, Values> {
+        self.inner.values.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Builder for Store.
+pub struct Builder {
+    file_name: Option<String>,
+    format: Box<dyn Format>,
+    version: u32,
+    migrations: HashMap<u32, Migration>,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            file_name: None,
+            format: Box::new(TextFormat),
+            version: 1,
+            migrations: HashMap::new(),
+        }
+    }
+
+    /// Set the format of the store file. The default is [`TextFormat`].
+    pub fn format(mut self, format: impl Format) -> Self {
+        self.format = Box::new(format);
+        self
+    }
+
+    /// Set the file name of the store. The default is `uka_savedata` with the extension of the format.
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// Set the latest schema version. The default is 1.
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Register the migration from the version `from` to the next version.
+    ///
+    /// When the store file is older than the latest version, the migrations are applied in order.
+    /// The versions without a migration are upgraded as they are.
+    pub fn migration<F>(mut self, from: u32, f: F) -> Self
+    where
+        F: Fn(&mut Values) + Send + Sync + 'static,
+    {
+        self.migrations.insert(from, Box::new(f));
+        self
+    }
+
+    /// Load the store from the directory.
+    ///
+    /// If the store file does not exist, the store is empty and the file is created when it is saved.
+    /// If the store file is already open in the process, the store sharing its values is returned
+    /// instead of reading the file, and it must have been opened with the same version.
+    pub fn open(self, dir: impl AsRef<Path>) -> Result<Store, Error> {
+        let file_name = self
+            .file_name
+            .unwrap_or_else(|| format!("{DEFAULT_FILE_STEM}.{}", self.format.extension()));
+        let path = dir.as_ref().join(file_name);
+        let key = std::path::absolute(&path).unwrap_or_else(|_| path.clone());
+
+        // The lock is held while reading the file, so that the file is not loaded twice.
+        let mut open_stores = OPEN_STORES.lock().unwrap_or_else(|e| e.into_inner());
+        open_stores.retain(|_, inner| inner.strong_count() > 0);
+        if let Some(inner) = open_stores.get(&key).and_then(Weak::upgrade) {
+            return match inner.version {
+                version if version > self.version => Err(Error::UnsupportedVersion {
+                    found: version,
+                    latest: self.version,
+                }),
+                version if version < self.version => Err(Error::VersionMismatch {
+                    open: version,
+                    requested: self.version,
+                }),
+                _ => Ok(Store { inner }),
+            };
+        }
+
+        let snapshot = match std::fs::read(&path) {
+            Ok(bytes) => self.format.decode(&bytes)?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Snapshot {
+                version: self.version,
+                values: Values::new(),
+            },
+            Err(e) => return Err(e.into()),
+        };
+        if snapshot.version > self.version {
+            return Err(Error::UnsupportedVersion {
+                found: snapshot.version,
+                latest: self.version,
+            });
+        }
+
+        let mut values = snapshot.values;
+        for version in snapshot.version..self.version {
+            if let Some(migration) = self.migrations.get(&version) {
+                migration(&mut values);
+            }
+        }
+
+        let inner = Arc::new(Inner {
+            path,
+            format: self.format,
+            version: self.version,
+            values: RwLock::new(values),
+            dirty: AtomicBool::new(snapshot.version < self.version),
+            save_lock: tokio::sync::Mutex::new(()),
+        });
+        open_stores.insert(key, Arc::downgrade(&inner));
+        Ok(Store { inner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::{handler, Context, ContextData, Shiori};
+    use crate::types::v3;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("uka_shiori_store_{name}"));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("failed to create temp dir");
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_save_and_open() -> Result<(), Error> {
+        let dir = temp_dir("save_and_open");
+
+        let store = Store::open(&dir)?;
+        assert!(store.is_empty());
+        assert!(!store.is_dirty());
+
+        store.set("user_name", "ユーザ");
+        store.set("talk_count", 10);
+        assert!(store.is_dirty());
+        store.save().await?;
+        assert!(!store.is_dirty());
+        assert!(dir.join("uka_savedata.txt").exists());
+        assert_eq!(
+            fs::read_dir(&dir)?.count(),
+            1,
+            "temporary file must be removed"
+        );
+
+        drop(store);
+        let store = Store::open(&dir)?;
+        assert_eq!(store.get("user_name").as_deref(), Some("ユーザ"));
+        assert_eq!(store.get_as::<u32>("talk_count"), Some(10));
+        assert_eq!(store.get_as::<u32>("user_name"), None);
+        assert_eq!(store.keys(), vec!["talk_count", "user_name"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_migration() -> Result<(), Error> {
+        let dir = temp_dir("migration");
+        fs::write(dir.join("uka_savedata.txt"), "#version=1\nname=ユーザ\n")?;
+
+        let store = Store::builder()
+            .version(3)
+            .migration(1, |values| {
+                if let Some(name) = values.remove("name") {
+                    values.insert("user_name".to_string(), name);
+                }
+            })
+            .migration(2, |values| {
+                values.insert("affection".to_string(), "0".to_string());
+            })
+            .open(&dir)?;
+        assert_eq!(store.version(), 3);
+        assert_eq!(store.get("user_name").as_deref(), Some("ユーザ"));
+        assert_eq!(store.get("affection").as_deref(), Some("0"));
+        assert!(store.is_dirty());
+
+        store.save().await?;
+        let content = fs::read_to_string(dir.join("uka_savedata.txt"))?;
+        assert!(content.starts_with("#version=3\n"));
+
+        let result = Store::builder().version(2).open(&dir);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedVersion {
+                found: 3,
+                latest: 2
+            })
+        ));
+
+        drop(store);
+        let result = Store::builder().version(2).open(&dir);
+        assert!(matches!(
+            result,
+            Err(Error::UnsupportedVersion {
+                found: 3,
+                latest: 2
+            })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_open_shares_open_store() -> Result<(), Error> {
+        let dir = temp_dir("shares_open_store");
+        let store = Store::open(&dir)?;
+        store.set("talk_count", 1);
+
+        let reopened = Store::open(&dir)?;
+        assert_eq!(reopened.get_as::<u32>("talk_count"), Some(1));
+        assert!(matches!(
+            Store::builder().version(2).open(&dir),
+            Err(Error::VersionMismatch {
+                open: 1,
+                requested: 2
+            })
+        ));
+
+        drop(store);
+        drop(reopened);
+        assert!(Store::open(&dir)?.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_autosave_saves_on_unload() -> Result<(), Error> {
+        let dir = temp_dir("autosave");
+        let store = Store::builder().file_name("save.txt").open(&dir)?;
+
+        let tasks = Tasks::current();
+        store.autosave(&tasks, Duration::from_secs(60));
+        store.set("talk_count", 1);
+        tasks.shutdown(Duration::from_secs(1)).await;
+
+        assert!(!store.is_dirty());
+        let store = Store::builder().file_name("save.txt").open(&dir)?;
+        assert_eq!(store.get_as::<u32>("talk_count"), Some(1));
+        Ok(())
+    }
+
+    struct Ghost {
+        store: Store,
+    }
+
+    impl ContextData for Ghost {
+        type Error = v3::ShioriError;
+
+        fn new(path: PathBuf) -> Result<Self, Self::Error> {
+            let store = Store::open(path).map_err(v3::ShioriError::new)?;
+            Ok(Self { store })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_autosave_keeps_values_across_reload() -> Result<(), v3::ShioriError> {
+        let dir = temp_dir("reload");
+        let shiori = Shiori::from(handler(
+            |ctx: Context<Ghost>, req: v3::Request| async move {
+                if ctx.tasks().is_empty() {
+                    ctx.store.autosave(ctx.tasks(), Duration::from_secs(60));
+                }
+                let id = req
+                    .id()
+                    .and_then(|v| v.text_with_charset(req.charset()).ok())
+                    .unwrap_or_default();
+                ctx.store.set(id, 1);
+                v3::Response::builder()
+                    .version(v3::Version::SHIORI_30)
+                    .status_code(v3::StatusCode::NO_CONTENT)
+                    .build()
+                    .map_err(v3::ShioriError::from)
+            },
+        ));
+        let request = |id: &str| {
+            v3::Request::builder()
+                .method(v3::Method::NOTIFY)
+                .version(v3::Version::SHIORI_30)
+                .header(v3::HeaderName::ID, id)
+                .build()
+        };
+
+        shiori.load(dir.clone()).await?;
+        shiori.request(request("OnBoot")?).await;
+        shiori.reload().await?;
+        shiori.request(request("OnClose")?).await;
+        shiori.unload().await?;
+
+        let store = Store::open(&dir).map_err(v3::ShioriError::new)?;
+        assert_eq!(store.get_as::<u32>("OnBoot"), Some(1));
+        assert_eq!(store.get_as::<u32>("OnClose"), Some(1));
+        Ok(())
+    }
+}
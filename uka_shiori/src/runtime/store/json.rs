@@ -0,0 +1,77 @@
+use super::{Error, Format, Snapshot, Values};
+use serde_json::{json, Value};
+
+/// `JsonFormat` stores the values as a JSON object with the schema version.
+///
+/// ```json
+/// {
+///   "version": 1,
+///   "values": {
+///     "talk_count": "10",
+///     "user_name": "ユーザ"
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct JsonFormat;
+
+impl Format for JsonFormat {
+    fn extension(&self) -> &'static str {
+        "json"
+    }
+
+    fn encode(&self, snapshot: &Snapshot) -> Result<Vec<u8>, Error> {
+        let value = json!({
+            "version": snapshot.version,
+            "values": snapshot.values,
+        });
+        Ok(serde_json::to_vec_pretty(&value)?)
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Snapshot, Error> {
+        let mut root = match serde_json::from_slice(bytes)? {
+            Value::Object(root) => root,
+            _ => {
+                return Err(Error::InvalidFormat(
+                    "the root is not an object".to_string(),
+                ))
+            }
+        };
+
+        let version = match root.get("version") {
+            None => 0,
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| Error::InvalidFormat(format!("invalid version {v}")))?,
+        };
+        let values: Values = match root.remove("values") {
+            None => Values::new(),
+            Some(values) => serde_json::from_value(values)?,
+        };
+        Ok(Snapshot { version, values })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        let mut values = Values::new();
+        values.insert("user_name".to_string(), "ユーザ".to_string());
+        values.insert("multi".to_string(), "line1\r\nline2".to_string());
+        let snapshot = Snapshot { version: 2, values };
+
+        let bytes = JsonFormat.encode(&snapshot)?;
+        assert_eq!(JsonFormat.decode(&bytes)?, snapshot);
+
+        let snapshot = JsonFormat.decode(br#"{"values": {"key": "value"}}"#)?;
+        assert_eq!(snapshot.version, 0);
+
+        assert!(JsonFormat.decode(br#"{"version": -1}"#).is_err());
+        assert!(JsonFormat.decode(br#"{"values": {"key": 1}}"#).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+use super::{Error, Format, Snapshot, Values};
+
+const VERSION_DIRECTIVE: &str = "#version=";
+
+/// `TextFormat` stores the values as `key=value` lines in UTF-8.
+///
+/// The first line `#version=N` is the schema version, and the other lines starting with `#` are comments.
+/// Backslash, line breaks and `=` in keys are escaped with a backslash.
+///
+/// ```text
+/// #version=1
+/// talk_count=10
+/// user_name=ユーザ
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TextFormat;
+
+impl Format for TextFormat {
+    fn extension(&self) -> &'static str {
+        "txt"
+    }
+
+    fn encode(&self, snapshot: &Snapshot) -> Result<Vec<u8>, Error> {
+        let mut text = format!("{VERSION_DIRECTIVE}{}\n", snapshot.version);
+        for (key, value) in &snapshot.values {
+            text.push_str(&escape(key, true));
+            text.push('=');
+            text.push_str(&escape(value, false));
+            text.push('\n');
+        }
+        Ok(text.into_bytes())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Result<Snapshot, Error> {
+        let text = std::str::from_utf8(bytes).map_err(|e| Error::InvalidFormat(e.to_string()))?;
+        let text = text.strip_prefix('\u{feff}').unwrap_or(text);
+
+        let mut version = 0;
+        let mut values = Values::new();
+        for (i, line) in text.lines().enumerate() {
+            if let Some(v) = line.strip_prefix(VERSION_DIRECTIVE) {
+                version = v.trim().parse().map_err(|_| {
+                    Error::InvalidFormat(format!("line {}: invalid version {v:?}", i + 1))
+                })?;
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key, value) = split_line(line)
+                .ok_or_else(|| Error::InvalidFormat(format!("line {}: missing '='", i + 1)))?;
+            values.insert(unescape(key), unescape(value));
+        }
+        Ok(Snapshot { version, values })
+    }
+}
+
+fn escape(s: &str, is_key: bool) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '=' if is_key => escaped.push_str("\\="),
+            '#' if is_key && i == 0 => escaped.push_str("\\#"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some(c) => unescaped.push(c),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Split the line at the first `=` that is not escaped.
+fn split_line(line: &str) -> Option<(&str, &str)> {
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '=' => return Some((&line[..i], &line[i + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() -> Result<(), Error> {
+        let mut values = Values::new();
+        values.insert("user_name".to_string(), "ユーザ".to_string());
+        values.insert("a=b".to_string(), "c=d".to_string());
+        values.insert("#comment".to_string(), "".to_string());
+        values.insert("multi".to_string(), "line1\r\nline2\\n".to_string());
+        let snapshot = Snapshot { version: 2, values };
+
+        let bytes = TextFormat.encode(&snapshot)?;
+        assert_eq!(
+            String::from_utf8_lossy(&bytes),
+            "#version=2\n\\#comment=\na\\=b=c=d\nmulti=line1\\r\\nline2\\\\n\nuser_name=ユーザ\n"
+        );
+        assert_eq!(TextFormat.decode(&bytes)?, snapshot);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_without_version() -> Result<(), Error> {
+        let snapshot = TextFormat.decode("\u{feff}# comment\r\n\r\nkey=value\r\n".as_bytes())?;
+        assert_eq!(snapshot.version, 0);
+        assert_eq!(
+            snapshot.values.get("key").map(String::as_str),
+            Some("value")
+        );
+
+        let result = TextFormat.decode(b"key");
+        assert_eq!(
+            result.map_err(|e| e.to_string()),
+            Err("invalid store file: line 1: missing '='".to_string())
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,246 @@
+use std::future::Future;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tokio::runtime::{self, Handle};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tokio_util::task::TaskTracker;
+
+/// The name of the thread that drives the background tasks.
+const DRIVER_THREAD_NAME: &str = "uka-shiori-background";
+
+/// `Tasks` spawns background tasks that keep running between SHIORI requests.
+///
+/// The tasks run on a dedicated driver thread started when the SHIORI is loaded,
+/// so they make progress even while the baseware is not calling the SHIORI.
+/// When the SHIORI is unloaded, the cancellation token passed to each task is cancelled,
+/// and the tasks that do not finish within the grace period are aborted.
+///
+/// `Tasks` is obtained from [`Context::tasks`](crate::runtime::Context::tasks).
+#[derive(Clone)]
+pub struct Tasks {
+    handle: Option<Handle>,
+    tracker: TaskTracker,
+    token: CancellationToken,
+}
+
+impl Tasks {
+    /// Constructs `Tasks` that spawn tasks on the runtime of the caller.
+    pub(crate) fn current() -> Self {
+        Self {
+            handle: None,
+            tracker: TaskTracker::new(),
+            token: CancellationToken::new(),
+        }
+    }
+
+    fn with_handle(handle: Handle) -> Self {
+        Self {
+            handle: Some(handle),
+            ..Self::current()
+        }
+    }
+
+    /// Spawn a background task.
+    ///
+    /// The task receives a cancellation token that is cancelled when the SHIORI is unloaded,
+    /// and it should return promptly once the token is cancelled.
+    ///
+    /// ```rust
+    /// # use std::path::PathBuf;
+    /// # use std::time::Duration;
+    /// # use uka_shiori::runtime::{Context, ContextData};
+    /// # use uka_shiori::types::v3;
+    /// #
+    /// # struct Data;
+    /// # impl ContextData for Data {
+    /// #     type Error = v3::ShioriError;
+    /// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+    /// # }
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let ctx = Context::from(Data);
+    /// ctx.tasks().spawn(|token| async move {
+    ///     loop {
+    ///         tokio::select! {
+    ///             _ = token.cancelled() => break,
+    ///             _ = tokio::time::sleep(Duration::from_secs(60)) => {
+    ///                 // autosave
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn spawn<F, Fut>(&self, f: F) -> JoinHandle<Fut::Output>
+    where
+        F: FnOnce(CancellationToken) -> Fut,
+        Fut: Future + Send + 'static,
+        Fut::Output: Send + 'static,
+    {
+        let future = f(self.token.child_token());
+        match &self.handle {
+            Some(handle) => self.tracker.spawn_on(future, handle),
+            None => self.tracker.spawn(future),
+        }
+    }
+
+    /// Returns a token that is cancelled when the SHIORI is unloaded.
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.token.child_token()
+    }
+
+    /// Returns the number of the running tasks.
+    pub fn len(&self) -> usize {
+        self.tracker.len()
+    }
+
+    /// Returns true if there are no running tasks.
+    pub fn is_empty(&self) -> bool {
+        self.tracker.is_empty()
+    }
+
+    /// Cancel the tasks and wait for them to finish within the grace period.
+    ///
+    /// Returns false if some tasks are still running after the grace period.
+    pub(crate) async fn shutdown(&self, grace_period: Duration) -> bool {
+        self.token.cancel();
+        self.tracker.close();
+        match tokio::time::timeout(grace_period, self.tracker.wait()).await {
+            Ok(()) => true,
+            Err(_) => {
+                log::warn!(
+                    "{} background tasks did not finish within {grace_period:?}, they will be aborted",
+                    self.tracker.len()
+                );
+                false
+            }
+        }
+    }
+}
+
+/// `Driver` owns the thread that runs the background tasks.
+pub(crate) struct Driver {
+    tasks: Tasks,
+    shutdown: oneshot::Sender<Duration>,
+    stopped: oneshot::Receiver<()>,
+}
+
+impl Driver {
+    /// Start the driver thread.
+    pub(crate) fn start() -> std::io::Result<Self> {
+        let runtime = runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let (shutdown, shutdown_rx) = oneshot::channel::<Duration>();
+        let (stopped_tx, stopped) = oneshot::channel::<()>();
+        let (handle_tx, handle_rx) = mpsc::channel();
+
+        // The thread is detached, the end of the thread is notified through `stopped`
+        // so that `shutdown` does not block the caller's executor by joining it.
+        thread::Builder::new()
+            .name(DRIVER_THREAD_NAME.to_string())
+            .spawn(move || {
+                let _ = handle_tx.send(runtime.handle().clone());
+                let grace_period = runtime.block_on(shutdown_rx).unwrap_or_default();
+                // Shutting down the runtime aborts the tasks that are still running,
+                // and it waits for the blocking tasks only within the grace period.
+                runtime.shutdown_timeout(grace_period);
+                let _ = stopped_tx.send(());
+            })?;
+        let handle = handle_rx
+            .recv()
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+        Ok(Self {
+            tasks: Tasks::with_handle(handle),
+            shutdown,
+            stopped,
+        })
+    }
+
+    pub(crate) fn tasks(&self) -> &Tasks {
+        &self.tasks
+    }
+
+    /// Cancel the tasks, wait for them within the grace period and stop the driver thread.
+    ///
+    /// The driver thread waits for the blocking tasks for up to another grace period before it stops.
+    pub(crate) async fn shutdown(self, grace_period: Duration) {
+        self.tasks.shutdown(grace_period).await;
+        let _ = self.shutdown.send(grace_period);
+        if self.stopped.await.is_err() {
+            log::error!("background driver thread panicked");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_driver_runs_tasks_between_requests() {
+        let driver = Driver::start().expect("failed to start driver");
+        let (tx, rx) = mpsc::channel();
+        driver.tasks().spawn(|_token| async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let _ = tx.send(thread::current().name().map(|v| v.to_string()));
+        });
+
+        // nothing drives the caller's runtime, but the task still completes
+        let name = rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("task must complete");
+        assert_eq!(name.as_deref(), Some(DRIVER_THREAD_NAME));
+
+        runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("failed to create tokio runtime")
+            .block_on(driver.shutdown(Duration::from_secs(1)));
+    }
+
+    #[tokio::test]
+    async fn test_driver_shutdown_cancels_tasks() {
+        let driver = Driver::start().expect("failed to start driver");
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let aborted = Arc::new(AtomicBool::new(true));
+
+        let flag = cancelled.clone();
+        driver.tasks().spawn(|token| async move {
+            token.cancelled().await;
+            flag.store(true, Ordering::SeqCst);
+        });
+        let flag = aborted.clone();
+        driver.tasks().spawn(|_token| async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            flag.store(false, Ordering::SeqCst);
+        });
+
+        let tasks = driver.tasks().clone();
+        assert_eq!(tasks.len(), 2);
+        driver.shutdown(Duration::from_millis(50)).await;
+
+        assert!(cancelled.load(Ordering::SeqCst));
+        assert!(aborted.load(Ordering::SeqCst));
+        assert!(tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_driver_shutdown_does_not_wait_for_blocking_tasks() {
+        let driver = Driver::start().expect("failed to start driver");
+        driver.tasks().spawn(|_token| async move {
+            let _ = tokio::task::spawn_blocking(|| thread::sleep(Duration::from_secs(5))).await;
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+
+        let start = std::time::Instant::now();
+        driver.shutdown(Duration::from_millis(50)).await;
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
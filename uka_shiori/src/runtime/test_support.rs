@@ -0,0 +1,103 @@
+//! Fixtures shared by the tests of the runtime.
+
+use crate::runtime::{box_handler, BoxHandlerV3, Context, ContextData};
+use crate::types::v3;
+use std::path::PathBuf;
+
+/// Context data without any data.
+pub(crate) struct Data;
+
+impl ContextData for Data {
+    type Error = v3::ShioriError;
+
+    fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+/// Constructs a SHIORI/3.0 request with the `ID` header.
+pub(crate) fn request(method: v3::Method, id: &str) -> v3::Request {
+    v3::Request::builder()
+        .method(method)
+        .version(v3::Version::SHIORI_30)
+        .header(v3::HeaderName::ID, id)
+        .build()
+        .expect("valid request")
+}
+
+/// Constructs a SHIORI/3.0 response without headers.
+pub(crate) fn status(status_code: v3::StatusCode) -> Result<v3::Response, v3::ShioriError> {
+    v3::Response::builder()
+        .version(v3::Version::SHIORI_30)
+        .status_code(status_code)
+        .build()
+        .map_err(v3::ShioriError::from)
+}
+
+/// Constructs a `200 OK` response whose `Value` is encoded in UTF-8.
+pub(crate) fn ok(value: impl Into<String>) -> Result<v3::Response, v3::ShioriError> {
+    v3::Response::builder()
+        .version(v3::Version::SHIORI_30)
+        .status_code(v3::StatusCode::OK)
+        .charset(v3::Charset::UTF8)
+        .header(v3::HeaderName::VALUE, value.into())
+        .build()
+        .map_err(v3::ShioriError::from)
+}
+
+/// Returns `Value` of the response, decoded with the charset of the response.
+pub(crate) fn value(response: &v3::Response) -> Option<String> {
+    response
+        .value()
+        .and_then(|v| v.text_with_charset(response.charset()).ok())
+}
+
+/// A handler that answers every request with the status code.
+pub(crate) fn respond_with(status_code: v3::StatusCode) -> BoxHandlerV3<Data> {
+    box_handler(move |_ctx: Context<Data>, _req: v3::Request| async move { status(status_code) })
+}
+
+/// Captures the records logged on the current thread, so that the tests can assert the log output.
+pub(crate) mod logger {
+    use std::cell::RefCell;
+    use std::sync::Once;
+
+    thread_local! {
+        static RECORDS: RefCell<Vec<(log::Level, String)>> = const { RefCell::new(Vec::new()) };
+    }
+
+    struct CaptureLogger;
+
+    impl log::Log for CaptureLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+
+        fn log(&self, record: &log::Record) {
+            RECORDS.with(|records| {
+                records
+                    .borrow_mut()
+                    .push((record.level(), record.args().to_string()))
+            });
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: CaptureLogger = CaptureLogger;
+    static INIT: Once = Once::new();
+
+    /// Install the logger and discard the records captured on the current thread so far.
+    pub(crate) fn init() {
+        INIT.call_once(|| {
+            log::set_logger(&LOGGER).expect("no other logger is installed in tests");
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+        RECORDS.with(|records| records.borrow_mut().clear());
+    }
+
+    /// Take the records captured on the current thread.
+    pub(crate) fn take() -> Vec<(log::Level, String)> {
+        RECORDS.with(|records| records.take())
+    }
+}
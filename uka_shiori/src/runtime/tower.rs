@@ -0,0 +1,308 @@
+use crate::runtime::context::{Context, ContextData};
+use crate::runtime::layer::ResponseFuture;
+use crate::runtime::service::Service;
+use crate::types::v3;
+use std::future::poll_fn;
+use std::marker::PhantomData;
+use std::sync::Arc;
+use std::task::{Context as TaskContext, Poll};
+use tokio::sync::Mutex;
+
+/// `FromTower<T>` adapts a `tower::Service<(Context<C>, v3::Request)>` to [`Service`].
+///
+/// `tower::Service::call` takes `&mut self`, so the service is kept behind a mutex and
+/// each request drives `poll_ready` and then `call` on the same instance while holding the lock.
+/// Middleware that keeps state in the service value, such as a rate limit, therefore sees every request.
+/// Clones of `FromTower` share the same instance.
+/// Errors of the tower service are wrapped in `ShioriError` and answered as `500 Internal Server Error`.
+///
+/// # Examples
+///
+/// ```rust
+/// # use std::future::{ready, Ready};
+/// # use std::path::PathBuf;
+/// # use std::task::{Context as TaskContext, Poll};
+/// # use uka_shiori::runtime::{Context, ContextData, FromTower, Service};
+/// # use uka_shiori::types::v3;
+/// #
+/// # struct Data;
+/// # impl ContextData for Data {
+/// #     type Error = v3::ShioriError;
+/// #     fn new(_path: PathBuf) -> Result<Self, Self::Error> { Ok(Self) }
+/// # }
+/// struct NoContent;
+/// impl tower_service::Service<(Context<Data>, v3::Request)> for NoContent {
+///     type Response = v3::Response;
+///     type Error = v3::ShioriError;
+///     type Future = Ready<Result<v3::Response, v3::ShioriError>>;
+///
+///     fn poll_ready(&mut self, _cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+///         Poll::Ready(Ok(()))
+///     }
+///
+///     fn call(&mut self, _req: (Context<Data>, v3::Request)) -> Self::Future {
+///         ready(v3::Response::builder()
+///             .version(v3::Version::SHIORI_30)
+///             .status_code(v3::StatusCode::NO_CONTENT)
+///             .build()
+///             .map_err(v3::ShioriError::from))
+///     }
+/// }
+///
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() -> Result<(), v3::ShioriError> {
+/// let service = FromTower::new(NoContent);
+///
+/// let request = v3::Request::builder()
+///     .method(v3::Method::GET)
+///     .version(v3::Version::SHIORI_30)
+///     .build()?;
+/// let response = service.call(Context::from(Data), request).await?;
+/// assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FromTower<T> {
+    inner: Arc<Mutex<T>>,
+}
+
+impl<T> FromTower<T> {
+    /// Wrap the tower service.
+    pub fn new(inner: T) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    /// Returns the wrapped tower service.
+    ///
+    /// Returns `None` if the service is still shared with a clone or a request in flight.
+    pub fn into_inner(self) -> Option<T> {
+        Arc::try_unwrap(self.inner).ok().map(Mutex::into_inner)
+    }
+}
+
+impl<T> Clone for FromTower<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<C, T> Service<C, v3::Request> for FromTower<T>
+where
+    C: ContextData + 'static,
+    T: tower_service::Service<(Context<C>, v3::Request), Response = v3::Response> + 'static,
+    T::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    T::Future: 'static,
+{
+    type Response = v3::Response;
+    type Error = v3::ShioriError;
+    type Future = ResponseFuture;
+
+    fn call(&self, context: Context<C>, request: v3::Request) -> Self::Future {
+        let inner = self.inner.clone();
+
+        Box::pin(async move {
+            // the lock is released before awaiting the response, so that requests are not serialized
+            let future = {
+                let mut inner = inner.lock().await;
+                poll_fn(|cx| inner.poll_ready(cx))
+                    .await
+                    .map_err(v3::ShioriError::new)?;
+                inner.call((context, request))
+            };
+            future.await.map_err(v3::ShioriError::new)
+        })
+    }
+}
+
+/// `TowerService<C, S>` exposes a [`Service`] such as `ShioriHandler` or `Router` as a
+/// `tower::Service<(Context<C>, v3::Request)>`, so that tower middleware can wrap it.
+///
+/// The service is always ready, and it is shared between clones.
+pub struct TowerService<C, S> {
+    inner: Arc<S>,
+    _context: PhantomData<fn(C)>,
+}
+
+impl<C, S> TowerService<C, S> {
+    /// Wrap the service.
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner: Arc::new(inner),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl<C, S> Clone for TowerService<C, S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl<C, S> tower_service::Service<(Context<C>, v3::Request)> for TowerService<C, S>
+where
+    C: ContextData,
+    S: Service<C, v3::Request>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, _cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, (context, request): (Context<C>, v3::Request)) -> Self::Future {
+        self.inner.call(context, request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{request, status, Data};
+    use crate::runtime::Router;
+    use std::future::Future;
+    use std::pin::Pin;
+
+    /// A tower middleware that rejects requests until it has been polled for readiness twice.
+    struct Warmup<T> {
+        polled: usize,
+        inner: T,
+    }
+
+    impl<T> tower_service::Service<(Context<Data>, v3::Request)> for Warmup<T>
+    where
+        T: tower_service::Service<
+            (Context<Data>, v3::Request),
+            Response = v3::Response,
+            Error = v3::ShioriError,
+        >,
+        T::Future: 'static,
+    {
+        type Response = v3::Response;
+        type Error = String;
+        type Future = Pin<Box<dyn Future<Output = Result<v3::Response, String>>>>;
+
+        fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+            self.polled += 1;
+            if self.polled < 2 {
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.inner.poll_ready(cx).map_err(|e| e.to_string())
+        }
+
+        fn call(&mut self, req: (Context<Data>, v3::Request)) -> Self::Future {
+            let future = self.inner.call(req);
+            Box::pin(async move {
+                let response = future.await.map_err(|e| e.to_string())?;
+                match response.status_code() {
+                    v3::StatusCode::OK => Ok(response),
+                    code => Err(format!("unexpected status code {code}")),
+                }
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_through_tower() -> Result<(), v3::ShioriError> {
+        let router = Router::new()
+            .route("OnBoot", |_ctx: Context<Data>, _req: v3::Request| async {
+                status(v3::StatusCode::OK)
+            });
+        let service = FromTower::new(Warmup {
+            polled: 0,
+            inner: TowerService::new(router),
+        });
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::OK);
+
+        let err = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnClose"))
+            .await
+            .expect_err("middleware must reject 204");
+        assert_eq!(err.to_string(), "unexpected status code 204 No Content");
+        Ok(())
+    }
+
+    /// A tower middleware that admits only a limited number of requests, like `tower::limit::RateLimit`.
+    ///
+    /// The permit reserved by `poll_ready` is consumed by `call`, so the state must persist across requests.
+    struct Limit<T> {
+        remaining: usize,
+        reserved: bool,
+        inner: T,
+    }
+
+    impl<T> tower_service::Service<(Context<Data>, v3::Request)> for Limit<T>
+    where
+        T: tower_service::Service<
+            (Context<Data>, v3::Request),
+            Response = v3::Response,
+            Error = v3::ShioriError,
+        >,
+        T::Future: 'static,
+    {
+        type Response = v3::Response;
+        type Error = v3::ShioriError;
+        type Future = Pin<Box<dyn Future<Output = Result<v3::Response, v3::ShioriError>>>>;
+
+        fn poll_ready(&mut self, cx: &mut TaskContext<'_>) -> Poll<Result<(), Self::Error>> {
+            if !self.reserved {
+                if self.remaining == 0 {
+                    return Poll::Ready(Err(v3::ShioriError::new("limit exceeded")));
+                }
+                self.remaining -= 1;
+                self.reserved = true;
+            }
+            self.inner.poll_ready(cx)
+        }
+
+        fn call(&mut self, req: (Context<Data>, v3::Request)) -> Self::Future {
+            assert!(self.reserved, "call without poll_ready");
+            self.reserved = false;
+            Box::pin(self.inner.call(req))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tower_service_state_persists_across_calls() -> Result<(), v3::ShioriError> {
+        let router = Router::new()
+            .route("OnBoot", |_ctx: Context<Data>, _req: v3::Request| async {
+                status(v3::StatusCode::OK)
+            });
+        let service = FromTower::new(Limit {
+            remaining: 1,
+            reserved: false,
+            inner: TowerService::new(router),
+        });
+
+        let response = service
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await?;
+        assert_eq!(response.status_code(), v3::StatusCode::OK);
+
+        let err = service
+            .clone()
+            .call(Context::from(Data), request(v3::Method::GET, "OnBoot"))
+            .await
+            .expect_err("the second request must be limited");
+        assert_eq!(err.to_string(), "limit exceeded");
+
+        let limit = service.into_inner().expect("no request in flight");
+        assert_eq!(limit.remaining, 0);
+        Ok(())
+    }
+}
@@ -1 +1,7 @@
+mod request;
+mod response;
+pub mod v2;
 pub mod v3;
+
+pub use request::{ParseError, Request};
+pub use response::Response;
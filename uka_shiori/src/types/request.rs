@@ -0,0 +1,120 @@
+use crate::types::{v2, v3};
+
+/// Error that can occur when parsing a request of any SHIORI version.
+#[derive(thiserror::Error, Debug)]
+pub enum ParseError {
+    #[error("unsupported version: {0:?}")]
+    UnsupportedVersion(String),
+
+    #[error("{0}")]
+    V2(#[from] v2::ParseError),
+
+    #[error("{0}")]
+    V3(#[from] v3::ParseError),
+}
+
+/// Request is a SHIORI request of either SHIORI/2.x or SHIORI/3.0.
+///
+/// The baseware may send SHIORI/2.x requests (e.g. `GET Version SHIORI/2.6`) to a SHIORI/3.0 DLL,
+/// so the protocol version must be detected from the request line before parsing.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_shiori::types::{v2, Request};
+/// #
+/// let input = [
+///     b"GET Version SHIORI/2.6\r\n".to_vec(),
+///     b"Sender: SSP\r\n".to_vec(),
+///     b"\r\n".to_vec(),
+/// ].concat();
+/// match Request::parse(&input).unwrap() {
+///     Request::V2(request) => assert_eq!(request.method(), v2::Method::GET_VERSION),
+///     Request::V3(_) => unreachable!(),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum Request {
+    V2(v2::Request),
+    V3(v3::Request),
+}
+
+impl Request {
+    /// Parse a bytes into a Request of the version written in the request line.
+    pub fn parse(buf: &[u8]) -> Result<Self, ParseError> {
+        let line = buf.split(|b| *b == b'\n').next().unwrap_or_default();
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let version = line.rsplit(|b| *b == b' ').next().unwrap_or_default();
+        if version.starts_with(b"SHIORI/3.") {
+            Ok(Request::V3(v3::Request::parse(buf)?))
+        } else if version.starts_with(b"SHIORI/2.") {
+            Ok(Request::V2(v2::Request::parse(buf)?))
+        } else {
+            Err(ParseError::UnsupportedVersion(
+                String::from_utf8_lossy(version).to_string(),
+            ))
+        }
+    }
+
+    /// Convert request to bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            Request::V2(request) => request.to_vec(),
+            Request::V3(request) => request.to_vec(),
+        }
+    }
+}
+
+impl From<v2::Request> for Request {
+    fn from(value: v2::Request) -> Self {
+        Request::V2(value)
+    }
+}
+
+impl From<v3::Request> for Request {
+    fn from(value: v3::Request) -> Self {
+        Request::V3(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_detects_v2() -> anyhow::Result<()> {
+        let input = [
+            b"GET Sentence SHIORI/2.2\r\n".to_vec(),
+            b"Event: OnBoot\r\n".to_vec(),
+            b"\r\n".to_vec(),
+        ]
+        .concat();
+        let request = Request::parse(&input)?;
+        assert!(matches!(request, Request::V2(_)));
+        assert_eq!(request.to_vec(), input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_detects_v3() -> anyhow::Result<()> {
+        let input = [
+            b"GET SHIORI/3.0\r\n".to_vec(),
+            b"ID: OnBoot\r\n".to_vec(),
+            b"\r\n".to_vec(),
+        ]
+        .concat();
+        let request = Request::parse(&input)?;
+        assert!(matches!(request, Request::V3(_)));
+        assert_eq!(request.to_vec(), input);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_failed_unsupported_version() {
+        let input = [b"GET SHIORI/1.0\r\n".to_vec(), b"\r\n".to_vec()].concat();
+        let result = Request::parse(&input);
+        assert!(matches!(result, Err(ParseError::UnsupportedVersion(_))));
+    }
+}
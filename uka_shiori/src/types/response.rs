@@ -0,0 +1,30 @@
+use crate::types::{v2, v3};
+
+/// Response is a SHIORI response of either SHIORI/2.x or SHIORI/3.0.
+#[derive(Debug)]
+pub enum Response {
+    V2(v2::Response),
+    V3(v3::Response),
+}
+
+impl Response {
+    /// Convert response to bytes.
+    pub fn to_vec(&self) -> Vec<u8> {
+        match self {
+            Response::V2(response) => response.to_vec(),
+            Response::V3(response) => response.to_vec(),
+        }
+    }
+}
+
+impl From<v2::Response> for Response {
+    fn from(value: v2::Response) -> Self {
+        Response::V2(value)
+    }
+}
+
+impl From<v3::Response> for Response {
+    fn from(value: v3::Response) -> Self {
+        Response::V3(value)
+    }
+}
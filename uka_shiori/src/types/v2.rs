@@ -0,0 +1,16 @@
+mod header;
+mod method;
+mod parse;
+mod request;
+mod response;
+mod status;
+mod version;
+
+pub use crate::types::v3::{Charset, CharsetError};
+pub use header::{HeaderMap, HeaderName, HeaderNameError, HeaderValue, HeaderValueError};
+pub use method::Method;
+pub use parse::Error as ParseError;
+pub use request::{Error as RequestBuilderError, Request, RequestBuilder};
+pub use response::{Builder as ResponseBuilder, Error as ResponseBuilderError, Response};
+pub use status::{Error as StatusCodeError, StatusCode};
+pub use version::Version;
@@ -0,0 +1,7 @@
+pub use crate::types::v3::{HeaderValue, HeaderValueError};
+pub use name::{Error as HeaderNameError, HeaderName};
+use uka_util::bag::OrderedBag;
+
+mod name;
+
+pub type HeaderMap = OrderedBag<HeaderName, HeaderValue>;
@@ -0,0 +1,293 @@
+use std::fmt::Display;
+use uka_util::header::{parse_reference_index, ReferenceName};
+use uka_util::string::{Error as Rfc7230StringConvertError, Rfc7230String};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Inner {
+    Charset,
+    Sender,
+    SecurityLevel,
+    ID,
+    Event,
+    Reference(usize),
+    Word,
+    Type,
+    Status,
+    Ghost,
+    Sentence,
+    String,
+    To,
+    Age,
+    Surface,
+    Value,
+    Other(Rfc7230String),
+}
+
+/// Error that can occur when convert from string.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid header name: {0}")]
+    InvalidHeaderName(#[from] Rfc7230StringConvertError),
+}
+
+/// HeaderName is the name of the SHIORI/2.x header field.
+///
+/// It has some field names defined based on SHIORI/2.x specifications and extended proprietary field names.
+/// HeaderName is used as a key in the HeaderMap; constants are available for header names based on the SHIORI/2.x specification.
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct HeaderName(Inner);
+
+impl HeaderName {
+    /// Charset
+    pub const CHARSET: HeaderName = HeaderName(Inner::Charset);
+
+    /// Sender
+    pub const SENDER: HeaderName = HeaderName(Inner::Sender);
+
+    /// SecurityLevel
+    pub const SECURITY_LEVEL: HeaderName = HeaderName(Inner::SecurityLevel);
+
+    /// ID
+    pub const ID: HeaderName = HeaderName(Inner::ID);
+
+    /// Event
+    pub const EVENT: HeaderName = HeaderName(Inner::Event);
+
+    /// Reference0
+    pub const REFERENCE0: HeaderName = HeaderName(Inner::Reference(0));
+
+    /// Reference1
+    pub const REFERENCE1: HeaderName = HeaderName(Inner::Reference(1));
+
+    /// Reference2
+    pub const REFERENCE2: HeaderName = HeaderName(Inner::Reference(2));
+
+    /// Reference3
+    pub const REFERENCE3: HeaderName = HeaderName(Inner::Reference(3));
+
+    /// Reference4
+    pub const REFERENCE4: HeaderName = HeaderName(Inner::Reference(4));
+
+    /// Reference5
+    pub const REFERENCE5: HeaderName = HeaderName(Inner::Reference(5));
+
+    /// Reference6
+    pub const REFERENCE6: HeaderName = HeaderName(Inner::Reference(6));
+
+    /// Reference7
+    pub const REFERENCE7: HeaderName = HeaderName(Inner::Reference(7));
+
+    /// Word
+    pub const WORD: HeaderName = HeaderName(Inner::Word);
+
+    /// Type
+    pub const TYPE: HeaderName = HeaderName(Inner::Type);
+
+    /// Status
+    pub const STATUS: HeaderName = HeaderName(Inner::Status);
+
+    /// Ghost
+    pub const GHOST: HeaderName = HeaderName(Inner::Ghost);
+
+    /// Sentence
+    pub const SENTENCE: HeaderName = HeaderName(Inner::Sentence);
+
+    /// String
+    pub const STRING: HeaderName = HeaderName(Inner::String);
+
+    /// To
+    pub const TO: HeaderName = HeaderName(Inner::To);
+
+    /// Age
+    pub const AGE: HeaderName = HeaderName(Inner::Age);
+
+    /// Surface
+    pub const SURFACE: HeaderName = HeaderName(Inner::Surface);
+
+    /// Value
+    pub const VALUE: HeaderName = HeaderName(Inner::Value);
+
+    /// Returns the `ReferenceN` header name for any index `n`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::HeaderName;
+    /// assert_eq!(HeaderName::reference(0), HeaderName::REFERENCE0);
+    /// assert_eq!(HeaderName::reference(8).to_string(), "Reference8");
+    /// assert_eq!(HeaderName::from_static("Reference8").unwrap(), HeaderName::reference(8));
+    /// ```
+    pub const fn reference(n: usize) -> HeaderName {
+        HeaderName(Inner::Reference(n))
+    }
+
+    /// Returns the index if the header name is `ReferenceN`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::HeaderName;
+    /// assert_eq!(HeaderName::REFERENCE7.reference_index(), Some(7));
+    /// assert_eq!(HeaderName::from_static("Reference12").unwrap().reference_index(), Some(12));
+    /// assert_eq!(HeaderName::from_static("Reference012").unwrap().reference_index(), None);
+    /// assert_eq!(HeaderName::SENDER.reference_index(), None);
+    /// ```
+    pub fn reference_index(&self) -> Option<usize> {
+        match self.0 {
+            Inner::Reference(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    ///　Converts a str to HeaderName.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::HeaderName;
+    /// assert_eq!(HeaderName::from_static("Charset").unwrap(), HeaderName::CHARSET);
+    /// assert_eq!(HeaderName::from_static("Sender").unwrap(), HeaderName::SENDER);
+    /// assert_eq!(HeaderName::from_static("SecurityLevel").unwrap(), HeaderName::SECURITY_LEVEL);
+    /// assert_eq!(HeaderName::from_static("ID").unwrap(), HeaderName::ID);
+    /// assert_eq!(HeaderName::from_static("Event").unwrap(), HeaderName::EVENT);
+    /// assert_eq!(HeaderName::from_static("Reference0").unwrap(), HeaderName::REFERENCE0);
+    /// assert_eq!(HeaderName::from_static("Reference1").unwrap(), HeaderName::REFERENCE1);
+    /// assert_eq!(HeaderName::from_static("Reference2").unwrap(), HeaderName::REFERENCE2);
+    /// assert_eq!(HeaderName::from_static("Reference3").unwrap(), HeaderName::REFERENCE3);
+    /// assert_eq!(HeaderName::from_static("Reference4").unwrap(), HeaderName::REFERENCE4);
+    /// assert_eq!(HeaderName::from_static("Reference5").unwrap(), HeaderName::REFERENCE5);
+    /// assert_eq!(HeaderName::from_static("Reference6").unwrap(), HeaderName::REFERENCE6);
+    /// assert_eq!(HeaderName::from_static("Reference7").unwrap(), HeaderName::REFERENCE7);
+    /// assert_eq!(HeaderName::from_static("Word").unwrap(), HeaderName::WORD);
+    /// assert_eq!(HeaderName::from_static("Type").unwrap(), HeaderName::TYPE);
+    /// assert_eq!(HeaderName::from_static("Status").unwrap(), HeaderName::STATUS);
+    /// assert_eq!(HeaderName::from_static("Ghost").unwrap(), HeaderName::GHOST);
+    /// assert_eq!(HeaderName::from_static("Sentence").unwrap(), HeaderName::SENTENCE);
+    /// assert_eq!(HeaderName::from_static("String").unwrap(), HeaderName::STRING);
+    /// assert_eq!(HeaderName::from_static("To").unwrap(), HeaderName::TO);
+    /// assert_eq!(HeaderName::from_static("Age").unwrap(), HeaderName::AGE);
+    /// assert_eq!(HeaderName::from_static("Surface").unwrap(), HeaderName::SURFACE);
+    /// assert_eq!(HeaderName::from_static("Value").unwrap(), HeaderName::VALUE);
+    /// ```
+    pub fn from_static(s: &str) -> Result<HeaderName, Error> {
+        if let Some(n) = parse_reference_index(s) {
+            return Ok(HeaderName(Inner::Reference(n)));
+        }
+        match s {
+            "Charset" => Ok(HeaderName(Inner::Charset)),
+            "Sender" => Ok(HeaderName(Inner::Sender)),
+            "SecurityLevel" => Ok(HeaderName(Inner::SecurityLevel)),
+            "ID" => Ok(HeaderName(Inner::ID)),
+            "Event" => Ok(HeaderName(Inner::Event)),
+            "Word" => Ok(HeaderName(Inner::Word)),
+            "Type" => Ok(HeaderName(Inner::Type)),
+            "Status" => Ok(HeaderName(Inner::Status)),
+            "Ghost" => Ok(HeaderName(Inner::Ghost)),
+            "Sentence" => Ok(HeaderName(Inner::Sentence)),
+            "String" => Ok(HeaderName(Inner::String)),
+            "To" => Ok(HeaderName(Inner::To)),
+            "Age" => Ok(HeaderName(Inner::Age)),
+            "Surface" => Ok(HeaderName(Inner::Surface)),
+            "Value" => Ok(HeaderName(Inner::Value)),
+            _ => Ok(HeaderName(Inner::Other(Rfc7230String::from_string(
+                s.to_string(),
+            )?))),
+        }
+    }
+
+    ///　Converts a bytes to HeaderName.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::HeaderName;
+    /// assert_eq!(HeaderName::from_bytes(b"Charset").unwrap(), HeaderName::CHARSET);
+    /// assert_eq!(HeaderName::from_bytes(b"Sender").unwrap(), HeaderName::SENDER);
+    /// assert_eq!(HeaderName::from_bytes(b"SecurityLevel").unwrap(), HeaderName::SECURITY_LEVEL);
+    /// assert_eq!(HeaderName::from_bytes(b"ID").unwrap(), HeaderName::ID);
+    /// assert_eq!(HeaderName::from_bytes(b"Event").unwrap(), HeaderName::EVENT);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference0").unwrap(), HeaderName::REFERENCE0);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference1").unwrap(), HeaderName::REFERENCE1);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference2").unwrap(), HeaderName::REFERENCE2);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference3").unwrap(), HeaderName::REFERENCE3);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference4").unwrap(), HeaderName::REFERENCE4);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference5").unwrap(), HeaderName::REFERENCE5);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference6").unwrap(), HeaderName::REFERENCE6);
+    /// assert_eq!(HeaderName::from_bytes(b"Reference7").unwrap(), HeaderName::REFERENCE7);
+    /// assert_eq!(HeaderName::from_bytes(b"Word").unwrap(), HeaderName::WORD);
+    /// assert_eq!(HeaderName::from_bytes(b"Type").unwrap(), HeaderName::TYPE);
+    /// assert_eq!(HeaderName::from_bytes(b"Status").unwrap(), HeaderName::STATUS);
+    /// assert_eq!(HeaderName::from_bytes(b"Ghost").unwrap(), HeaderName::GHOST);
+    /// assert_eq!(HeaderName::from_bytes(b"Sentence").unwrap(), HeaderName::SENTENCE);
+    /// assert_eq!(HeaderName::from_bytes(b"String").unwrap(), HeaderName::STRING);
+    /// assert_eq!(HeaderName::from_bytes(b"To").unwrap(), HeaderName::TO);
+    /// assert_eq!(HeaderName::from_bytes(b"Age").unwrap(), HeaderName::AGE);
+    /// assert_eq!(HeaderName::from_bytes(b"Surface").unwrap(), HeaderName::SURFACE);
+    /// assert_eq!(HeaderName::from_bytes(b"Value").unwrap(), HeaderName::VALUE);
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<HeaderName, Error> {
+        let s = String::from_utf8_lossy(bytes);
+        HeaderName::from_static(s.as_ref())
+    }
+
+    ///　Converts a HeaderName to bytes.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::HeaderName;
+    /// assert_eq!(HeaderName::CHARSET.to_vec(), b"Charset");
+    /// assert_eq!(HeaderName::SENDER.to_vec(), b"Sender");
+    /// assert_eq!(HeaderName::SECURITY_LEVEL.to_vec(), b"SecurityLevel");
+    /// assert_eq!(HeaderName::ID.to_vec(), b"ID");
+    /// assert_eq!(HeaderName::EVENT.to_vec(), b"Event");
+    /// assert_eq!(HeaderName::REFERENCE0.to_vec(), b"Reference0");
+    /// assert_eq!(HeaderName::REFERENCE1.to_vec(), b"Reference1");
+    /// assert_eq!(HeaderName::REFERENCE2.to_vec(), b"Reference2");
+    /// assert_eq!(HeaderName::REFERENCE3.to_vec(), b"Reference3");
+    /// assert_eq!(HeaderName::REFERENCE4.to_vec(), b"Reference4");
+    /// assert_eq!(HeaderName::REFERENCE5.to_vec(), b"Reference5");
+    /// assert_eq!(HeaderName::REFERENCE6.to_vec(), b"Reference6");
+    /// assert_eq!(HeaderName::REFERENCE7.to_vec(), b"Reference7");
+    /// assert_eq!(HeaderName::WORD.to_vec(), b"Word");
+    /// assert_eq!(HeaderName::TYPE.to_vec(), b"Type");
+    /// assert_eq!(HeaderName::STATUS.to_vec(), b"Status");
+    /// assert_eq!(HeaderName::GHOST.to_vec(), b"Ghost");
+    /// assert_eq!(HeaderName::SENTENCE.to_vec(), b"Sentence");
+    /// assert_eq!(HeaderName::STRING.to_vec(), b"String");
+    /// assert_eq!(HeaderName::TO.to_vec(), b"To");
+    /// assert_eq!(HeaderName::AGE.to_vec(), b"Age");
+    /// assert_eq!(HeaderName::SURFACE.to_vec(), b"Surface");
+    /// assert_eq!(HeaderName::VALUE.to_vec(), b"Value");
+    /// assert_eq!(HeaderName::from_static("X-Extend-Header").unwrap().to_vec(), b"X-Extend-Header");
+    /// ```
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.to_string().into_bytes()
+    }
+}
+
+impl Display for HeaderName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Inner::Charset => write!(f, "Charset"),
+            Inner::Sender => write!(f, "Sender"),
+            Inner::SecurityLevel => write!(f, "SecurityLevel"),
+            Inner::ID => write!(f, "ID"),
+            Inner::Event => write!(f, "Event"),
+            Inner::Reference(n) => write!(f, "Reference{n}"),
+            Inner::Word => write!(f, "Word"),
+            Inner::Type => write!(f, "Type"),
+            Inner::Status => write!(f, "Status"),
+            Inner::Ghost => write!(f, "Ghost"),
+            Inner::Sentence => write!(f, "Sentence"),
+            Inner::String => write!(f, "String"),
+            Inner::To => write!(f, "To"),
+            Inner::Age => write!(f, "Age"),
+            Inner::Surface => write!(f, "Surface"),
+            Inner::Value => write!(f, "Value"),
+            Inner::Other(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl ReferenceName for HeaderName {
+    fn reference_index(&self) -> Option<usize> {
+        HeaderName::reference_index(self)
+    }
+}
+
+impl From<HeaderName> for String {
+    fn from(header_name: HeaderName) -> Self {
+        header_name.to_string()
+    }
+}
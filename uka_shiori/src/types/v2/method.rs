@@ -0,0 +1,82 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+enum Inner {
+    GetVersion,
+    GetSentence,
+    GetWord,
+    GetStatus,
+    GetString,
+    Teach,
+    NotifyOwnerGhostName,
+    NotifyOtherGhostName,
+    TranslateSentence,
+}
+
+/// Method is the method of the SHIORI/2.x request.
+///
+/// In SHIORI/2.x, the method consists of a command and the kind of resource to be requested.
+///
+/// ```rust
+/// # use uka_shiori::types::v2::Method;
+/// # let method = Method::GET_VERSION;
+/// match method {
+///     Method::GET_VERSION => assert_eq!(method.to_string(), "GET Version"),
+///     Method::GET_SENTENCE => assert_eq!(method.to_string(), "GET Sentence"),
+///     Method::GET_WORD => assert_eq!(method.to_string(), "GET Word"),
+///     Method::GET_STATUS => assert_eq!(method.to_string(), "GET Status"),
+///     Method::GET_STRING => assert_eq!(method.to_string(), "GET String"),
+///     Method::TEACH => assert_eq!(method.to_string(), "TEACH"),
+///     Method::NOTIFY_OWNER_GHOST_NAME => assert_eq!(method.to_string(), "NOTIFY OwnerGhostName"),
+///     Method::NOTIFY_OTHER_GHOST_NAME => assert_eq!(method.to_string(), "NOTIFY OtherGhostName"),
+///     Method::TRANSLATE_SENTENCE => assert_eq!(method.to_string(), "TRANSLATE Sentence"),
+/// }
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct Method(Inner);
+impl Method {
+    /// GET Version
+    pub const GET_VERSION: Method = Method(Inner::GetVersion);
+
+    /// GET Sentence
+    pub const GET_SENTENCE: Method = Method(Inner::GetSentence);
+
+    /// GET Word
+    pub const GET_WORD: Method = Method(Inner::GetWord);
+
+    /// GET Status
+    pub const GET_STATUS: Method = Method(Inner::GetStatus);
+
+    /// GET String
+    pub const GET_STRING: Method = Method(Inner::GetString);
+
+    /// TEACH
+    pub const TEACH: Method = Method(Inner::Teach);
+
+    /// NOTIFY OwnerGhostName
+    pub const NOTIFY_OWNER_GHOST_NAME: Method = Method(Inner::NotifyOwnerGhostName);
+
+    /// NOTIFY OtherGhostName
+    pub const NOTIFY_OTHER_GHOST_NAME: Method = Method(Inner::NotifyOtherGhostName);
+
+    /// TRANSLATE Sentence
+    pub const TRANSLATE_SENTENCE: Method = Method(Inner::TranslateSentence);
+}
+
+impl fmt::Display for Method {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Inner::*;
+
+        f.write_str(match self.0 {
+            GetVersion => "GET Version",
+            GetSentence => "GET Sentence",
+            GetWord => "GET Word",
+            GetStatus => "GET Status",
+            GetString => "GET String",
+            Teach => "TEACH",
+            NotifyOwnerGhostName => "NOTIFY OwnerGhostName",
+            NotifyOtherGhostName => "NOTIFY OtherGhostName",
+            TranslateSentence => "TRANSLATE Sentence",
+        })
+    }
+}
@@ -0,0 +1,201 @@
+use crate::types::v2::header::{HeaderMap, HeaderName, HeaderNameError, HeaderValueError};
+use crate::types::v2::method::Method;
+use crate::types::v2::request::Request;
+use crate::types::v2::response::Response;
+use crate::types::v2::status::StatusCode;
+use crate::types::v2::version::Version;
+use crate::types::v2::ParseError;
+use crate::types::v3::{Charset, CharsetError};
+use std::io::Cursor;
+use uka_util::cursor::{lookahead, read, read_expect, read_match, read_repeat, read_until};
+use uka_util::status::{parse_reason_phrase, Error as ReasonPhraseError};
+
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    IO(#[from] std::io::Error),
+
+    #[error("invalid method: {0:?}")]
+    InvalidMethod(String),
+
+    #[error("invalid status code: {0:?}")]
+    InvalidStatusCode(String),
+
+    #[error("invalid header name: {0:?}")]
+    InvalidHeaderName(#[from] HeaderNameError),
+
+    #[error("`{0}` header not found")]
+    MissingHeader(HeaderName),
+
+    #[error("{1} in `{0}` header")]
+    FailedDecode(HeaderName, #[source] HeaderValueError),
+
+    #[error("{0}")]
+    UnsupportedCharset(#[from] CharsetError),
+
+    #[error("unexpected eof")]
+    UnexpectedEof,
+}
+
+impl From<ReasonPhraseError> for Error {
+    fn from(e: ReasonPhraseError) -> Self {
+        match e {
+            ReasonPhraseError::Io(e) => Error::IO(e),
+            ReasonPhraseError::InvalidStatusCode(s) => Error::InvalidStatusCode(s),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+pub fn parse_request(input: &[u8]) -> Result<Request> {
+    let mut cursor = Cursor::new(input);
+    let (method, version) = parse_request_line(&mut cursor)?;
+    let headers = parse_headers(&mut cursor)?;
+    let charset = headers
+        .get(&HeaderName::CHARSET)
+        .ok_or(Error::MissingHeader(HeaderName::CHARSET))
+        .and_then(|v| {
+            v.text()
+                .map_err(|e| Error::FailedDecode(HeaderName::CHARSET, e))
+        })
+        .and_then(|v| Charset::from_string(v).map_err(Error::from))
+        .or(Ok::<Charset, ParseError>(Charset::ASCII))?;
+    skip_newline(&mut cursor)?;
+    eof(&mut cursor)?;
+
+    Ok(Request {
+        method,
+        version,
+        headers,
+        charset,
+    })
+}
+
+pub fn parse_response(input: &[u8]) -> Result<Response> {
+    let mut cursor = Cursor::new(input);
+    let version = parse_version(&mut cursor)?;
+    skip_spaces(&mut cursor)?;
+    let status_code = parse_status_code(&mut cursor)?;
+    let reason_phrase = parse_reason_phrase(
+        &mut cursor,
+        status_code.as_u16(),
+        status_code.canonical_reason(),
+    )?;
+    let headers = parse_headers(&mut cursor)?;
+    let charset = headers
+        .get(&HeaderName::CHARSET)
+        .ok_or(Error::MissingHeader(HeaderName::CHARSET))
+        .and_then(|v| {
+            v.text()
+                .map_err(|e| Error::FailedDecode(HeaderName::CHARSET, e))
+        })
+        .and_then(|v| Charset::from_string(v).map_err(Error::from))
+        .or(Ok::<Charset, ParseError>(Charset::ASCII))?;
+    skip_newline(&mut cursor)?;
+    eof(&mut cursor)?;
+
+    Ok(Response {
+        version,
+        status_code,
+        reason_phrase,
+        headers,
+        charset,
+    })
+}
+
+/// Parse the request line of SHIORI/2.x.
+///
+/// Unlike SHIORI/3.0, the method of SHIORI/2.x consists of a command and a resource separated by a space
+/// (e.g. `GET Sentence SHIORI/2.2`), so the version is taken from the last token of the line.
+fn parse_request_line(cursor: &mut Cursor<&[u8]>) -> Result<(Method, Version)> {
+    let line = read_until!(cursor, b"\r\n").map_err(Error::from)?;
+    let pos = line
+        .iter()
+        .rposition(|b| *b == b' ')
+        .ok_or_else(|| Error::InvalidMethod(String::from_utf8_lossy(&line).to_string()))?;
+    let method = parse_method(line[..pos].trim_ascii_end())?;
+
+    let mut version_cursor = Cursor::new(&line[pos + 1..]);
+    let version = parse_version(&mut version_cursor)?;
+    eof(&mut version_cursor)?;
+
+    Ok((method, version))
+}
+
+fn parse_method(bytes: &[u8]) -> Result<Method> {
+    match bytes {
+        b"GET Version" => Ok(Method::GET_VERSION),
+        b"GET Sentence" => Ok(Method::GET_SENTENCE),
+        b"GET Word" => Ok(Method::GET_WORD),
+        b"GET Status" => Ok(Method::GET_STATUS),
+        b"GET String" => Ok(Method::GET_STRING),
+        b"TEACH" => Ok(Method::TEACH),
+        b"NOTIFY OwnerGhostName" => Ok(Method::NOTIFY_OWNER_GHOST_NAME),
+        b"NOTIFY OtherGhostName" => Ok(Method::NOTIFY_OTHER_GHOST_NAME),
+        b"TRANSLATE Sentence" => Ok(Method::TRANSLATE_SENTENCE),
+        _ => Err(Error::InvalidMethod(
+            String::from_utf8_lossy(bytes).to_string(),
+        )),
+    }
+}
+
+fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<Version> {
+    read_match!(cursor, {
+        b"SHIORI/2.0" => Version::SHIORI_20,
+        b"SHIORI/2.1" => Version::SHIORI_21,
+        b"SHIORI/2.2" => Version::SHIORI_22,
+        b"SHIORI/2.3" => Version::SHIORI_23,
+        b"SHIORI/2.4" => Version::SHIORI_24,
+        b"SHIORI/2.5" => Version::SHIORI_25,
+        b"SHIORI/2.6" => Version::SHIORI_26,
+    }, 10)
+    .map_err(Error::from)
+}
+
+fn parse_status_code(cursor: &mut Cursor<&[u8]>) -> Result<StatusCode> {
+    let code = read!(cursor, 3).map_err(Error::from)?;
+    std::str::from_utf8(&code)
+        .ok()
+        .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|s| s.parse::<u16>().ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .ok_or_else(|| Error::InvalidStatusCode(String::from_utf8_lossy(&code).to_string()))
+}
+
+fn parse_headers(cursor: &mut Cursor<&[u8]>) -> Result<HeaderMap> {
+    let mut map = HeaderMap::new();
+    loop {
+        let buffer = lookahead!(cursor, 2).map_err(Error::from)?;
+        if &buffer == b"\r\n" {
+            break;
+        }
+        let name = read_until!(cursor, b":").map_err(Error::from)?;
+        skip_spaces(cursor)?;
+        let value = read_until!(cursor, b"\r\n").map_err(Error::from)?;
+
+        map.insert(
+            HeaderName::from_bytes(&name).map_err(Error::from)?,
+            value.into(),
+        )
+    }
+    Ok(map)
+}
+
+fn skip_spaces(cursor: &mut Cursor<&[u8]>) -> Result<()> {
+    read_repeat!(cursor, b" ").map_err(Error::from)?;
+    Ok(())
+}
+
+fn skip_newline(cursor: &mut Cursor<&[u8]>) -> Result<()> {
+    read_expect!(cursor, b"\r\n").map_err(Error::from)?;
+    Ok(())
+}
+
+fn eof(cursor: &mut Cursor<&[u8]>) -> Result<()> {
+    if cursor.position() as usize == cursor.get_ref().len() {
+        Ok(())
+    } else {
+        Err(Error::UnexpectedEof)
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt;
+use std::fmt::Display;
+
+/// Error that can occur when converting StatusCode from integer.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid status code `{0}`, the status code must be three digits")]
+    Invalid(u16),
+}
+
+/// StatusCode represents the status code of the SHIORI/2.x response.
+///
+/// Status codes that are not defined in the specification can also be represented with [`StatusCode::from_u16`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_shiori::types::v2::StatusCode;
+/// assert_eq!(StatusCode::OK.to_string(), "200 OK");
+/// assert_eq!(StatusCode::NO_CONTENT.to_string(), "204 No Content");
+/// assert_eq!(StatusCode::NOT_ENOUGH.to_string(), "311 Not Enough");
+/// assert_eq!(StatusCode::ADVICE.to_string(), "312 Advice");
+/// assert_eq!(StatusCode::BAD_REQUEST.to_string(), "400 Bad Request");
+/// assert_eq!(StatusCode::INTERNAL_SERVER_ERROR.to_string(), "500 Internal Server Error");
+/// assert_eq!(StatusCode::from_u16(299).unwrap().to_string(), "299");
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct StatusCode(u16);
+impl StatusCode {
+    // 2xx - Process Completed
+    /// 200 OK
+    pub const OK: StatusCode = StatusCode(200);
+
+    /// 204 No Content
+    pub const NO_CONTENT: StatusCode = StatusCode(204);
+
+    // 3xx - 処理完了、追加アクション要求
+    /// 311 Not Enough
+    pub const NOT_ENOUGH: StatusCode = StatusCode(311);
+
+    /// 312 Advice
+    pub const ADVICE: StatusCode = StatusCode(312);
+
+    // 4xx - Request Error
+    /// 400 Bad Request
+    pub const BAD_REQUEST: StatusCode = StatusCode(400);
+
+    // 5xx - Server Error
+    /// 500 Internal Server Error
+    pub const INTERNAL_SERVER_ERROR: StatusCode = StatusCode(500);
+
+    ///　Converts a u16 to StatusCode.
+    ///
+    /// Any three digits code is accepted, even if it is not defined in the specification.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::StatusCode;
+    /// assert_eq!(StatusCode::from_u16(200).unwrap(), StatusCode::OK);
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().as_u16(), 299);
+    /// assert!(StatusCode::from_u16(99).is_err());
+    /// assert!(StatusCode::from_u16(1000).is_err());
+    /// ```
+    pub fn from_u16(code: u16) -> Result<StatusCode, Error> {
+        if (100..1000).contains(&code) {
+            Ok(StatusCode(code))
+        } else {
+            Err(Error::Invalid(code))
+        }
+    }
+
+    /// Returns the status code as u16.
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the reason phrase defined in the specification, if any.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v2::StatusCode;
+    /// assert_eq!(StatusCode::OK.canonical_reason(), Some("OK"));
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().canonical_reason(), None);
+    /// ```
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        match self.0 {
+            200 => Some("OK"),
+            204 => Some("No Content"),
+            311 => Some("Not Enough"),
+            312 => Some("Advice"),
+            400 => Some("Bad Request"),
+            500 => Some("Internal Server Error"),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the status code is in the range 200-299.
+    pub fn is_success(&self) -> bool {
+        uka_util::status::is_success(self.0)
+    }
+
+    /// Returns true if the status code is in the range 300-399.
+    pub fn is_redirection(&self) -> bool {
+        uka_util::status::is_redirection(self.0)
+    }
+
+    /// Returns true if the status code is in the range 400-499.
+    pub fn is_client_error(&self) -> bool {
+        uka_util::status::is_client_error(self.0)
+    }
+
+    /// Returns true if the status code is in the range 500-599.
+    pub fn is_server_error(&self) -> bool {
+        uka_util::status::is_server_error(self.0)
+    }
+}
+
+impl Display for StatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.canonical_reason() {
+            Some(reason) => write!(f, "{} {reason}", self.0),
+            None => write!(f, "{}", self.0),
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+enum Protocol {
+    SHIORI20,
+    SHIORI21,
+    SHIORI22,
+    SHIORI23,
+    SHIORI24,
+    SHIORI25,
+    SHIORI26,
+}
+
+/// Version is the version of the SHIORI/2.x protocol.
+///
+/// # Examples
+///
+/// ```rust
+/// # use uka_shiori::types::v2::Version;
+/// # let version = Version::SHIORI_26;
+/// match version {
+///     Version::SHIORI_20 => assert_eq!(version.to_string(), "SHIORI/2.0"),
+///     Version::SHIORI_21 => assert_eq!(version.to_string(), "SHIORI/2.1"),
+///     Version::SHIORI_22 => assert_eq!(version.to_string(), "SHIORI/2.2"),
+///     Version::SHIORI_23 => assert_eq!(version.to_string(), "SHIORI/2.3"),
+///     Version::SHIORI_24 => assert_eq!(version.to_string(), "SHIORI/2.4"),
+///     Version::SHIORI_25 => assert_eq!(version.to_string(), "SHIORI/2.5"),
+///     Version::SHIORI_26 => assert_eq!(version.to_string(), "SHIORI/2.6"),
+/// }
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct Version(Protocol);
+impl Version {
+    /// SHIORI/2.0
+    pub const SHIORI_20: Version = Version(Protocol::SHIORI20);
+
+    /// SHIORI/2.1
+    pub const SHIORI_21: Version = Version(Protocol::SHIORI21);
+
+    /// SHIORI/2.2
+    pub const SHIORI_22: Version = Version(Protocol::SHIORI22);
+
+    /// SHIORI/2.3
+    pub const SHIORI_23: Version = Version(Protocol::SHIORI23);
+
+    /// SHIORI/2.4
+    pub const SHIORI_24: Version = Version(Protocol::SHIORI24);
+
+    /// SHIORI/2.5
+    pub const SHIORI_25: Version = Version(Protocol::SHIORI25);
+
+    /// SHIORI/2.6
+    pub const SHIORI_26: Version = Version(Protocol::SHIORI26);
+}
+
+impl Default for Version {
+    #[inline]
+    fn default() -> Version {
+        // Default to the latest version
+        Version::SHIORI_26
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Protocol::*;
+
+        f.write_str(match self.0 {
+            SHIORI20 => "SHIORI/2.0",
+            SHIORI21 => "SHIORI/2.1",
+            SHIORI22 => "SHIORI/2.2",
+            SHIORI23 => "SHIORI/2.3",
+            SHIORI24 => "SHIORI/2.4",
+            SHIORI25 => "SHIORI/2.5",
+            SHIORI26 => "SHIORI/2.6",
+        })
+    }
+}
@@ -1,21 +1,30 @@
 mod charset;
 mod error;
+mod error_level;
+mod ghost_status;
 mod header;
 mod method;
 mod parse;
 mod request;
 mod response;
+mod security;
+mod sender_type;
 mod status;
 mod version;
 
 pub use charset::{Charset, Error as CharsetError};
 pub use error::{ShioriError, ShioriErrorContext};
+pub use error_level::{Error as ErrorLevelError, ErrorLevel};
+pub use ghost_status::{GhostStatus, GhostStatusFlag};
 pub use header::{HeaderMap, HeaderName, HeaderNameError, HeaderValue, HeaderValueError};
 pub use method::Method;
 pub use parse::Error as ParseError;
 pub use request::{Error as RequestBuilderError, Request, RequestBuilder};
 pub use response::{
-    Builder as ResponseBuilder, Error as ResponseBuilderError, IntoResponse, Response,
+    Builder as ResponseBuilder, Error as ResponseBuilderError, HeaderError as ResponseHeaderError,
+    IntoResponse, Response,
 };
-pub use status::StatusCode;
+pub use security::{Error as SecurityOriginError, SecurityLevel, SecurityOrigin};
+pub use sender_type::{SenderType, SenderTypeFlag};
+pub use status::{Error as StatusCodeError, StatusCode};
 pub use version::Version;
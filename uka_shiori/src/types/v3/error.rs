@@ -67,6 +67,16 @@ impl ShioriError {
         self
     }
 
+    /// Returns the SHIORI version of the error response, if set.
+    pub fn version(&self) -> Option<Version> {
+        self.version
+    }
+
+    /// Returns the status code of the error response, if set.
+    pub fn status_code(&self) -> Option<StatusCode> {
+        self.status_code
+    }
+
     /// Attaches a context message to the `ShioriError`.
     ///
     /// If there is an existing context, the new context will be appended to it.
@@ -181,6 +191,10 @@ impl IntoResponse for ShioriError {
             .build()
             .expect("failed to build error response")
     }
+
+    fn into_result(self) -> Result<Response, ShioriError> {
+        Err(self)
+    }
 }
 
 /// `ShioriErrorContext` provides extension methods to attach additional context information
@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// Error that can occur when converting ErrorLevel from string.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid error level `{0}`, the error level must be one of `info`, `notice`, `warning`, `error` or `critical`")]
+    Invalid(String),
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+enum Inner {
+    Info,
+    Notice,
+    Warning,
+    Error,
+    Critical,
+}
+
+/// ErrorLevel is the severity of the error reported by the SHIORI, sent in the `ErrorLevel` header.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::ErrorLevel;
+/// #
+/// # let level = ErrorLevel::WARNING;
+/// match level {
+///     ErrorLevel::INFO => assert_eq!(level.to_string(), "info"),
+///     ErrorLevel::NOTICE => assert_eq!(level.to_string(), "notice"),
+///     ErrorLevel::WARNING => assert_eq!(level.to_string(), "warning"),
+///     ErrorLevel::ERROR => assert_eq!(level.to_string(), "error"),
+///     ErrorLevel::CRITICAL => assert_eq!(level.to_string(), "critical"),
+/// }
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct ErrorLevel(Inner);
+impl ErrorLevel {
+    /// info
+    pub const INFO: ErrorLevel = ErrorLevel(Inner::Info);
+
+    /// notice
+    pub const NOTICE: ErrorLevel = ErrorLevel(Inner::Notice);
+
+    /// warning
+    pub const WARNING: ErrorLevel = ErrorLevel(Inner::Warning);
+
+    /// error
+    pub const ERROR: ErrorLevel = ErrorLevel(Inner::Error);
+
+    /// critical
+    pub const CRITICAL: ErrorLevel = ErrorLevel(Inner::Critical);
+
+    ///　Converts a str to ErrorLevel.
+    ///
+    /// The comparison is case-insensitive, as with `SecurityLevel`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::ErrorLevel;
+    /// assert_eq!(ErrorLevel::from_static("info").unwrap(), ErrorLevel::INFO);
+    /// assert_eq!(ErrorLevel::from_static("Warning").unwrap(), ErrorLevel::WARNING);
+    /// assert_eq!(ErrorLevel::from_static("critical").unwrap(), ErrorLevel::CRITICAL);
+    /// assert!(ErrorLevel::from_static("fatal").is_err());
+    /// ```
+    pub fn from_static(s: &str) -> Result<ErrorLevel, Error> {
+        match s.to_ascii_lowercase().as_str() {
+            "info" => Ok(ErrorLevel::INFO),
+            "notice" => Ok(ErrorLevel::NOTICE),
+            "warning" => Ok(ErrorLevel::WARNING),
+            "error" => Ok(ErrorLevel::ERROR),
+            "critical" => Ok(ErrorLevel::CRITICAL),
+            _ => Err(Error::Invalid(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for ErrorLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Inner::*;
+
+        f.write_str(match self.0 {
+            Info => "info",
+            Notice => "notice",
+            Warning => "warning",
+            Error => "error",
+            Critical => "critical",
+        })
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+enum Inner {
+    Talking,
+    Choosing,
+    Minimizing,
+    Induction,
+    Passive,
+    TimeCritical,
+    NoUserBreak,
+    Online,
+    Opening,
+    Balloon(Vec<(usize, i32)>),
+    Other(String),
+}
+
+/// GhostStatusFlag is a flag of the `Status` header that SSP sends with SHIORI requests.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::GhostStatusFlag;
+/// assert_eq!(GhostStatusFlag::from_static("talking"), GhostStatusFlag::TALKING);
+/// assert_eq!(GhostStatusFlag::from_static("balloon(0=0,1=2)"), GhostStatusFlag::balloon([(0, 0), (1, 2)]));
+/// assert_eq!(GhostStatusFlag::from_static("dancing").to_string(), "dancing");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct GhostStatusFlag(Inner);
+impl GhostStatusFlag {
+    /// talking: the ghost is talking.
+    pub const TALKING: GhostStatusFlag = GhostStatusFlag(Inner::Talking);
+
+    /// choosing: the ghost is waiting for the user to choose.
+    pub const CHOOSING: GhostStatusFlag = GhostStatusFlag(Inner::Choosing);
+
+    /// minimizing: the ghost is minimized.
+    pub const MINIMIZING: GhostStatusFlag = GhostStatusFlag(Inner::Minimizing);
+
+    /// induction: the ghost is in induction mode (e.g. dragging).
+    pub const INDUCTION: GhostStatusFlag = GhostStatusFlag(Inner::Induction);
+
+    /// passive: the ghost is in passive mode.
+    pub const PASSIVE: GhostStatusFlag = GhostStatusFlag(Inner::Passive);
+
+    /// timecritical: the ghost is in time critical section.
+    pub const TIME_CRITICAL: GhostStatusFlag = GhostStatusFlag(Inner::TimeCritical);
+
+    /// nouserbreak: the talk cannot be interrupted by the user.
+    pub const NO_USER_BREAK: GhostStatusFlag = GhostStatusFlag(Inner::NoUserBreak);
+
+    /// online: the baseware is connected to the network.
+    pub const ONLINE: GhostStatusFlag = GhostStatusFlag(Inner::Online);
+
+    /// opening: the ghost is opening.
+    pub const OPENING: GhostStatusFlag = GhostStatusFlag(Inner::Opening);
+
+    /// balloon(scope=id,...): the balloons that are currently displayed.
+    pub fn balloon<I>(balloons: I) -> GhostStatusFlag
+    where
+        I: IntoIterator<Item = (usize, i32)>,
+    {
+        GhostStatusFlag(Inner::Balloon(balloons.into_iter().collect()))
+    }
+
+    ///　Converts a str to GhostStatusFlag.
+    ///
+    /// Unrecognized flags are kept as they are.
+    pub fn from_static(s: &str) -> GhostStatusFlag {
+        match s {
+            "talking" => GhostStatusFlag::TALKING,
+            "choosing" => GhostStatusFlag::CHOOSING,
+            "minimizing" => GhostStatusFlag::MINIMIZING,
+            "induction" => GhostStatusFlag::INDUCTION,
+            "passive" => GhostStatusFlag::PASSIVE,
+            "timecritical" => GhostStatusFlag::TIME_CRITICAL,
+            "nouserbreak" => GhostStatusFlag::NO_USER_BREAK,
+            "online" => GhostStatusFlag::ONLINE,
+            "opening" => GhostStatusFlag::OPENING,
+            _ => parse_balloon(s)
+                .map(|balloons| GhostStatusFlag(Inner::Balloon(balloons)))
+                .unwrap_or_else(|| GhostStatusFlag(Inner::Other(s.to_string()))),
+        }
+    }
+}
+
+impl fmt::Display for GhostStatusFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Inner::*;
+
+        match &self.0 {
+            Talking => f.write_str("talking"),
+            Choosing => f.write_str("choosing"),
+            Minimizing => f.write_str("minimizing"),
+            Induction => f.write_str("induction"),
+            Passive => f.write_str("passive"),
+            TimeCritical => f.write_str("timecritical"),
+            NoUserBreak => f.write_str("nouserbreak"),
+            Online => f.write_str("online"),
+            Opening => f.write_str("opening"),
+            Balloon(balloons) => {
+                let balloons = balloons
+                    .iter()
+                    .map(|(scope, id)| format!("{scope}={id}"))
+                    .collect::<Vec<_>>();
+                write!(f, "balloon({})", balloons.join(","))
+            }
+            Other(s) => f.write_str(s),
+        }
+    }
+}
+
+/// GhostStatus is the set of flags in the `Status` header, e.g. `talking,balloon(0=0,1=2)`.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::{GhostStatus, GhostStatusFlag};
+/// let status = GhostStatus::from_static("talking,balloon(0=0,1=2),online");
+/// assert!(status.is_talking());
+/// assert!(status.contains(&GhostStatusFlag::ONLINE));
+/// assert_eq!(status.balloons(), &[(0, 0), (1, 2)]);
+/// assert_eq!(status.to_string(), "talking,balloon(0=0,1=2),online");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct GhostStatus(Vec<GhostStatusFlag>);
+impl GhostStatus {
+    /// Constructs a new GhostStatus from flags.
+    pub fn new<I>(flags: I) -> GhostStatus
+    where
+        I: IntoIterator<Item = GhostStatusFlag>,
+    {
+        GhostStatus(flags.into_iter().collect())
+    }
+
+    ///　Converts a str to GhostStatus.
+    pub fn from_static(s: &str) -> GhostStatus {
+        GhostStatus(
+            split_flags(s)
+                .into_iter()
+                .map(GhostStatusFlag::from_static)
+                .collect(),
+        )
+    }
+
+    /// Returns the flags in the order they were written.
+    pub fn flags(&self) -> &[GhostStatusFlag] {
+        &self.0
+    }
+
+    /// Returns true if the status contains the flag.
+    pub fn contains(&self, flag: &GhostStatusFlag) -> bool {
+        self.0.contains(flag)
+    }
+
+    /// Returns true if the ghost is talking.
+    pub fn is_talking(&self) -> bool {
+        self.contains(&GhostStatusFlag::TALKING)
+    }
+
+    /// Returns true if the ghost is waiting for the user to choose.
+    pub fn is_choosing(&self) -> bool {
+        self.contains(&GhostStatusFlag::CHOOSING)
+    }
+
+    /// Returns true if the ghost is minimized.
+    pub fn is_minimizing(&self) -> bool {
+        self.contains(&GhostStatusFlag::MINIMIZING)
+    }
+
+    /// Returns true if the ghost is in passive mode.
+    pub fn is_passive(&self) -> bool {
+        self.contains(&GhostStatusFlag::PASSIVE)
+    }
+
+    /// Returns the pairs of scope and balloon ID that are currently displayed.
+    pub fn balloons(&self) -> &[(usize, i32)] {
+        self.0
+            .iter()
+            .find_map(|flag| match &flag.0 {
+                Inner::Balloon(balloons) => Some(balloons.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for GhostStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = self.0.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        f.write_str(&flags.join(","))
+    }
+}
+
+/// Split comma separated flags, ignoring commas in parentheses such as `balloon(0=0,1=2)`.
+fn split_flags(s: &str) -> Vec<&str> {
+    let mut flags = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                flags.push(s[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    flags.push(s[start..].trim());
+    flags.into_iter().filter(|s| !s.is_empty()).collect()
+}
+
+fn parse_balloon(s: &str) -> Option<Vec<(usize, i32)>> {
+    let inner = s.strip_prefix("balloon(")?.strip_suffix(')')?;
+    inner
+        .split(',')
+        .filter(|v| !v.is_empty())
+        .map(|pair| {
+            let (scope, id) = pair.split_once('=')?;
+            Some((scope.trim().parse().ok()?, id.trim().parse().ok()?))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ghost_status_from_static() {
+        let status =
+            GhostStatus::from_static("talking, balloon(0=0,1=-1),nouserbreak,balloon(x),,dancing");
+        assert_eq!(
+            status.flags(),
+            &[
+                GhostStatusFlag::TALKING,
+                GhostStatusFlag::balloon([(0, 0), (1, -1)]),
+                GhostStatusFlag::NO_USER_BREAK,
+                GhostStatusFlag(Inner::Other("balloon(x)".to_string())),
+                GhostStatusFlag(Inner::Other("dancing".to_string())),
+            ]
+        );
+        assert_eq!(status.balloons(), &[(0, 0), (1, -1)]);
+        assert!(!status.is_choosing());
+    }
+
+    #[test]
+    fn test_ghost_status_from_static_empty() {
+        let status = GhostStatus::from_static("");
+        assert!(status.flags().is_empty());
+        assert!(status.balloons().is_empty());
+        assert_eq!(status.to_string(), "");
+    }
+}
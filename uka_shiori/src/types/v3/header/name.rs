@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use uka_util::header::{parse_reference_index, ReferenceName};
 use uka_util::string::{Error as Rfc7230StringConvertError, Rfc7230String};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -6,16 +7,19 @@ enum Inner {
     Charset,
     Sender,
     ID,
-    Reference0,
-    Reference1,
-    Reference2,
-    Reference3,
-    Reference4,
-    Reference5,
-    Reference6,
-    Reference7,
+    Reference(usize),
     SecurityLevel,
+    SecurityOrigin,
     Value,
+    Marker,
+    ErrorLevel,
+    ErrorDescription,
+    BalloonOffset,
+    Age,
+    ValueNotify,
+    Status,
+    SenderType,
+    BaseID,
     Other(Rfc7230String),
 }
 
@@ -44,35 +48,93 @@ impl HeaderName {
     pub const ID: HeaderName = HeaderName(Inner::ID);
 
     /// Reference0
-    pub const REFERENCE0: HeaderName = HeaderName(Inner::Reference0);
+    pub const REFERENCE0: HeaderName = HeaderName(Inner::Reference(0));
 
     /// Reference1
-    pub const REFERENCE1: HeaderName = HeaderName(Inner::Reference1);
+    pub const REFERENCE1: HeaderName = HeaderName(Inner::Reference(1));
 
     /// Reference2
-    pub const REFERENCE2: HeaderName = HeaderName(Inner::Reference2);
+    pub const REFERENCE2: HeaderName = HeaderName(Inner::Reference(2));
 
     /// Reference3
-    pub const REFERENCE3: HeaderName = HeaderName(Inner::Reference3);
+    pub const REFERENCE3: HeaderName = HeaderName(Inner::Reference(3));
 
     /// Reference4
-    pub const REFERENCE4: HeaderName = HeaderName(Inner::Reference4);
+    pub const REFERENCE4: HeaderName = HeaderName(Inner::Reference(4));
 
     /// Reference5
-    pub const REFERENCE5: HeaderName = HeaderName(Inner::Reference5);
+    pub const REFERENCE5: HeaderName = HeaderName(Inner::Reference(5));
 
     /// Reference6
-    pub const REFERENCE6: HeaderName = HeaderName(Inner::Reference6);
+    pub const REFERENCE6: HeaderName = HeaderName(Inner::Reference(6));
 
     /// Reference7
-    pub const REFERENCE7: HeaderName = HeaderName(Inner::Reference7);
+    pub const REFERENCE7: HeaderName = HeaderName(Inner::Reference(7));
 
     /// SecurityLevel
     pub const SECURITY_LEVEL: HeaderName = HeaderName(Inner::SecurityLevel);
 
+    /// SecurityOrigin
+    pub const SECURITY_ORIGIN: HeaderName = HeaderName(Inner::SecurityOrigin);
+
     /// Value
     pub const VALUE: HeaderName = HeaderName(Inner::Value);
 
+    /// Marker
+    pub const MARKER: HeaderName = HeaderName(Inner::Marker);
+
+    /// ErrorLevel
+    pub const ERROR_LEVEL: HeaderName = HeaderName(Inner::ErrorLevel);
+
+    /// ErrorDescription
+    pub const ERROR_DESCRIPTION: HeaderName = HeaderName(Inner::ErrorDescription);
+
+    /// BalloonOffset
+    pub const BALLOON_OFFSET: HeaderName = HeaderName(Inner::BalloonOffset);
+
+    /// Age
+    pub const AGE: HeaderName = HeaderName(Inner::Age);
+
+    /// ValueNotify
+    pub const VALUE_NOTIFY: HeaderName = HeaderName(Inner::ValueNotify);
+
+    /// Status
+    pub const STATUS: HeaderName = HeaderName(Inner::Status);
+
+    /// SenderType
+    pub const SENDER_TYPE: HeaderName = HeaderName(Inner::SenderType);
+
+    /// BaseID
+    pub const BASE_ID: HeaderName = HeaderName(Inner::BaseID);
+
+    /// Returns the `ReferenceN` header name for any index `n`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::HeaderName;
+    /// assert_eq!(HeaderName::reference(0), HeaderName::REFERENCE0);
+    /// assert_eq!(HeaderName::reference(8).to_string(), "Reference8");
+    /// assert_eq!(HeaderName::from_static("Reference8").unwrap(), HeaderName::reference(8));
+    /// ```
+    pub const fn reference(n: usize) -> HeaderName {
+        HeaderName(Inner::Reference(n))
+    }
+
+    /// Returns the index if the header name is `ReferenceN`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::HeaderName;
+    /// assert_eq!(HeaderName::REFERENCE7.reference_index(), Some(7));
+    /// assert_eq!(HeaderName::from_static("Reference12").unwrap().reference_index(), Some(12));
+    /// assert_eq!(HeaderName::from_static("Reference012").unwrap().reference_index(), None);
+    /// assert_eq!(HeaderName::SENDER.reference_index(), None);
+    /// ```
+    pub fn reference_index(&self) -> Option<usize> {
+        match self.0 {
+            Inner::Reference(n) => Some(n),
+            _ => None,
+        }
+    }
+
     ///　Converts a str to HeaderName.
     ///
     /// ```rust
@@ -89,23 +151,38 @@ impl HeaderName {
     /// assert_eq!(HeaderName::from_static("Reference6").unwrap(), HeaderName::REFERENCE6);
     /// assert_eq!(HeaderName::from_static("Reference7").unwrap(), HeaderName::REFERENCE7);
     /// assert_eq!(HeaderName::from_static("SecurityLevel").unwrap(), HeaderName::SECURITY_LEVEL);
+    /// assert_eq!(HeaderName::from_static("SecurityOrigin").unwrap(), HeaderName::SECURITY_ORIGIN);
     /// assert_eq!(HeaderName::from_static("Value").unwrap(), HeaderName::VALUE);
+    /// assert_eq!(HeaderName::from_static("Marker").unwrap(), HeaderName::MARKER);
+    /// assert_eq!(HeaderName::from_static("ErrorLevel").unwrap(), HeaderName::ERROR_LEVEL);
+    /// assert_eq!(HeaderName::from_static("ErrorDescription").unwrap(), HeaderName::ERROR_DESCRIPTION);
+    /// assert_eq!(HeaderName::from_static("BalloonOffset").unwrap(), HeaderName::BALLOON_OFFSET);
+    /// assert_eq!(HeaderName::from_static("Age").unwrap(), HeaderName::AGE);
+    /// assert_eq!(HeaderName::from_static("ValueNotify").unwrap(), HeaderName::VALUE_NOTIFY);
+    /// assert_eq!(HeaderName::from_static("Status").unwrap(), HeaderName::STATUS);
+    /// assert_eq!(HeaderName::from_static("SenderType").unwrap(), HeaderName::SENDER_TYPE);
+    /// assert_eq!(HeaderName::from_static("BaseID").unwrap(), HeaderName::BASE_ID);
     /// ```
     pub fn from_static(s: &str) -> Result<HeaderName, Error> {
+        if let Some(n) = parse_reference_index(s) {
+            return Ok(HeaderName(Inner::Reference(n)));
+        }
         match s {
             "Charset" => Ok(HeaderName(Inner::Charset)),
             "Sender" => Ok(HeaderName(Inner::Sender)),
             "ID" => Ok(HeaderName(Inner::ID)),
-            "Reference0" => Ok(HeaderName(Inner::Reference0)),
-            "Reference1" => Ok(HeaderName(Inner::Reference1)),
-            "Reference2" => Ok(HeaderName(Inner::Reference2)),
-            "Reference3" => Ok(HeaderName(Inner::Reference3)),
-            "Reference4" => Ok(HeaderName(Inner::Reference4)),
-            "Reference5" => Ok(HeaderName(Inner::Reference5)),
-            "Reference6" => Ok(HeaderName(Inner::Reference6)),
-            "Reference7" => Ok(HeaderName(Inner::Reference7)),
             "SecurityLevel" => Ok(HeaderName(Inner::SecurityLevel)),
+            "SecurityOrigin" => Ok(HeaderName(Inner::SecurityOrigin)),
             "Value" => Ok(HeaderName(Inner::Value)),
+            "Marker" => Ok(HeaderName(Inner::Marker)),
+            "ErrorLevel" => Ok(HeaderName(Inner::ErrorLevel)),
+            "ErrorDescription" => Ok(HeaderName(Inner::ErrorDescription)),
+            "BalloonOffset" => Ok(HeaderName(Inner::BalloonOffset)),
+            "Age" => Ok(HeaderName(Inner::Age)),
+            "ValueNotify" => Ok(HeaderName(Inner::ValueNotify)),
+            "Status" => Ok(HeaderName(Inner::Status)),
+            "SenderType" => Ok(HeaderName(Inner::SenderType)),
+            "BaseID" => Ok(HeaderName(Inner::BaseID)),
             _ => Ok(HeaderName(Inner::Other(Rfc7230String::from_string(
                 s.to_string(),
             )?))),
@@ -128,7 +205,17 @@ impl HeaderName {
     /// assert_eq!(HeaderName::from_bytes(b"Reference6").unwrap(), HeaderName::REFERENCE6);
     /// assert_eq!(HeaderName::from_bytes(b"Reference7").unwrap(), HeaderName::REFERENCE7);
     /// assert_eq!(HeaderName::from_bytes(b"SecurityLevel").unwrap(), HeaderName::SECURITY_LEVEL);
+    /// assert_eq!(HeaderName::from_bytes(b"SecurityOrigin").unwrap(), HeaderName::SECURITY_ORIGIN);
     /// assert_eq!(HeaderName::from_bytes(b"Value").unwrap(), HeaderName::VALUE);
+    /// assert_eq!(HeaderName::from_bytes(b"Marker").unwrap(), HeaderName::MARKER);
+    /// assert_eq!(HeaderName::from_bytes(b"ErrorLevel").unwrap(), HeaderName::ERROR_LEVEL);
+    /// assert_eq!(HeaderName::from_bytes(b"ErrorDescription").unwrap(), HeaderName::ERROR_DESCRIPTION);
+    /// assert_eq!(HeaderName::from_bytes(b"BalloonOffset").unwrap(), HeaderName::BALLOON_OFFSET);
+    /// assert_eq!(HeaderName::from_bytes(b"Age").unwrap(), HeaderName::AGE);
+    /// assert_eq!(HeaderName::from_bytes(b"ValueNotify").unwrap(), HeaderName::VALUE_NOTIFY);
+    /// assert_eq!(HeaderName::from_bytes(b"Status").unwrap(), HeaderName::STATUS);
+    /// assert_eq!(HeaderName::from_bytes(b"SenderType").unwrap(), HeaderName::SENDER_TYPE);
+    /// assert_eq!(HeaderName::from_bytes(b"BaseID").unwrap(), HeaderName::BASE_ID);
     /// ```
     pub fn from_bytes(bytes: &[u8]) -> Result<HeaderName, Error> {
         let s = String::from_utf8_lossy(bytes);
@@ -151,7 +238,17 @@ impl HeaderName {
     /// assert_eq!(HeaderName::REFERENCE6.to_vec(), b"Reference6");
     /// assert_eq!(HeaderName::REFERENCE7.to_vec(), b"Reference7");
     /// assert_eq!(HeaderName::SECURITY_LEVEL.to_vec(), b"SecurityLevel");
+    /// assert_eq!(HeaderName::SECURITY_ORIGIN.to_vec(), b"SecurityOrigin");
     /// assert_eq!(HeaderName::VALUE.to_vec(), b"Value");
+    /// assert_eq!(HeaderName::MARKER.to_vec(), b"Marker");
+    /// assert_eq!(HeaderName::ERROR_LEVEL.to_vec(), b"ErrorLevel");
+    /// assert_eq!(HeaderName::ERROR_DESCRIPTION.to_vec(), b"ErrorDescription");
+    /// assert_eq!(HeaderName::BALLOON_OFFSET.to_vec(), b"BalloonOffset");
+    /// assert_eq!(HeaderName::AGE.to_vec(), b"Age");
+    /// assert_eq!(HeaderName::VALUE_NOTIFY.to_vec(), b"ValueNotify");
+    /// assert_eq!(HeaderName::STATUS.to_vec(), b"Status");
+    /// assert_eq!(HeaderName::SENDER_TYPE.to_vec(), b"SenderType");
+    /// assert_eq!(HeaderName::BASE_ID.to_vec(), b"BaseID");
     /// assert_eq!(HeaderName::from_static("X-Extend-Header").unwrap().to_vec(), b"X-Extend-Header");
     /// ```
     pub fn to_vec(&self) -> Vec<u8> {
@@ -165,21 +262,30 @@ impl Display for HeaderName {
             Inner::Charset => write!(f, "Charset"),
             Inner::Sender => write!(f, "Sender"),
             Inner::ID => write!(f, "ID"),
-            Inner::Reference0 => write!(f, "Reference0"),
-            Inner::Reference1 => write!(f, "Reference1"),
-            Inner::Reference2 => write!(f, "Reference2"),
-            Inner::Reference3 => write!(f, "Reference3"),
-            Inner::Reference4 => write!(f, "Reference4"),
-            Inner::Reference5 => write!(f, "Reference5"),
-            Inner::Reference6 => write!(f, "Reference6"),
-            Inner::Reference7 => write!(f, "Reference7"),
+            Inner::Reference(n) => write!(f, "Reference{n}"),
             Inner::SecurityLevel => write!(f, "SecurityLevel"),
+            Inner::SecurityOrigin => write!(f, "SecurityOrigin"),
             Inner::Value => write!(f, "Value"),
+            Inner::Marker => write!(f, "Marker"),
+            Inner::ErrorLevel => write!(f, "ErrorLevel"),
+            Inner::ErrorDescription => write!(f, "ErrorDescription"),
+            Inner::BalloonOffset => write!(f, "BalloonOffset"),
+            Inner::Age => write!(f, "Age"),
+            Inner::ValueNotify => write!(f, "ValueNotify"),
+            Inner::Status => write!(f, "Status"),
+            Inner::SenderType => write!(f, "SenderType"),
+            Inner::BaseID => write!(f, "BaseID"),
             Inner::Other(s) => write!(f, "{s}"),
         }
     }
 }
 
+impl ReferenceName for HeaderName {
+    fn reference_index(&self) -> Option<usize> {
+        HeaderName::reference_index(self)
+    }
+}
+
 impl From<HeaderName> for String {
     fn from(header_name: HeaderName) -> Self {
         header_name.to_string()
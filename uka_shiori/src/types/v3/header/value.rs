@@ -17,13 +17,19 @@ pub enum Error {
 
 type Result<T> = std::result::Result<T, Error>;
 
+/// Separator of the header field that has multiple values.
+const LIST_SEPARATOR: u8 = 0x01;
+
+/// Separator of the rows of the header field that has a table of values.
+const TABLE_SEPARATOR: u8 = 0x02;
+
 /// HeaderValue is the value of the SHIORI header field.
 ///
 /// The value is held in a byte string of the character set and encoding that can be specified in Charset.
 /// Since it is not possible to determine which SHIORI header fields are allowed to contain multibyte characters
 /// and which actually contain multibyte characters,
 /// users should specify them explicitly when retrieving them.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct HeaderValue(Vec<u8>);
 impl HeaderValue {
     /// Extract HeaderValue as an ASCII code string.
@@ -99,6 +105,94 @@ impl HeaderValue {
         Ok(Self(bytes))
     }
 
+    /// Extract HeaderValue as a list of strings separated by byte value 1 (`\x01`) with Charset.
+    ///
+    /// Some SSP header fields such as `ErrorLevel` and `ErrorDescription` contain multiple values.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{Charset, HeaderValue};
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let value = HeaderValue::from(b"warning\x01error".to_vec());
+    /// assert_eq!(value.list_with_charset(Charset::ASCII)?, vec!["warning", "error"]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn list_with_charset(&self, charset: Charset) -> Result<Vec<String>> {
+        self.0
+            .split(|b| *b == LIST_SEPARATOR)
+            .map(|item| HeaderValue::from(item).text_with_charset(charset))
+            .collect()
+    }
+
+    ///　Convert a list of strings to HeaderValue separated by byte value 1 (`\x01`) with Charset.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{Charset, HeaderValue};
+    /// assert_eq!(
+    ///     HeaderValue::from_list_with_charset(&["warning", "error"], Charset::ASCII).unwrap().as_bytes(),
+    ///     b"warning\x01error");
+    /// ```
+    pub fn from_list_with_charset<S: AsRef<str>>(items: &[S], charset: Charset) -> Result<Self> {
+        let mut bytes = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                bytes.push(LIST_SEPARATOR);
+            }
+            bytes.extend(Self::from_static_with_charset(item.as_ref(), charset)?.0);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Extract HeaderValue as a table of strings with Charset.
+    ///
+    /// Rows are separated by byte value 2 (`\x02`) and the columns of each row are separated by byte value 1 (`\x01`),
+    /// as used by resources such as `sakura.recommendsites`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{Charset, HeaderValue};
+    /// # use anyhow::Result;
+    /// # fn main() -> Result<()> {
+    /// let value = HeaderValue::from(b"a\x01http://a/\x02b\x01http://b/".to_vec());
+    /// assert_eq!(
+    ///     value.table_with_charset(Charset::ASCII)?,
+    ///     vec![vec!["a", "http://a/"], vec!["b", "http://b/"]]);
+    /// #     Ok(())
+    /// # }
+    /// ```
+    pub fn table_with_charset(&self, charset: Charset) -> Result<Vec<Vec<String>>> {
+        self.0
+            .split(|b| *b == TABLE_SEPARATOR)
+            .map(|row| HeaderValue::from(row).list_with_charset(charset))
+            .collect()
+    }
+
+    ///　Convert a table of strings to HeaderValue with Charset.
+    ///
+    /// Rows are separated by byte value 2 (`\x02`) and the columns of each row are separated by byte value 1 (`\x01`).
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{Charset, HeaderValue};
+    /// assert_eq!(
+    ///     HeaderValue::from_table_with_charset(&[vec!["a", "http://a/"], vec!["b", "http://b/"]], Charset::ASCII)
+    ///         .unwrap()
+    ///         .as_bytes(),
+    ///     b"a\x01http://a/\x02b\x01http://b/");
+    /// ```
+    pub fn from_table_with_charset<S: AsRef<str>>(
+        rows: &[Vec<S>],
+        charset: Charset,
+    ) -> Result<Self> {
+        let mut bytes = Vec::new();
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                bytes.push(TABLE_SEPARATOR);
+            }
+            bytes.extend(Self::from_list_with_charset(row, charset)?.0);
+        }
+        Ok(Self(bytes))
+    }
+
     /// Convert HeaderValue to bytes.
     pub fn as_bytes(&self) -> Vec<u8> {
         self.0.clone()
@@ -284,4 +378,21 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    pub fn test_list_with_charset_pass_sjis() -> Result<()> {
+        let value =
+            HeaderValue::from_list_with_charset(&["さくら", "", "うにゅう"], Charset::SHIFT_JIS)?;
+        assert_eq!(
+            value.list_with_charset(Charset::SHIFT_JIS)?,
+            vec!["さくら", "", "うにゅう"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_from_list_with_charset_failed_control_character() {
+        let res = HeaderValue::from_list_with_charset(&["warning", "\x01"], Charset::ASCII);
+        assert!(matches!(res, Err(Error::UnprintableCharacters(_))));
+    }
 }
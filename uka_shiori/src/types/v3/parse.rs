@@ -7,13 +7,17 @@ use crate::types::v3::status::StatusCode;
 use crate::types::v3::version::Version;
 use crate::types::v3::ParseError;
 use std::io::Cursor;
-use uka_util::cursor::{lookahead, read_expect, read_match, read_repeat, read_until};
+use uka_util::cursor::{lookahead, read, read_expect, read_match, read_repeat, read_until};
+use uka_util::status::{parse_reason_phrase, Error as ReasonPhraseError};
 
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     #[error("{0}")]
     IO(#[from] std::io::Error),
 
+    #[error("invalid status code: {0:?}")]
+    InvalidStatusCode(String),
+
     #[error("invalid header name: {0:?}")]
     InvalidHeaderName(#[from] HeaderNameError),
 
@@ -30,6 +34,15 @@ pub enum Error {
     UnexpectedEof,
 }
 
+impl From<ReasonPhraseError> for Error {
+    fn from(e: ReasonPhraseError) -> Self {
+        match e {
+            ReasonPhraseError::Io(e) => Error::IO(e),
+            ReasonPhraseError::InvalidStatusCode(s) => Error::InvalidStatusCode(s),
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
 pub fn parse_request(input: &[u8]) -> Result<Request> {
@@ -64,7 +77,11 @@ pub fn parse_response(input: &[u8]) -> Result<Response> {
     let version = parse_version(&mut cursor)?;
     skip_spaces(&mut cursor)?;
     let status_code = parse_status_code(&mut cursor)?;
-    skip_newline(&mut cursor)?;
+    let reason_phrase = parse_reason_phrase(
+        &mut cursor,
+        status_code.as_u16(),
+        status_code.canonical_reason(),
+    )?;
     let headers = parse_headers(&mut cursor)?;
     let charset = headers
         .get(&HeaderName::CHARSET)
@@ -81,6 +98,7 @@ pub fn parse_response(input: &[u8]) -> Result<Response> {
     Ok(Response {
         version,
         status_code,
+        reason_phrase,
         headers,
         charset,
     })
@@ -102,16 +120,13 @@ fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<Version> {
 }
 
 fn parse_status_code(cursor: &mut Cursor<&[u8]>) -> Result<StatusCode> {
-    read_match!(cursor, {
-        b"200 OK" => StatusCode::OK,
-        b"204 No Content" => StatusCode::NO_CONTENT,
-        b"310 Communicate" => StatusCode::COMMUNICATE,
-        b"311 Not Enough" => StatusCode::NOT_ENOUGH,
-        b"312 Advice" => StatusCode::ADVICE,
-        b"400 Bad Request" => StatusCode::BAD_REQUEST,
-        b"500 Internal Server Error" => StatusCode::INTERNAL_SERVER_ERROR,
-    }, 3)
-    .map_err(Error::from)
+    let code = read!(cursor, 3).map_err(Error::from)?;
+    std::str::from_utf8(&code)
+        .ok()
+        .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|s| s.parse::<u16>().ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .ok_or_else(|| Error::InvalidStatusCode(String::from_utf8_lossy(&code).to_string()))
 }
 
 fn parse_headers(cursor: &mut Cursor<&[u8]>) -> Result<HeaderMap> {
@@ -1,10 +1,14 @@
 use crate::types::v3::charset::Charset;
+use crate::types::v3::ghost_status::GhostStatus;
 use crate::types::v3::header::{
     HeaderMap, HeaderName, HeaderNameError, HeaderValue, HeaderValueError,
 };
 use crate::types::v3::parse::{parse_request, Error as ParseError};
+use crate::types::v3::security::{Error as SecurityOriginError, SecurityLevel, SecurityOrigin};
+use crate::types::v3::sender_type::SenderType;
 use crate::types::v3::{Method, Version};
 use uka_util::bag::OrderedBag;
+use uka_util::header::HeaderMapExt;
 
 /// Request is a type that represents an SHIORI v3 request.
 ///
@@ -26,7 +30,7 @@ use uka_util::bag::OrderedBag;
 ///     .unwrap();
 /// assert_eq!(request.method(), Method::GET);
 /// ```
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Request {
     pub(crate) method: Method,
     pub(crate) version: Version,
@@ -149,9 +153,78 @@ impl Request {
         self.headers.get(&HeaderName::REFERENCE7)
     }
 
+    /// Returns ReferenceN in SHIORI header fields for any index `n`.
+    pub fn reference(&self, n: usize) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::reference(n))
+    }
+
+    /// Returns all ReferenceN in SHIORI header fields ordered by index.
+    pub fn references(&self) -> impl Iterator<Item = (usize, &HeaderValue)> {
+        self.headers.references()
+    }
+
     /// Returns SecurityLevel in SHIORI header fields.
-    pub fn security_level(&self) -> Option<&HeaderValue> {
-        self.headers.get(&HeaderName::SECURITY_LEVEL)
+    ///
+    /// Values that are not `local` or `external` are returned as `SecurityLevel::UNKNOWN`.
+    pub fn security_level(&self) -> Option<SecurityLevel> {
+        self.headers.get(&HeaderName::SECURITY_LEVEL).map(|v| {
+            v.text_with_charset(self.charset())
+                .map(|s| SecurityLevel::from_static(s.trim()))
+                .unwrap_or(SecurityLevel::UNKNOWN)
+        })
+    }
+
+    /// Returns Status in SHIORI header fields.
+    ///
+    /// If the value cannot be decoded with the charset of the request, an empty status is returned.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{HeaderName, Method, Request, Version};
+    /// let request = Request::builder()
+    ///     .method(Method::GET)
+    ///     .version(Version::SHIORI_30)
+    ///     .header(HeaderName::STATUS, "talking,balloon(0=0)")
+    ///     .build()
+    ///     .unwrap();
+    /// assert!(request.status().is_some_and(|status| status.is_talking()));
+    /// ```
+    pub fn status(&self) -> Option<GhostStatus> {
+        self.headers.get(&HeaderName::STATUS).map(|v| {
+            v.text_with_charset(self.charset())
+                .map(|s| GhostStatus::from_static(&s))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns SenderType in SHIORI header fields.
+    ///
+    /// If the value cannot be decoded with the charset of the request, an empty sender type is returned.
+    pub fn sender_type(&self) -> Option<SenderType> {
+        self.headers.get(&HeaderName::SENDER_TYPE).map(|v| {
+            v.text_with_charset(self.charset())
+                .map(|s| SenderType::from_static(&s))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns BaseID in SHIORI header fields.
+    pub fn base_id(&self) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::BASE_ID)
+    }
+
+    /// Returns SecurityOrigin in SHIORI header fields.
+    ///
+    /// Returns an error if the header is present but is not of the form `scheme://host[:port]`.
+    pub fn security_origin(&self) -> Result<Option<SecurityOrigin>, SecurityOriginError> {
+        self.headers
+            .get(&HeaderName::SECURITY_ORIGIN)
+            .map(|v| {
+                let s = v.text_with_charset(self.charset()).map_err(|_| {
+                    SecurityOriginError::Invalid(String::from_utf8_lossy(&v.as_bytes()).to_string())
+                })?;
+                SecurityOrigin::from_static(s.trim())
+            })
+            .transpose()
     }
 
     /// Convert request to bytes.
@@ -296,6 +369,34 @@ impl RequestBuilder {
         })
     }
 
+    /// Set ReferenceN SHIORI header field for any index `n`.
+    pub fn reference<V>(self, n: usize, value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.header(HeaderName::reference(n), value)
+    }
+
+    /// Set SecurityLevel SHIORI header field.
+    pub fn security_level(self, level: SecurityLevel) -> Self {
+        self.header(HeaderName::SECURITY_LEVEL, level.to_string())
+    }
+
+    /// Set SecurityOrigin SHIORI header field.
+    pub fn security_origin(self, origin: SecurityOrigin) -> Self {
+        self.header(HeaderName::SECURITY_ORIGIN, origin.to_string())
+    }
+
+    /// Set Status SHIORI header field.
+    pub fn status(self, status: GhostStatus) -> Self {
+        self.header(HeaderName::STATUS, status.to_string())
+    }
+
+    /// Set SenderType SHIORI header field.
+    pub fn sender_type(self, sender_type: SenderType) -> Self {
+        self.header(HeaderName::SENDER_TYPE, sender_type.to_string())
+    }
+
     /// Set SHIORI charset.
     pub fn charset(self, charset: Charset) -> Self {
         self.and_then(|mut inner| {
@@ -1,12 +1,16 @@
 use crate::types::v3::charset::Charset;
+use crate::types::v3::error::ShioriError;
+use crate::types::v3::error_level::ErrorLevel;
 use crate::types::v3::header::{
     HeaderMap, HeaderName, HeaderNameError, HeaderValue, HeaderValueError,
 };
 use crate::types::v3::parse::{parse_response, Error as ParseError};
+use crate::types::v3::request::Request;
 use crate::types::v3::status::StatusCode;
 use crate::types::v3::version::Version;
 use uka_util::bag::OrderedBag;
 use uka_util::encode::Error as EncodeError;
+use uka_util::header::HeaderMapExt;
 
 /// `Response` is a type that represents an SHIORI v3 request.
 ///
@@ -32,6 +36,7 @@ use uka_util::encode::Error as EncodeError;
 pub struct Response {
     pub(crate) version: Version,
     pub(crate) status_code: StatusCode,
+    pub(crate) reason_phrase: Option<String>,
     pub(crate) headers: HeaderMap,
     pub(crate) charset: Charset,
 }
@@ -62,6 +67,69 @@ impl Response {
         Builder::new()
     }
 
+    /// Constructs a `200 OK` response whose `Value` is the script, in the charset of the request.
+    ///
+    /// The script is a string or `uka_sakura::Script` built with its builder.
+    /// If the script cannot be encoded in the charset of the request, it is sent in UTF-8 instead.
+    /// If the script contains control characters such as line breaks, the error is logged
+    /// and `500 Internal Server Error` is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{Charset, HeaderName, Method, Request, Response, StatusCode, Version};
+    /// #
+    /// let request = Request::builder()
+    ///     .method(Method::GET)
+    ///     .version(Version::SHIORI_30)
+    ///     .header(HeaderName::ID, "OnBoot")
+    ///     .charset(Charset::SHIFT_JIS)
+    ///     .build()
+    ///     .unwrap();
+    /// let response = Response::script(&request, "\\h\\s0こんにちは\\e");
+    /// assert_eq!(response.status_code(), StatusCode::OK);
+    /// assert_eq!(response.charset(), Charset::SHIFT_JIS);
+    /// ```
+    pub fn script(request: &Request, script: impl AsRef<str>) -> Self {
+        Self::with_value(request.charset(), script.as_ref())
+    }
+
+    /// Constructs a `204 No Content` response in the charset of the request.
+    pub fn no_content(request: &Request) -> Self {
+        Self::with_status_code(request.charset(), StatusCode::NO_CONTENT)
+    }
+
+    fn with_value(charset: Charset, value: &str) -> Self {
+        let build = |charset| {
+            Self::builder()
+                .version(Version::SHIORI_30)
+                .status_code(StatusCode::OK)
+                .header(HeaderName::VALUE, value)
+                .charset(charset)
+                .build()
+        };
+        // only the characters that the charset cannot represent are retried in UTF-8
+        match build(charset) {
+            Err(Error::FailedEncodeHeaderValue(HeaderValueError::FailedEncode(_))) => {
+                build(Charset::UTF8)
+            }
+            result => result,
+        }
+        .unwrap_or_else(|e| {
+            log::error!("failed to build the response with the value {value:?}: {e}");
+            ShioriError::from(e).into_response()
+        })
+    }
+
+    fn with_status_code(charset: Charset, status_code: StatusCode) -> Self {
+        Self::builder()
+            .version(Version::SHIORI_30)
+            .status_code(status_code)
+            .charset(charset)
+            .build()
+            .expect("response without headers is always valid")
+    }
+
     /// Returns SHIORI version.
     pub fn version(&self) -> Version {
         self.version
@@ -72,6 +140,15 @@ impl Response {
         self.status_code
     }
 
+    /// Returns the reason phrase of the status line.
+    ///
+    /// If no custom reason phrase is set, the one defined in the specification is returned.
+    pub fn reason_phrase(&self) -> Option<&str> {
+        self.reason_phrase
+            .as_deref()
+            .or_else(|| self.status_code.canonical_reason())
+    }
+
     /// Returns SHIORI header fields.
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -142,11 +219,89 @@ impl Response {
         self.headers.get(&HeaderName::VALUE)
     }
 
+    /// ReferenceN for any index `n`
+    pub fn reference(&self, n: usize) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::reference(n))
+    }
+
+    /// All ReferenceN header fields as `(n, value)` pairs in ascending order of `n`.
+    pub fn references(&self) -> impl Iterator<Item = (usize, &HeaderValue)> {
+        self.headers.references()
+    }
+
+    /// Marker
+    pub fn marker(&self) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::MARKER)
+    }
+
+    /// ValueNotify
+    pub fn value_notify(&self) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::VALUE_NOTIFY)
+    }
+
+    /// Age
+    pub fn age(&self) -> Result<Option<u32>, HeaderError> {
+        self.headers
+            .get(&HeaderName::AGE)
+            .map(|v| {
+                let text = v
+                    .text()
+                    .map_err(|e| HeaderError::FailedDecode(HeaderName::AGE, e))?;
+                text.trim()
+                    .parse::<u32>()
+                    .map_err(|_| HeaderError::InvalidValue(HeaderName::AGE, text))
+            })
+            .transpose()
+    }
+
+    /// BalloonOffset as `(x, y)` pairs in the order of the scopes.
+    pub fn balloon_offsets(&self) -> Result<Vec<(i32, i32)>, HeaderError> {
+        self.list(&HeaderName::BALLOON_OFFSET)?
+            .into_iter()
+            .map(|item| {
+                item.split_once(',')
+                    .and_then(|(x, y)| Some((x.trim().parse().ok()?, y.trim().parse().ok()?)))
+                    .ok_or(HeaderError::InvalidValue(HeaderName::BALLOON_OFFSET, item))
+            })
+            .collect()
+    }
+
+    /// ErrorLevel
+    pub fn error_levels(&self) -> Result<Vec<ErrorLevel>, HeaderError> {
+        self.list(&HeaderName::ERROR_LEVEL)?
+            .into_iter()
+            .map(|item| {
+                ErrorLevel::from_static(&item)
+                    .map_err(|_| HeaderError::InvalidValue(HeaderName::ERROR_LEVEL, item))
+            })
+            .collect()
+    }
+
+    /// ErrorDescription
+    pub fn error_descriptions(&self) -> Result<Vec<String>, HeaderError> {
+        self.list(&HeaderName::ERROR_DESCRIPTION)
+    }
+
+    fn list(&self, name: &HeaderName) -> Result<Vec<String>, HeaderError> {
+        self.headers
+            .get(name)
+            .map(|v| {
+                v.list_with_charset(self.charset)
+                    .map_err(|e| HeaderError::FailedDecode(name.clone(), e))
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    }
+
     pub fn to_vec(&self) -> Vec<u8> {
         let mut buf = Vec::new();
         buf.extend_from_slice(self.version.to_string().as_bytes());
         buf.extend_from_slice(b" ");
-        buf.extend_from_slice(self.status_code.to_string().as_bytes());
+        buf.extend_from_slice(self.status_code.as_u16().to_string().as_bytes());
+        if let Some(reason) = self.reason_phrase() {
+            buf.extend_from_slice(b" ");
+            buf.extend_from_slice(reason.as_bytes());
+        }
         buf.extend_from_slice(b"\r\n");
         for (name, value) in self.headers.iter() {
             buf.extend_from_slice(&name.to_vec());
@@ -159,6 +314,15 @@ impl Response {
     }
 }
 
+/// Error that can occur when reading typed SHIORI header fields from response.
+#[derive(thiserror::Error, Debug)]
+pub enum HeaderError {
+    #[error("{1} in `{0}` header")]
+    FailedDecode(HeaderName, #[source] HeaderValueError),
+    #[error("invalid value in `{0}` header: {1:?}")]
+    InvalidValue(HeaderName, String),
+}
+
 /// Error that can occur when build SHIORI response.
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -166,6 +330,8 @@ pub enum Error {
     MissingVersion,
     #[error("status_code is required")]
     MissingStatusCode,
+    #[error("reason phrase cannot contain unprintable characters: {0:?}")]
+    InvalidReasonPhrase(String),
     #[error("charset is required")]
     MissingCharset,
     #[error("{0}")]
@@ -180,10 +346,22 @@ pub enum Error {
 struct Parts {
     version: Option<Version>,
     status_code: Option<StatusCode>,
+    reason_phrase: Option<String>,
     headers: OrderedBag<String, String>,
+    lists: Vec<(HeaderName, Vec<String>)>,
+    tables: Vec<(HeaderName, Vec<Vec<String>>)>,
     charset: Option<Charset>,
 }
 
+impl Parts {
+    fn push_list(&mut self, name: HeaderName, item: String) {
+        match self.lists.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, items)) => items.push(item),
+            None => self.lists.push((name, vec![item])),
+        }
+    }
+}
+
 /// Builder for SHIORI response.
 pub struct Builder {
     inner: Result<Parts, Error>,
@@ -216,6 +394,25 @@ impl Builder {
         })
     }
 
+    /// Set the custom reason phrase of the status line.
+    ///
+    /// If it is not set, the reason phrase defined in the specification is used.
+    pub fn reason_phrase<V>(self, reason_phrase: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.and_then(|parts| {
+            let reason_phrase = reason_phrase.into();
+            if reason_phrase.chars().any(|c| c.is_control()) {
+                return Err(Error::InvalidReasonPhrase(reason_phrase));
+            }
+            Ok(Parts {
+                reason_phrase: Some(reason_phrase),
+                ..parts
+            })
+        })
+    }
+
     /// Set SHIORI header field.
     pub fn header<K, V>(self, name: K, value: V) -> Self
     where
@@ -228,6 +425,92 @@ impl Builder {
         })
     }
 
+    /// Set ReferenceN SHIORI header field for any index `n`.
+    pub fn reference<V>(self, n: usize, value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.header(HeaderName::reference(n), value)
+    }
+
+    /// Set Marker SHIORI header field.
+    pub fn marker<V>(self, value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.header(HeaderName::MARKER, value)
+    }
+
+    /// Set ValueNotify SHIORI header field.
+    pub fn value_notify<V>(self, value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.header(HeaderName::VALUE_NOTIFY, value)
+    }
+
+    /// Set Age SHIORI header field.
+    pub fn age(self, age: u32) -> Self {
+        self.header(HeaderName::AGE, age.to_string())
+    }
+
+    /// Add the offset of the balloon for the next scope to BalloonOffset SHIORI header field.
+    ///
+    /// Multiple offsets are separated by byte value 1 in the order they are added.
+    pub fn balloon_offset(self, x: i32, y: i32) -> Self {
+        self.and_then(|mut inner| {
+            inner.push_list(HeaderName::BALLOON_OFFSET, format!("{x},{y}"));
+            Ok(inner)
+        })
+    }
+
+    /// Add an error to ErrorLevel and ErrorDescription SHIORI header fields.
+    ///
+    /// Multiple errors are separated by byte value 1 in the order they are added.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::{ErrorLevel, Response, StatusCode, Version};
+    /// #
+    /// let response = Response::builder()
+    ///     .version(Version::SHIORI_30)
+    ///     .status_code(StatusCode::OK)
+    ///     .error(ErrorLevel::WARNING, "dictionary not found")
+    ///     .error(ErrorLevel::ERROR, "syntax error")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(response.error_levels().unwrap(), vec![ErrorLevel::WARNING, ErrorLevel::ERROR]);
+    /// ```
+    pub fn error<V>(self, level: ErrorLevel, description: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.and_then(|mut inner| {
+            inner.push_list(HeaderName::ERROR_LEVEL, level.to_string());
+            inner.push_list(HeaderName::ERROR_DESCRIPTION, description.into());
+            Ok(inner)
+        })
+    }
+
+    /// Set SHIORI header field that has a table of values.
+    ///
+    /// Rows are separated by byte value 2 and the columns of each row are separated by byte value 1.
+    pub fn table<K, V>(self, name: K, rows: Vec<Vec<V>>) -> Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.and_then(|mut inner| {
+            let name = HeaderName::from_static(&name.into()).map_err(Error::InvalidHeaderName)?;
+            let rows = rows
+                .into_iter()
+                .map(|row| row.into_iter().map(Into::into).collect())
+                .collect();
+            inner.tables.retain(|(n, _)| *n != name);
+            inner.tables.push((name, rows));
+            Ok(inner)
+        })
+    }
+
     /// Set SHIORI charset.
     pub fn charset(self, charset: Charset) -> Self {
         self.and_then(|mut inner| {
@@ -253,22 +536,41 @@ impl Builder {
     pub fn build(self) -> Result<Response, Error> {
         let inner = self.inner?;
         let charset = inner.charset.unwrap_or(Charset::ASCII);
+        let status_code = inner.status_code.ok_or(Error::MissingStatusCode)?;
+        let mut headers = inner
+            .headers
+            .into_iter()
+            .map(|(k, v)| {
+                HeaderName::from_static(&k)
+                    .map_err(Error::InvalidHeaderName)
+                    .and_then(|name| {
+                        HeaderValue::from_static_with_charset(&v, charset)
+                            .map(|value| (name, value))
+                            .map_err(Error::FailedEncodeHeaderValue)
+                    })
+            })
+            .collect::<Result<HeaderMap, Error>>()?;
+        for (name, items) in inner.lists {
+            headers.insert(
+                name,
+                HeaderValue::from_list_with_charset(&items, charset)
+                    .map_err(Error::FailedEncodeHeaderValue)?,
+            );
+        }
+        for (name, rows) in inner.tables {
+            headers.insert(
+                name,
+                HeaderValue::from_table_with_charset(&rows, charset)
+                    .map_err(Error::FailedEncodeHeaderValue)?,
+            );
+        }
         Ok(Response {
             version: inner.version.ok_or(Error::MissingVersion)?,
-            status_code: inner.status_code.ok_or(Error::MissingStatusCode)?,
-            headers: inner
-                .headers
-                .into_iter()
-                .map(|(k, v)| {
-                    HeaderName::from_static(&k)
-                        .map_err(Error::InvalidHeaderName)
-                        .and_then(|name| {
-                            HeaderValue::from_static_with_charset(&v, charset)
-                                .map(|value| (name, value))
-                                .map_err(Error::FailedEncodeHeaderValue)
-                        })
-                })
-                .collect::<Result<HeaderMap, Error>>()?,
+            status_code,
+            reason_phrase: inner
+                .reason_phrase
+                .filter(|v| !v.is_empty() && Some(v.as_str()) != status_code.canonical_reason()),
+            headers,
             charset,
         })
     }
@@ -283,13 +585,102 @@ impl Builder {
     }
 }
 
+/// `IntoResponse` converts the value returned by a handler into a [`Response`].
+///
+/// - `Response` is returned as it is.
+/// - `String`, `&str` and `uka_sakura::Script` become `200 OK` with the `Value` header in UTF-8.
+///   Use [`Response::script`] to answer in the charset of the request.
+/// - `StatusCode` becomes an empty response with the status code.
+/// - `()` and `None` become `204 No Content`.
+/// - `Result<T, E>` is converted from either of `T` and `E`.
 pub trait IntoResponse {
+    /// Convert the value into a response.
     fn into_response(self) -> Response;
+
+    /// Convert the value into a response, or returns `ShioriError` as it is.
+    ///
+    /// Handlers use this instead of [`IntoResponse::into_response`], so that the errors they return
+    /// reach the layers such as `MapErrorLayer` before being answered.
+    fn into_result(self) -> Result<Response, ShioriError>
+    where
+        Self: Sized,
+    {
+        Ok(self.into_response())
+    }
+}
+
+impl IntoResponse for Response {
+    fn into_response(self) -> Response {
+        self
+    }
+}
+
+impl IntoResponse for String {
+    fn into_response(self) -> Response {
+        self.as_str().into_response()
+    }
+}
+
+impl IntoResponse for &str {
+    fn into_response(self) -> Response {
+        Response::with_value(Charset::UTF8, self)
+    }
+}
+
+impl IntoResponse for uka_sakura::Script {
+    fn into_response(self) -> Response {
+        self.as_str().into_response()
+    }
+}
+
+impl IntoResponse for StatusCode {
+    fn into_response(self) -> Response {
+        Response::with_status_code(Charset::UTF8, self)
+    }
+}
+
+impl IntoResponse for () {
+    fn into_response(self) -> Response {
+        StatusCode::NO_CONTENT.into_response()
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Option<T> {
+    fn into_response(self) -> Response {
+        match self {
+            Some(value) => value.into_response(),
+            None => ().into_response(),
+        }
+    }
+
+    fn into_result(self) -> Result<Response, ShioriError> {
+        match self {
+            Some(value) => value.into_result(),
+            None => ().into_result(),
+        }
+    }
+}
+
+impl<T: IntoResponse, E: IntoResponse> IntoResponse for Result<T, E> {
+    fn into_response(self) -> Response {
+        match self {
+            Ok(value) => value.into_response(),
+            Err(e) => e.into_response(),
+        }
+    }
+
+    fn into_result(self) -> Result<Response, ShioriError> {
+        match self {
+            Ok(value) => value.into_result(),
+            Err(e) => e.into_result(),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::v3::Method;
 
     #[test]
     fn test_response_parse_and_builder_will_be_same() -> anyhow::Result<()> {
@@ -322,4 +713,116 @@ mod tests {
 
         Ok(())
     }
+
+    fn request(charset: Charset) -> anyhow::Result<Request> {
+        Ok(Request::builder()
+            .method(Method::GET)
+            .version(Version::SHIORI_30)
+            .header(HeaderName::ID, "OnBoot")
+            .charset(charset)
+            .build()?)
+    }
+
+    #[test]
+    fn test_script_inherits_request_charset() -> anyhow::Result<()> {
+        let response = Response::script(&request(Charset::SHIFT_JIS)?, "\\h\\s0こんにちは\\e");
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.charset(), Charset::SHIFT_JIS);
+        assert_eq!(
+            response
+                .value()
+                .map(|v| v.text_with_charset(Charset::SHIFT_JIS))
+                .transpose()?
+                .as_deref(),
+            Some("\\h\\s0こんにちは\\e")
+        );
+
+        let script = uka_sakura::Script::builder()
+            .sakura()
+            .surface(0)
+            .text("100%")
+            .end();
+        let response = Response::script(&request(Charset::SHIFT_JIS)?, &script);
+        assert_eq!(
+            response
+                .value()
+                .map(|v| v.text_with_charset(Charset::SHIFT_JIS))
+                .transpose()?
+                .as_deref(),
+            Some(r"\0\s[0]100\%\e")
+        );
+        assert_eq!(script.into_response().charset(), Charset::UTF8);
+
+        let response = Response::no_content(&request(Charset::EUC_JP)?);
+        assert_eq!(response.status_code(), StatusCode::NO_CONTENT);
+        assert_eq!(response.charset(), Charset::EUC_JP);
+        Ok(())
+    }
+
+    #[test]
+    fn test_script_falls_back_to_utf8() -> anyhow::Result<()> {
+        let response = Response::script(&request(Charset::ASCII)?, "こんにちは");
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.charset(), Charset::UTF8);
+
+        let response = Response::script(&request(Charset::ASCII)?, "line\r\nbreak");
+        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+
+        let response = Response::script(&request(Charset::ASCII)?, "こんにちは\r\n");
+        assert_eq!(response.status_code(), StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(response.charset(), Charset::ASCII);
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_response() -> anyhow::Result<()> {
+        let response = "こんにちは".into_response();
+        assert_eq!(response.status_code(), StatusCode::OK);
+        assert_eq!(response.charset(), Charset::UTF8);
+        assert_eq!(
+            response
+                .value()
+                .map(|v| v.text_with_charset(Charset::UTF8))
+                .transpose()?
+                .as_deref(),
+            Some("こんにちは")
+        );
+
+        assert_eq!(
+            String::from("hello").into_response().status_code(),
+            StatusCode::OK
+        );
+        assert_eq!(().into_response().status_code(), StatusCode::NO_CONTENT);
+        assert_eq!(
+            None::<String>.into_response().status_code(),
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            StatusCode::BAD_REQUEST.into_response().status_code(),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            Err::<String, _>(StatusCode::NOT_ENOUGH)
+                .into_response()
+                .status_code(),
+            StatusCode::NOT_ENOUGH
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_result_keeps_shiori_error() {
+        let result: Result<String, ShioriError> =
+            Err(ShioriError::from(Error::MissingStatusCode)
+                .with_status_code(StatusCode::BAD_REQUEST));
+        let error = result.into_result().expect_err("ShioriError must be kept");
+        assert_eq!(error.into_response().status_code(), StatusCode::BAD_REQUEST);
+
+        let result: Result<String, StatusCode> = Err(StatusCode::BAD_REQUEST);
+        let response = result.into_result().expect("StatusCode is a response");
+        assert_eq!(response.status_code(), StatusCode::BAD_REQUEST);
+
+        let response = Some(Ok::<_, ShioriError>("hello")).into_result();
+        assert_eq!(response.map(|v| v.status_code()).ok(), Some(StatusCode::OK));
+    }
 }
@@ -0,0 +1,240 @@
+use std::fmt;
+
+/// Error that can occur when converting SecurityOrigin from string.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid security origin `{0}`, the security origin must be `scheme://host[:port]`")]
+    Invalid(String),
+}
+type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+enum Inner {
+    Local,
+    External,
+    Unknown,
+}
+
+/// SecurityLevel is the level of trust of the event source, sent in the `SecurityLevel` header.
+///
+/// `local` means that the event was raised on the local machine, and `external` means that
+/// the event came from outside (e.g. SSTP over the network).
+/// Values other than these are treated as `unknown`.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::SecurityLevel;
+/// #
+/// # let level = SecurityLevel::LOCAL;
+/// match level {
+///     SecurityLevel::LOCAL => assert_eq!(level.to_string(), "local"),
+///     SecurityLevel::EXTERNAL => assert_eq!(level.to_string(), "external"),
+///     SecurityLevel::UNKNOWN => assert_eq!(level.to_string(), "unknown"),
+/// }
+/// ```
+#[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
+pub struct SecurityLevel(Inner);
+impl SecurityLevel {
+    /// local
+    pub const LOCAL: SecurityLevel = SecurityLevel(Inner::Local);
+
+    /// external
+    pub const EXTERNAL: SecurityLevel = SecurityLevel(Inner::External);
+
+    /// unknown
+    pub const UNKNOWN: SecurityLevel = SecurityLevel(Inner::Unknown);
+
+    ///　Converts a str to SecurityLevel.
+    ///
+    /// The comparison is case-insensitive, and unrecognized values are converted to `UNKNOWN`.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::SecurityLevel;
+    /// assert_eq!(SecurityLevel::from_static("local"), SecurityLevel::LOCAL);
+    /// assert_eq!(SecurityLevel::from_static("External"), SecurityLevel::EXTERNAL);
+    /// assert_eq!(SecurityLevel::from_static("remote"), SecurityLevel::UNKNOWN);
+    /// ```
+    pub fn from_static(s: &str) -> SecurityLevel {
+        if s.eq_ignore_ascii_case("local") {
+            SecurityLevel::LOCAL
+        } else if s.eq_ignore_ascii_case("external") {
+            SecurityLevel::EXTERNAL
+        } else {
+            SecurityLevel::UNKNOWN
+        }
+    }
+
+    /// Returns true if the event was raised on the local machine.
+    pub fn is_local(&self) -> bool {
+        *self == SecurityLevel::LOCAL
+    }
+
+    /// Returns true if the event came from outside the local machine.
+    pub fn is_external(&self) -> bool {
+        *self == SecurityLevel::EXTERNAL
+    }
+}
+
+impl fmt::Display for SecurityLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Inner::*;
+
+        f.write_str(match self.0 {
+            Local => "local",
+            External => "external",
+            Unknown => "unknown",
+        })
+    }
+}
+
+/// SecurityOrigin is the origin of the event source, sent in the `SecurityOrigin` header.
+///
+/// The origin is written in the form of `scheme://host[:port]`.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::SecurityOrigin;
+/// #
+/// let origin = SecurityOrigin::from_static("http://127.0.0.1:9801").unwrap();
+/// assert_eq!(origin.scheme(), "http");
+/// assert_eq!(origin.host(), "127.0.0.1");
+/// assert_eq!(origin.port(), Some(9801));
+/// assert_eq!(origin.to_string(), "http://127.0.0.1:9801");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SecurityOrigin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+impl SecurityOrigin {
+    /// Constructs a new SecurityOrigin.
+    pub fn new(scheme: impl Into<String>, host: impl Into<String>, port: Option<u16>) -> Self {
+        Self {
+            scheme: scheme.into(),
+            host: host.into(),
+            port,
+        }
+    }
+
+    ///　Converts a str to SecurityOrigin.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::SecurityOrigin;
+    /// #
+    /// assert_eq!(
+    ///     SecurityOrigin::from_static("https://example.com/").unwrap(),
+    ///     SecurityOrigin::new("https", "example.com", None)
+    /// );
+    /// assert_eq!(
+    ///     SecurityOrigin::from_static("http://[::1]:9801").unwrap(),
+    ///     SecurityOrigin::new("http", "::1", Some(9801))
+    /// );
+    /// assert!(SecurityOrigin::from_static("example.com").is_err());
+    /// ```
+    pub fn from_static(s: &str) -> Result<SecurityOrigin> {
+        let invalid = || Error::Invalid(s.to_string());
+
+        let (scheme, rest) = s.split_once("://").ok_or_else(invalid)?;
+        if scheme.is_empty()
+            || !scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+        {
+            return Err(invalid());
+        }
+        let authority = rest.strip_suffix('/').unwrap_or(rest);
+
+        let (host, port) = match authority.strip_prefix('[') {
+            Some(rest) => {
+                let (host, rest) = rest.split_once(']').ok_or_else(invalid)?;
+                match rest {
+                    "" => (host, None),
+                    _ => (host, Some(rest.strip_prefix(':').ok_or_else(invalid)?)),
+                }
+            }
+            None => match authority.rsplit_once(':') {
+                Some((host, port)) => (host, Some(port)),
+                None => (authority, None),
+            },
+        };
+        if host.is_empty() || host.contains(['/', ' ']) {
+            return Err(invalid());
+        }
+        let port = port
+            .map(|p| p.parse::<u16>().map_err(|_| invalid()))
+            .transpose()?;
+
+        Ok(SecurityOrigin::new(scheme, host, port))
+    }
+
+    ///　Converts a string to SecurityOrigin.
+    pub fn from_string(s: impl Into<String>) -> Result<SecurityOrigin> {
+        SecurityOrigin::from_static(s.into().as_str())
+    }
+
+    /// Returns the scheme of the origin.
+    pub fn scheme(&self) -> &str {
+        &self.scheme
+    }
+
+    /// Returns the host of the origin.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// Returns the port of the origin if it is specified.
+    pub fn port(&self) -> Option<u16> {
+        self.port
+    }
+}
+
+impl fmt::Display for SecurityOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let host = if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.clone()
+        };
+        match self.port {
+            Some(port) => write!(f, "{}://{host}:{port}", self.scheme),
+            None => write!(f, "{}://{host}", self.scheme),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::http("http://localhost", "http", "localhost", None)]
+    #[case::port("http://127.0.0.1:9801", "http", "127.0.0.1", Some(9801))]
+    #[case::trailing_slash("https://example.com:443/", "https", "example.com", Some(443))]
+    #[case::ipv6("http://[::1]:9801", "http", "::1", Some(9801))]
+    fn test_security_origin_from_static(
+        #[case] input: &str,
+        #[case] scheme: &str,
+        #[case] host: &str,
+        #[case] port: Option<u16>,
+    ) -> anyhow::Result<()> {
+        let origin = SecurityOrigin::from_static(input)?;
+        assert_eq!(origin.scheme(), scheme);
+        assert_eq!(origin.host(), host);
+        assert_eq!(origin.port(), port);
+        assert_eq!(origin.to_string(), input.trim_end_matches('/'));
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[case::no_scheme("localhost")]
+    #[case::empty_host("http://")]
+    #[case::invalid_port("http://localhost:port")]
+    #[case::port_out_of_range("http://localhost:65536")]
+    #[case::path("http://localhost/path")]
+    fn test_security_origin_from_static_failed(#[case] input: &str) {
+        let result = SecurityOrigin::from_static(input);
+        assert!(matches!(result, Err(Error::Invalid(s)) if s == input));
+    }
+}
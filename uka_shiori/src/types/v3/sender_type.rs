@@ -0,0 +1,149 @@
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+enum Inner {
+    Internal,
+    External,
+    SakuraApi,
+    Embed,
+    Raise,
+    Property,
+    Plugin,
+    Sstp,
+    Communicate,
+    Other(String),
+}
+
+/// SenderTypeFlag is a flag of the `SenderType` header that SSP sends with SHIORI requests.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::SenderTypeFlag;
+/// assert_eq!(SenderTypeFlag::from_static("raise"), SenderTypeFlag::RAISE);
+/// assert_eq!(SenderTypeFlag::from_static("unknown").to_string(), "unknown");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct SenderTypeFlag(Inner);
+impl SenderTypeFlag {
+    /// internal: the event is raised by the baseware itself.
+    pub const INTERNAL: SenderTypeFlag = SenderTypeFlag(Inner::Internal);
+
+    /// external: the event is raised from outside of the baseware.
+    pub const EXTERNAL: SenderTypeFlag = SenderTypeFlag(Inner::External);
+
+    /// sakuraapi: the event is raised by SakuraAPI (window messages).
+    pub const SAKURA_API: SenderTypeFlag = SenderTypeFlag(Inner::SakuraApi);
+
+    /// embed: the event is raised by `\![embed]`.
+    pub const EMBED: SenderTypeFlag = SenderTypeFlag(Inner::Embed);
+
+    /// raise: the event is raised by `\![raise]` or `\![notify]`.
+    pub const RAISE: SenderTypeFlag = SenderTypeFlag(Inner::Raise);
+
+    /// property: the event is raised by property system.
+    pub const PROPERTY: SenderTypeFlag = SenderTypeFlag(Inner::Property);
+
+    /// plugin: the event is raised by a plugin.
+    pub const PLUGIN: SenderTypeFlag = SenderTypeFlag(Inner::Plugin);
+
+    /// sstp: the event is raised by SSTP.
+    pub const SSTP: SenderTypeFlag = SenderTypeFlag(Inner::Sstp);
+
+    /// communicate: the event is raised by communication between ghosts.
+    pub const COMMUNICATE: SenderTypeFlag = SenderTypeFlag(Inner::Communicate);
+
+    ///　Converts a str to SenderTypeFlag.
+    ///
+    /// Unrecognized flags are kept as they are.
+    pub fn from_static(s: &str) -> SenderTypeFlag {
+        match s {
+            "internal" => SenderTypeFlag::INTERNAL,
+            "external" => SenderTypeFlag::EXTERNAL,
+            "sakuraapi" => SenderTypeFlag::SAKURA_API,
+            "embed" => SenderTypeFlag::EMBED,
+            "raise" => SenderTypeFlag::RAISE,
+            "property" => SenderTypeFlag::PROPERTY,
+            "plugin" => SenderTypeFlag::PLUGIN,
+            "sstp" => SenderTypeFlag::SSTP,
+            "communicate" => SenderTypeFlag::COMMUNICATE,
+            _ => SenderTypeFlag(Inner::Other(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for SenderTypeFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Inner::*;
+
+        f.write_str(match &self.0 {
+            Internal => "internal",
+            External => "external",
+            SakuraApi => "sakuraapi",
+            Embed => "embed",
+            Raise => "raise",
+            Property => "property",
+            Plugin => "plugin",
+            Sstp => "sstp",
+            Communicate => "communicate",
+            Other(s) => s,
+        })
+    }
+}
+
+/// SenderType is the set of flags in the `SenderType` header, e.g. `internal,raise`.
+///
+/// ```rust
+/// # use uka_shiori::types::v3::{SenderType, SenderTypeFlag};
+/// let sender_type = SenderType::from_static("internal,raise");
+/// assert!(sender_type.is_internal());
+/// assert!(sender_type.contains(&SenderTypeFlag::RAISE));
+/// assert_eq!(sender_type.to_string(), "internal,raise");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash, Default)]
+pub struct SenderType(Vec<SenderTypeFlag>);
+impl SenderType {
+    /// Constructs a new SenderType from flags.
+    pub fn new<I>(flags: I) -> SenderType
+    where
+        I: IntoIterator<Item = SenderTypeFlag>,
+    {
+        SenderType(flags.into_iter().collect())
+    }
+
+    ///　Converts a str to SenderType.
+    pub fn from_static(s: &str) -> SenderType {
+        SenderType(
+            s.split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(SenderTypeFlag::from_static)
+                .collect(),
+        )
+    }
+
+    /// Returns the flags in the order they were written.
+    pub fn flags(&self) -> &[SenderTypeFlag] {
+        &self.0
+    }
+
+    /// Returns true if the sender type contains the flag.
+    pub fn contains(&self, flag: &SenderTypeFlag) -> bool {
+        self.0.contains(flag)
+    }
+
+    /// Returns true if the event is raised by the baseware itself.
+    pub fn is_internal(&self) -> bool {
+        self.contains(&SenderTypeFlag::INTERNAL)
+    }
+
+    /// Returns true if the event is raised from outside of the baseware.
+    pub fn is_external(&self) -> bool {
+        self.contains(&SenderTypeFlag::EXTERNAL)
+    }
+}
+
+impl fmt::Display for SenderType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let flags = self.0.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        f.write_str(&flags.join(","))
+    }
+}
@@ -1,8 +1,17 @@
 use std::fmt;
 use std::fmt::Display;
 
+/// Error that can occur when converting StatusCode from integer.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid status code `{0}`, the status code must be three digits")]
+    Invalid(u16),
+}
+
 /// StatusCode represents the status code of the SHIORI response.
 ///
+/// Status codes that are not defined in the specification can also be represented with [`StatusCode::from_u16`].
+///
 /// # Examples
 ///
 /// ```rust
@@ -14,6 +23,7 @@ use std::fmt::Display;
 /// assert_eq!(StatusCode::ADVICE.to_string(), "312 Advice");
 /// assert_eq!(StatusCode::BAD_REQUEST.to_string(), "400 Bad Request");
 /// assert_eq!(StatusCode::INTERNAL_SERVER_ERROR.to_string(), "500 Internal Server Error");
+/// assert_eq!(StatusCode::from_u16(299).unwrap().to_string(), "299");
 /// ```
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
 pub struct StatusCode(u16);
@@ -42,19 +52,77 @@ impl StatusCode {
     // 5xx - Server Error
     /// 500 Internal Server Error
     pub const INTERNAL_SERVER_ERROR: StatusCode = StatusCode(500);
+
+    ///　Converts a u16 to StatusCode.
+    ///
+    /// Any three digits code is accepted, even if it is not defined in the specification.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::StatusCode;
+    /// assert_eq!(StatusCode::from_u16(200).unwrap(), StatusCode::OK);
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().as_u16(), 299);
+    /// assert!(StatusCode::from_u16(99).is_err());
+    /// assert!(StatusCode::from_u16(1000).is_err());
+    /// ```
+    pub fn from_u16(code: u16) -> Result<StatusCode, Error> {
+        if (100..1000).contains(&code) {
+            Ok(StatusCode(code))
+        } else {
+            Err(Error::Invalid(code))
+        }
+    }
+
+    /// Returns the status code as u16.
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the reason phrase defined in the specification, if any.
+    ///
+    /// ```rust
+    /// # use uka_shiori::types::v3::StatusCode;
+    /// assert_eq!(StatusCode::OK.canonical_reason(), Some("OK"));
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().canonical_reason(), None);
+    /// ```
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        match self.0 {
+            200 => Some("OK"),
+            204 => Some("No Content"),
+            310 => Some("Communicate"),
+            311 => Some("Not Enough"),
+            312 => Some("Advice"),
+            400 => Some("Bad Request"),
+            500 => Some("Internal Server Error"),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the status code is in the range 200-299.
+    pub fn is_success(&self) -> bool {
+        uka_util::status::is_success(self.0)
+    }
+
+    /// Returns true if the status code is in the range 300-399.
+    pub fn is_redirection(&self) -> bool {
+        uka_util::status::is_redirection(self.0)
+    }
+
+    /// Returns true if the status code is in the range 400-499.
+    pub fn is_client_error(&self) -> bool {
+        uka_util::status::is_client_error(self.0)
+    }
+
+    /// Returns true if the status code is in the range 500-599.
+    pub fn is_server_error(&self) -> bool {
+        uka_util::status::is_server_error(self.0)
+    }
 }
 
 impl Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
-            200 => write!(f, "200 OK"),
-            204 => write!(f, "204 No Content"),
-            310 => write!(f, "310 Communicate"),
-            311 => write!(f, "311 Not Enough"),
-            312 => write!(f, "312 Advice"),
-            400 => write!(f, "400 Bad Request"),
-            500 => write!(f, "500 Internal Server Error"),
-            _ => unreachable!("unreachable because StatusCode cannot be new instance"),
+        match self.canonical_reason() {
+            Some(reason) => write!(f, "{} {reason}", self.0),
+            None => write!(f, "{}", self.0),
         }
     }
 }
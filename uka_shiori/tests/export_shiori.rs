@@ -0,0 +1,79 @@
+use std::mem::ManuallyDrop;
+use std::path::PathBuf;
+use uka_shiori::runtime::{Context, ContextData, Router};
+use uka_shiori::types::v3;
+use uka_util::ptr::RawPtr;
+
+struct Data;
+impl ContextData for Data {
+    type Error = v3::ShioriError;
+
+    fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+#[uka_shiori::shiori]
+fn service() -> Router<Data> {
+    Router::new()
+        .get("OnBoot", |_ctx: Context<Data>, _req: v3::Request| async {
+            v3::Response::builder()
+                .version(v3::Version::SHIORI_30)
+                .status_code(v3::StatusCode::OK)
+                .header(v3::HeaderName::VALUE, "\\h\\s0hello\\e")
+                .build()
+                .map_err(v3::ShioriError::from)
+        })
+        .get("OnPanic", on_panic)
+}
+
+async fn on_panic(_ctx: Context<Data>, _req: v3::Request) -> v3::Response {
+    panic!("handler panicked")
+}
+
+fn call(id: &str) -> v3::Response {
+    let req = v3::Request::builder()
+        .method(v3::Method::GET)
+        .version(v3::Version::SHIORI_30)
+        .header(v3::HeaderName::ID, id)
+        .build()
+        .expect("failed to build request");
+    let bytes = ManuallyDrop::new(req.to_vec());
+    let mut len = bytes.len();
+    let h = unsafe { request(bytes.as_ptr() as isize, &mut len) };
+    assert_ne!(h, 0);
+
+    let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+    v3::Response::parse(unsafe { ptr.as_slice() }).expect("failed to parse response")
+}
+
+#[test]
+fn export_shiori_load_request_unload() {
+    // the adapter is not constructed until the first `load`
+    assert!(!unload());
+    let bytes = ManuallyDrop::new(b"GET SHIORI/3.0\r\n\r\n".to_vec());
+    let mut len = bytes.len();
+    assert_eq!(unsafe { request(bytes.as_ptr() as isize, &mut len) }, 0);
+
+    let path = std::env::temp_dir().into_os_string().into_encoded_bytes();
+    let path = ManuallyDrop::new(path);
+    assert!(unsafe { load(path.as_ptr() as isize, path.len()) });
+
+    let resp = call("OnBoot");
+    assert_eq!(resp.status_code(), v3::StatusCode::OK);
+    assert_eq!(
+        resp.value()
+            .map(|v| v.text())
+            .transpose()
+            .ok()
+            .flatten()
+            .as_deref(),
+        Some("\\h\\s0hello\\e")
+    );
+
+    let resp = call("OnPanic");
+    assert_eq!(resp.status_code(), v3::StatusCode::INTERNAL_SERVER_ERROR);
+
+    assert!(unload());
+    assert!(!unload());
+}
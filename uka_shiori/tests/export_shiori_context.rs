@@ -0,0 +1,41 @@
+use std::mem::ManuallyDrop;
+use std::path::PathBuf;
+use uka_shiori::runtime::{box_handler, BoxHandlerV3, Context, ContextData};
+use uka_shiori::types::v3;
+use uka_util::ptr::RawPtr;
+
+struct Data;
+impl ContextData for Data {
+    type Error = v3::ShioriError;
+
+    fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+        Ok(Self)
+    }
+}
+
+/// The context cannot be inferred from the alias, so it is given to the attribute.
+type Service = BoxHandlerV3<Data>;
+
+#[uka_shiori::shiori(context = Data)]
+fn service() -> Service {
+    box_handler(|_ctx: Context<Data>, req: v3::Request| async move {
+        Ok(v3::Response::no_content(&req))
+    })
+}
+
+#[test]
+fn export_shiori_with_context() {
+    let path = std::env::temp_dir().into_os_string().into_encoded_bytes();
+    let path = ManuallyDrop::new(path);
+    assert!(unsafe { load(path.as_ptr() as isize, path.len()) });
+
+    let bytes = ManuallyDrop::new(b"GET SHIORI/3.0\r\nID: OnBoot\r\n\r\n".to_vec());
+    let mut len = bytes.len();
+    let h = unsafe { request(bytes.as_ptr() as isize, &mut len) };
+    assert_ne!(h, 0);
+    let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+    let resp = v3::Response::parse(unsafe { ptr.as_slice() }).expect("failed to parse response");
+    assert_eq!(resp.status_code(), v3::StatusCode::NO_CONTENT);
+
+    assert!(unload());
+}
@@ -0,0 +1,66 @@
+use std::mem::ManuallyDrop;
+use std::path::PathBuf;
+use uka_shiori::runtime::{Context, ContextData};
+use uka_shiori::types::v3;
+use uka_util::ptr::RawPtr;
+
+struct Data {
+    name: String,
+}
+impl ContextData for Data {
+    type Error = v3::ShioriError;
+
+    fn new(_path: PathBuf) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: "uka".to_string(),
+        })
+    }
+}
+
+#[uka_shiori::shiori(crash_report)]
+async fn handler(ctx: Context<Data>, req: v3::Request) -> v3::Response {
+    match req.id().and_then(|v| v.text().ok()).as_deref() {
+        Some("OnPanic") => panic!("handler panicked"),
+        _ => v3::Response::script(&req, format!("\\h\\s0{}\\e", ctx.name)),
+    }
+}
+
+fn call(id: &str) -> v3::Response {
+    let req = v3::Request::builder()
+        .method(v3::Method::GET)
+        .version(v3::Version::SHIORI_30)
+        .header(v3::HeaderName::ID, id)
+        .build()
+        .expect("failed to build request");
+    let bytes = ManuallyDrop::new(req.to_vec());
+    let mut len = bytes.len();
+    let h = unsafe { request(bytes.as_ptr() as isize, &mut len) };
+    assert_ne!(h, 0);
+
+    let ptr = unsafe { RawPtr::<[u8]>::from_raw_address_parts(h, len) };
+    v3::Response::parse(unsafe { ptr.as_slice() }).expect("failed to parse response")
+}
+
+#[test]
+fn export_shiori_async_handler_with_crash_report() {
+    let dir = std::env::temp_dir().join("uka_shiori_export_handler");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("failed to create temp dir");
+    let path = ManuallyDrop::new(dir.clone().into_os_string().into_encoded_bytes());
+    assert!(unsafe { load(path.as_ptr() as isize, path.len()) });
+
+    let resp = call("OnBoot");
+    assert_eq!(resp.status_code(), v3::StatusCode::OK);
+    assert_eq!(
+        resp.value().and_then(|v| v.text().ok()).as_deref(),
+        Some("\\h\\s0uka\\e")
+    );
+
+    let resp = call("OnPanic");
+    assert_eq!(resp.status_code(), v3::StatusCode::INTERNAL_SERVER_ERROR);
+    let report =
+        std::fs::read_to_string(dir.join("crash_report.txt")).expect("crash report is written");
+    assert!(report.contains("handler panicked"));
+
+    assert!(unload());
+}
@@ -314,3 +314,337 @@ fn spec_shiori_response_character_set_for_response_header_names_is_based_on_rfc_
 
     Ok(())
 }
+
+/// This is an extended specification of uka-rs.
+///
+/// `ReferenceN` is not limited to `Reference0` to `Reference7`.
+/// SSP sends `Reference8` and above in events such as `OnUpdateReady` and `OnNotifyOSInfo`,
+/// so any index is handled as `ReferenceN`.
+#[test]
+fn spec_shiori_request_reference_headers_are_unbounded() -> anyhow::Result<()> {
+    let input = [
+        b"NOTIFY SHIORI/3.0\r\n".to_vec(),
+        b"Sender: SSP\r\n".to_vec(),
+        b"ID: OnNotifyOSInfo\r\n".to_vec(),
+        b"Reference0: zero\r\n".to_vec(),
+        b"Reference10: ten\r\n".to_vec(),
+        b"Reference8: eight\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v3::Request::parse(&input)?;
+    assert_eq!(
+        request.headers().get(&HeaderName::reference(8)),
+        request.reference(8)
+    );
+    assert_eq!(
+        request.reference(10).map(|v| v.text()).transpose()?,
+        Some("ten".to_string())
+    );
+    assert_eq!(
+        request
+            .references()
+            .map(|(n, v)| Ok((n, v.text()?)))
+            .collect::<anyhow::Result<Vec<_>>>()?,
+        vec![
+            (0, "zero".to_string()),
+            (8, "eight".to_string()),
+            (10, "ten".to_string())
+        ]
+    );
+    assert_eq!(request.to_vec(), input);
+
+    let built = v3::Request::builder()
+        .method(v3::Method::NOTIFY)
+        .version(v3::Version::SHIORI_30)
+        .header(HeaderName::SENDER, "SSP")
+        .header(HeaderName::ID, "OnNotifyOSInfo")
+        .reference(0, "zero")
+        .reference(10, "ten")
+        .reference(8, "eight")
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    Ok(())
+}
+
+/// This is an extended specification of uka-rs.
+///
+/// `SecurityLevel` and `SecurityOrigin` are sent by SSP to tell where the event came from.
+/// `SecurityLevel` is parsed as `local`, `external` or `unknown`,
+/// and `SecurityOrigin` is parsed as `scheme://host[:port]`.
+#[test]
+fn spec_shiori_request_security_level_and_origin() -> anyhow::Result<()> {
+    let input = [
+        b"NOTIFY SHIORI/3.0\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"Sender: SSP\r\n".to_vec(),
+        b"ID: OnSSTPBreak\r\n".to_vec(),
+        b"SecurityLevel: external\r\n".to_vec(),
+        b"SecurityOrigin: http://192.168.0.10:9801\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v3::Request::parse(&input)?;
+    assert_eq!(request.security_level(), Some(v3::SecurityLevel::EXTERNAL));
+    let origin = request.security_origin()?.expect("SecurityOrigin header");
+    assert_eq!(origin.scheme(), "http");
+    assert_eq!(origin.host(), "192.168.0.10");
+    assert_eq!(origin.port(), Some(9801));
+
+    let built = v3::Request::builder()
+        .method(v3::Method::NOTIFY)
+        .version(v3::Version::SHIORI_30)
+        .charset(Charset::UTF8)
+        .header(HeaderName::SENDER, "SSP")
+        .header(HeaderName::ID, "OnSSTPBreak")
+        .security_level(v3::SecurityLevel::EXTERNAL)
+        .security_origin(v3::SecurityOrigin::new("http", "192.168.0.10", Some(9801)))
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    let request = v3::Request::builder()
+        .method(v3::Method::GET)
+        .version(v3::Version::SHIORI_30)
+        .header(HeaderName::SECURITY_LEVEL, "remote")
+        .header(HeaderName::SECURITY_ORIGIN, "192.168.0.10")
+        .build()?;
+    assert_eq!(request.security_level(), Some(v3::SecurityLevel::UNKNOWN));
+    assert!(request.security_origin().is_err());
+
+    let request = v3::Request::builder()
+        .method(v3::Method::GET)
+        .version(v3::Version::SHIORI_30)
+        .build()?;
+    assert_eq!(request.security_level(), None);
+    assert_eq!(request.security_origin()?, None);
+
+    let input = [
+        b"NOTIFY SHIORI/3.0\r\n".to_vec(),
+        b"Charset: Shift_JIS\r\n".to_vec(),
+        b"ID: OnSSTPBreak\r\n".to_vec(),
+        b"SecurityLevel: external\r\n".to_vec(),
+        b"SecurityOrigin: http://".to_vec(),
+        vec![130, 179, 130, 173, 130, 231],
+        b".example:9801\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v3::Request::parse(&input)?;
+    assert_eq!(request.security_level(), Some(v3::SecurityLevel::EXTERNAL));
+    let origin = request.security_origin()?.expect("SecurityOrigin header");
+    assert_eq!(origin.host(), "さくら.example");
+    assert_eq!(origin.port(), Some(9801));
+
+    Ok(())
+}
+
+/// This is an extended specification of uka-rs.
+///
+/// SSP accepts `Marker`, `ErrorLevel`, `ErrorDescription`, `BalloonOffset`, `Age`, `ValueNotify`
+/// and `ReferenceN` in the SHIORI/3.0 response.
+/// `ErrorLevel`, `ErrorDescription` and `BalloonOffset` may contain multiple values separated by byte value 1.
+#[test]
+fn spec_shiori_response_ssp_headers() -> anyhow::Result<()> {
+    let input = [
+        b"SHIORI/3.0 200 OK\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"Sender: F.I.R.S.T\r\n".to_vec(),
+        b"Value: \\h\\s0hello\\e\r\n".to_vec(),
+        b"Reference0: Emily\r\n".to_vec(),
+        b"Age: 3\r\n".to_vec(),
+        b"Marker: ".to_vec(),
+        Encoder::encode_utf8("会話中")?,
+        b"\r\n".to_vec(),
+        b"ValueNotify: \\![raise,OnTalked]\r\n".to_vec(),
+        b"BalloonOffset: 10,-20\x010,5\r\n".to_vec(),
+        b"ErrorLevel: warning\x01error\r\n".to_vec(),
+        b"ErrorDescription: ".to_vec(),
+        Encoder::encode_utf8("辞書が見つかりません")?,
+        b"\x01syntax error\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let response = v3::Response::parse(&input)?;
+    assert_eq!(
+        response.reference(0).map(|v| v.text()).transpose()?,
+        Some("Emily".to_string())
+    );
+    assert_eq!(response.age()?, Some(3));
+    assert_eq!(
+        response
+            .marker()
+            .map(|v| v.text_with_charset(Charset::UTF8))
+            .transpose()?,
+        Some("会話中".to_string())
+    );
+    assert_eq!(
+        response.value_notify().map(|v| v.text()).transpose()?,
+        Some("\\![raise,OnTalked]".to_string())
+    );
+    assert_eq!(response.balloon_offsets()?, vec![(10, -20), (0, 5)]);
+    assert_eq!(
+        response.error_levels()?,
+        vec![v3::ErrorLevel::WARNING, v3::ErrorLevel::ERROR]
+    );
+    assert_eq!(
+        response.error_descriptions()?,
+        vec!["辞書が見つかりません", "syntax error"]
+    );
+
+    let built = v3::Response::builder()
+        .version(v3::Version::SHIORI_30)
+        .status_code(v3::StatusCode::OK)
+        .charset(Charset::UTF8)
+        .header(HeaderName::SENDER, "F.I.R.S.T")
+        .header(HeaderName::VALUE, "\\h\\s0hello\\e")
+        .reference(0, "Emily")
+        .age(3)
+        .marker("会話中")
+        .value_notify("\\![raise,OnTalked]")
+        .balloon_offset(10, -20)
+        .error(v3::ErrorLevel::WARNING, "辞書が見つかりません")
+        .balloon_offset(0, 5)
+        .error(v3::ErrorLevel::ERROR, "syntax error")
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    Ok(())
+}
+
+/// This is an extended specification of uka-rs.
+///
+/// The typed accessors of the SSP response headers report malformed values as errors.
+#[test]
+fn spec_shiori_response_ssp_headers_failed_invalid_value() -> anyhow::Result<()> {
+    let response = v3::Response::parse(
+        &[
+            b"SHIORI/3.0 200 OK\r\n".to_vec(),
+            b"Age: old\r\n".to_vec(),
+            b"BalloonOffset: 10\r\n".to_vec(),
+            b"ErrorLevel: fatal\r\n".to_vec(),
+            b"\r\n".to_vec(),
+        ]
+        .concat(),
+    )?;
+    assert!(matches!(
+        response.age(),
+        Err(v3::ResponseHeaderError::InvalidValue(name, value)) if name == HeaderName::AGE && value == "old"
+    ));
+    assert!(response.balloon_offsets().is_err());
+    assert!(response.error_levels().is_err());
+    assert!(response.error_descriptions()?.is_empty());
+
+    Ok(())
+}
+
+/// This is an extended specification of uka-rs.
+///
+/// SSP sends `Status`, `SenderType` and `BaseID` with SHIORI requests.
+/// `Status` and `SenderType` are comma separated flags, and `balloon(scope=id,...)` in `Status` is parsed as a flag.
+#[test]
+fn spec_shiori_request_status_sender_type_and_base_id() -> anyhow::Result<()> {
+    let input = [
+        b"GET SHIORI/3.0\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"Sender: SSP\r\n".to_vec(),
+        b"SenderType: internal,raise\r\n".to_vec(),
+        b"Status: talking,balloon(0=0,1=2),online\r\n".to_vec(),
+        b"ID: OnMouseDoubleClick\r\n".to_vec(),
+        b"BaseID: OnMouseClick\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v3::Request::parse(&input)?;
+    let status = request.status().expect("Status header");
+    assert!(status.is_talking());
+    assert!(!status.is_choosing());
+    assert!(status.contains(&v3::GhostStatusFlag::ONLINE));
+    assert_eq!(status.balloons(), &[(0, 0), (1, 2)]);
+    let sender_type = request.sender_type().expect("SenderType header");
+    assert!(sender_type.is_internal());
+    assert!(sender_type.contains(&v3::SenderTypeFlag::RAISE));
+    assert!(!sender_type.is_external());
+    assert_eq!(
+        request.base_id().map(|v| v.text()).transpose()?,
+        Some("OnMouseClick".to_string())
+    );
+
+    let built = v3::Request::builder()
+        .method(v3::Method::GET)
+        .version(v3::Version::SHIORI_30)
+        .charset(Charset::UTF8)
+        .header(HeaderName::SENDER, "SSP")
+        .sender_type(v3::SenderType::new([
+            v3::SenderTypeFlag::INTERNAL,
+            v3::SenderTypeFlag::RAISE,
+        ]))
+        .status(v3::GhostStatus::new([
+            v3::GhostStatusFlag::TALKING,
+            v3::GhostStatusFlag::balloon([(0, 0), (1, 2)]),
+            v3::GhostStatusFlag::ONLINE,
+        ]))
+        .header(HeaderName::ID, "OnMouseDoubleClick")
+        .header(HeaderName::BASE_ID, "OnMouseClick")
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    let input = [
+        b"GET SHIORI/3.0\r\n".to_vec(),
+        b"Charset: Shift_JIS\r\n".to_vec(),
+        b"SenderType: external,".to_vec(),
+        vec![130, 179, 130, 173, 130, 231],
+        b"\r\n".to_vec(),
+        b"Status: online,".to_vec(),
+        vec![130, 179, 130, 173, 130, 231],
+        b"\r\n".to_vec(),
+        b"ID: OnMouseDoubleClick\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v3::Request::parse(&input)?;
+    let status = request.status().expect("Status header");
+    assert!(status.contains(&v3::GhostStatusFlag::from_static("さくら")));
+    let sender_type = request.sender_type().expect("SenderType header");
+    assert!(sender_type.is_external());
+    assert!(sender_type.contains(&v3::SenderTypeFlag::from_static("さくら")));
+
+    Ok(())
+}
+
+/// This is an extended specification of uka-rs.
+///
+/// Status codes and reason phrases that are not defined in the specification are accepted,
+/// so that a nonstandard response from a third-party SHIORI does not break parsing.
+#[test]
+fn spec_shiori_response_accepts_unknown_status_code_and_reason_phrase() -> anyhow::Result<()> {
+    let input = [
+        b"SHIORI/3.0 599 Dictionary Broken\r\n".to_vec(),
+        b"Sender: F.I.R.S.T\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let response = v3::Response::parse(&input)?;
+    assert_eq!(response.status_code().as_u16(), 599);
+    assert!(response.status_code().is_server_error());
+    assert_eq!(response.status_code().canonical_reason(), None);
+    assert_eq!(response.reason_phrase(), Some("Dictionary Broken"));
+    assert_eq!(response.to_vec(), input);
+
+    let built = v3::Response::builder()
+        .version(v3::Version::SHIORI_30)
+        .status_code(v3::StatusCode::from_u16(599)?)
+        .reason_phrase("Dictionary Broken")
+        .header(HeaderName::SENDER, "F.I.R.S.T")
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    let response = v3::Response::parse(b"SHIORI/3.0 204\r\n\r\n")?;
+    assert_eq!(response.status_code(), v3::StatusCode::NO_CONTENT);
+    assert_eq!(response.reason_phrase(), Some("No Content"));
+    assert_eq!(response.to_vec(), b"SHIORI/3.0 204 No Content\r\n\r\n");
+
+    assert!(v3::Response::parse(b"SHIORI/3.0 2x4 No Content\r\n\r\n").is_err());
+
+    Ok(())
+}
@@ -0,0 +1,295 @@
+use uka_shiori::types::v2;
+use uka_shiori::types::v2::HeaderValue;
+
+/// SHIORI/2.x requests consist of a command line, header lines and an empty line, like SHIORI/3.0.
+/// Unlike SHIORI/3.0, the command line is composed of the command, the resource and the version.
+///
+/// `GET Version` is sent by the baseware to determine the protocol version spoken by the SHIORI.
+#[test]
+fn spec_shiori_request_get_version() -> anyhow::Result<()> {
+    let input = [
+        b"GET Version SHIORI/2.6\r\n".to_vec(),
+        b"Sender: SSP\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::GET_VERSION);
+    assert_eq!(request.version(), v2::Version::SHIORI_26);
+    assert_eq!(request.charset(), v2::Charset::UTF8);
+    assert_eq!(
+        request.sender(),
+        Some(&HeaderValue::from(b"SSP".as_slice()))
+    );
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `GET Sentence` requests a script to talk.
+/// Since SHIORI/2.2, the event that triggered the request is sent in the `Event` header with its `Reference*` headers.
+#[test]
+fn spec_shiori_request_get_sentence() -> anyhow::Result<()> {
+    let input = [
+        b"GET Sentence SHIORI/2.2\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"Event: OnMinuteChange\r\n".to_vec(),
+        b"Reference0: 1\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::GET_SENTENCE);
+    assert_eq!(request.version(), v2::Version::SHIORI_22);
+    assert_eq!(
+        request.sender(),
+        Some(&HeaderValue::from(b"Materia".as_slice()))
+    );
+    assert_eq!(
+        request.event(),
+        Some(&HeaderValue::from(b"OnMinuteChange".as_slice()))
+    );
+    assert_eq!(
+        request.reference0(),
+        Some(&HeaderValue::from(b"1".as_slice()))
+    );
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `GET Word` requests a word of the class specified by the `Type` header.
+#[test]
+fn spec_shiori_request_get_word() -> anyhow::Result<()> {
+    let input = [
+        b"GET Word SHIORI/2.0\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"Type: \\ms\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::GET_WORD);
+    assert_eq!(request.version(), v2::Version::SHIORI_20);
+    assert_eq!(
+        request.word_type(),
+        Some(&HeaderValue::from(b"\\ms".as_slice()))
+    );
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `GET Status` requests the internal status of the SHIORI.
+#[test]
+fn spec_shiori_request_get_status() -> anyhow::Result<()> {
+    let input = [
+        b"GET Status SHIORI/2.0\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::GET_STATUS);
+    assert_eq!(request.version(), v2::Version::SHIORI_20);
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `TEACH` passes a word taught by the user.
+#[test]
+fn spec_shiori_request_teach() -> anyhow::Result<()> {
+    let input = [
+        b"TEACH SHIORI/2.4\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"Word: hoge\r\n".to_vec(),
+        b"Reference0: uge\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::TEACH);
+    assert_eq!(request.version(), v2::Version::SHIORI_24);
+    assert_eq!(request.word(), Some(&HeaderValue::from(b"hoge".as_slice())));
+    assert_eq!(
+        request.reference0(),
+        Some(&HeaderValue::from(b"uge".as_slice()))
+    );
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `NOTIFY OwnerGhostName` notifies the name of the ghost that owns the SHIORI.
+#[test]
+fn spec_shiori_request_notify_owner_ghost_name() -> anyhow::Result<()> {
+    let input = [
+        b"NOTIFY OwnerGhostName SHIORI/2.0\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"Ghost: Sakura\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let request = v2::Request::parse(&input)?;
+    assert_eq!(request.method(), v2::Method::NOTIFY_OWNER_GHOST_NAME);
+    assert_eq!(request.version(), v2::Version::SHIORI_20);
+    assert_eq!(
+        request.ghost(),
+        Some(&HeaderValue::from(b"Sakura".as_slice()))
+    );
+
+    assert_eq!(
+        request.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&request.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// An unknown command is rejected by the parser.
+#[test]
+fn spec_shiori_request_unknown_method_is_error() {
+    let input = [
+        b"GET Unknown SHIORI/2.0\r\n".to_vec(),
+        b"Sender: Materia\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let result = v2::Request::parse(&input);
+    assert!(matches!(result, Err(v2::ParseError::InvalidMethod(_))));
+}
+
+/// SHIORI/2.x responses consist of a status line, header lines and an empty line.
+/// The script of `GET Sentence` is returned in the `Sentence` header, and the partner of the talk in the `To` header.
+#[test]
+fn spec_shiori_response_sentence() -> anyhow::Result<()> {
+    let input = [
+        b"SHIORI/2.3 200 OK\r\n".to_vec(),
+        b"Sender: F.I.R.S.T\r\n".to_vec(),
+        b"Sentence: \\h\\s0hoge\\e\r\n".to_vec(),
+        b"To: Sakura\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+
+    let response = v2::Response::parse(&input)?;
+    assert_eq!(response.version(), v2::Version::SHIORI_23);
+    assert_eq!(response.status_code(), v2::StatusCode::OK);
+    assert_eq!(
+        response.sender(),
+        Some(&HeaderValue::from(b"F.I.R.S.T".as_slice()))
+    );
+    assert_eq!(
+        response.sentence(),
+        Some(&HeaderValue::from(b"\\h\\s0hoge\\e".as_slice()))
+    );
+    assert_eq!(
+        response.to(),
+        Some(&HeaderValue::from(b"Sakura".as_slice()))
+    );
+
+    assert_eq!(
+        response.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&response.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// The word of `GET Word` is returned in the `Word` header.
+#[test]
+fn spec_shiori_response_word() -> anyhow::Result<()> {
+    let input = [
+        b"SHIORI/2.0 200 OK\r\n".to_vec(),
+        b"Sender: F.I.R.S.T\r\n".to_vec(),
+        b"Word: hoge\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+
+    let response = v2::Response::parse(&input)?;
+    assert_eq!(response.version(), v2::Version::SHIORI_20);
+    assert_eq!(response.status_code(), v2::StatusCode::OK);
+    assert_eq!(
+        response.word(),
+        Some(&HeaderValue::from(b"hoge".as_slice()))
+    );
+
+    assert_eq!(
+        response.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&response.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
+
+/// `204 No Content` is returned when there is nothing to return.
+#[test]
+fn spec_shiori_response_no_content() -> anyhow::Result<()> {
+    let input = [
+        b"SHIORI/2.6 204 No Content\r\n".to_vec(),
+        b"Sender: F.I.R.S.T\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+
+    let response = v2::Response::parse(&input)?;
+    assert_eq!(response.version(), v2::Version::SHIORI_26);
+    assert_eq!(response.status_code(), v2::StatusCode::NO_CONTENT);
+
+    assert_eq!(
+        response.to_vec(),
+        input,
+        "\nassertion failed: `(left == right)\n  left: `{:?}`,\n right: `{:?}`",
+        String::from_utf8_lossy(&response.to_vec()),
+        String::from_utf8_lossy(&input)
+    );
+
+    Ok(())
+}
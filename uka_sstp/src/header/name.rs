@@ -1,4 +1,5 @@
 use std::fmt::Display;
+use uka_util::header::{parse_reference_index, ReferenceName};
 use uka_util::string::{Error as Rfc7230StringConvertError, Rfc7230String};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -6,14 +7,7 @@ enum Inner {
     Charset,
     Sender,
     Event,
-    Reference0,
-    Reference1,
-    Reference2,
-    Reference3,
-    Reference4,
-    Reference5,
-    Reference6,
-    Reference7,
+    Reference(usize),
     Script,
     Option,
     Entry,
@@ -53,28 +47,28 @@ impl HeaderName {
     pub const EVENT: HeaderName = HeaderName(Inner::Event);
 
     /// Reference0
-    pub const REFERENCE0: HeaderName = HeaderName(Inner::Reference0);
+    pub const REFERENCE0: HeaderName = HeaderName(Inner::Reference(0));
 
     /// Reference1
-    pub const REFERENCE1: HeaderName = HeaderName(Inner::Reference1);
+    pub const REFERENCE1: HeaderName = HeaderName(Inner::Reference(1));
 
     /// Reference2
-    pub const REFERENCE2: HeaderName = HeaderName(Inner::Reference2);
+    pub const REFERENCE2: HeaderName = HeaderName(Inner::Reference(2));
 
     /// Reference3
-    pub const REFERENCE3: HeaderName = HeaderName(Inner::Reference3);
+    pub const REFERENCE3: HeaderName = HeaderName(Inner::Reference(3));
 
     /// Reference4
-    pub const REFERENCE4: HeaderName = HeaderName(Inner::Reference4);
+    pub const REFERENCE4: HeaderName = HeaderName(Inner::Reference(4));
 
     /// Reference5
-    pub const REFERENCE5: HeaderName = HeaderName(Inner::Reference5);
+    pub const REFERENCE5: HeaderName = HeaderName(Inner::Reference(5));
 
     /// Reference6
-    pub const REFERENCE6: HeaderName = HeaderName(Inner::Reference6);
+    pub const REFERENCE6: HeaderName = HeaderName(Inner::Reference(6));
 
     /// Reference7
-    pub const REFERENCE7: HeaderName = HeaderName(Inner::Reference7);
+    pub const REFERENCE7: HeaderName = HeaderName(Inner::Reference(7));
 
     /// Script
     pub const SCRIPT: HeaderName = HeaderName(Inner::Script);
@@ -109,6 +103,34 @@ impl HeaderName {
     /// Surface
     pub const SURFACE: HeaderName = HeaderName(Inner::Surface);
 
+    /// Returns the `ReferenceN` header name for any index `n`.
+    ///
+    /// ```rust
+    /// # use uka_sstp::HeaderName;
+    /// assert_eq!(HeaderName::reference(0), HeaderName::REFERENCE0);
+    /// assert_eq!(HeaderName::reference(8).to_string(), "Reference8");
+    /// assert_eq!(HeaderName::from_static("Reference8").unwrap(), HeaderName::reference(8));
+    /// ```
+    pub const fn reference(n: usize) -> HeaderName {
+        HeaderName(Inner::Reference(n))
+    }
+
+    /// Returns the index if the header name is `ReferenceN`.
+    ///
+    /// ```rust
+    /// # use uka_sstp::HeaderName;
+    /// assert_eq!(HeaderName::REFERENCE7.reference_index(), Some(7));
+    /// assert_eq!(HeaderName::from_static("Reference12").unwrap().reference_index(), Some(12));
+    /// assert_eq!(HeaderName::from_static("Reference012").unwrap().reference_index(), None);
+    /// assert_eq!(HeaderName::SENDER.reference_index(), None);
+    /// ```
+    pub fn reference_index(&self) -> Option<usize> {
+        match self.0 {
+            Inner::Reference(n) => Some(n),
+            _ => None,
+        }
+    }
+
     ///　Converts a str to HeaderName.
     ///
     /// ```rust
@@ -138,18 +160,13 @@ impl HeaderName {
     /// assert_eq!(HeaderName::from_static("X-Extend-Header").unwrap().to_string(), "X-Extend-Header");
     /// ```
     pub fn from_static(s: &str) -> Result<HeaderName, Error> {
+        if let Some(n) = parse_reference_index(s) {
+            return Ok(HeaderName(Inner::Reference(n)));
+        }
         match s {
             "Charset" => Ok(HeaderName(Inner::Charset)),
             "Sender" => Ok(HeaderName(Inner::Sender)),
             "Event" => Ok(HeaderName(Inner::Event)),
-            "Reference0" => Ok(HeaderName(Inner::Reference0)),
-            "Reference1" => Ok(HeaderName(Inner::Reference1)),
-            "Reference2" => Ok(HeaderName(Inner::Reference2)),
-            "Reference3" => Ok(HeaderName(Inner::Reference3)),
-            "Reference4" => Ok(HeaderName(Inner::Reference4)),
-            "Reference5" => Ok(HeaderName(Inner::Reference5)),
-            "Reference6" => Ok(HeaderName(Inner::Reference6)),
-            "Reference7" => Ok(HeaderName(Inner::Reference7)),
             "Script" => Ok(HeaderName(Inner::Script)),
             "Option" => Ok(HeaderName(Inner::Option)),
             "Entry" => Ok(HeaderName(Inner::Entry)),
@@ -239,14 +256,7 @@ impl Display for HeaderName {
             Inner::Charset => write!(f, "Charset"),
             Inner::Sender => write!(f, "Sender"),
             Inner::Event => write!(f, "Event"),
-            Inner::Reference0 => write!(f, "Reference0"),
-            Inner::Reference1 => write!(f, "Reference1"),
-            Inner::Reference2 => write!(f, "Reference2"),
-            Inner::Reference3 => write!(f, "Reference3"),
-            Inner::Reference4 => write!(f, "Reference4"),
-            Inner::Reference5 => write!(f, "Reference5"),
-            Inner::Reference6 => write!(f, "Reference6"),
-            Inner::Reference7 => write!(f, "Reference7"),
+            Inner::Reference(n) => write!(f, "Reference{n}"),
             Inner::Script => write!(f, "Script"),
             Inner::Option => write!(f, "Option"),
             Inner::Entry => write!(f, "Entry"),
@@ -263,6 +273,12 @@ impl Display for HeaderName {
     }
 }
 
+impl ReferenceName for HeaderName {
+    fn reference_index(&self) -> Option<usize> {
+        HeaderName::reference_index(self)
+    }
+}
+
 impl From<HeaderName> for String {
     fn from(header_name: HeaderName) -> Self {
         header_name.to_string()
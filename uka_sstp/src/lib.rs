@@ -11,5 +11,5 @@ pub use charset::Charset;
 pub use header::{HeaderMap, HeaderName, HeaderNameError, HeaderValue, HeaderValueError};
 pub use method::Method;
 pub use parse::Error;
-pub use status::StatusCode;
+pub use status::{Error as StatusCodeError, StatusCode};
 pub use version::Version;
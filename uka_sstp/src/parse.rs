@@ -8,7 +8,8 @@ use std::io;
 use std::io::Cursor;
 use std::num::ParseIntError;
 use std::str::Utf8Error;
-use uka_util::cursor::{lookahead, read_expect, read_match, read_repeat, read_until};
+use uka_util::cursor::{lookahead, read, read_expect, read_match, read_repeat, read_until};
+use uka_util::status::{parse_reason_phrase, Error as ReasonPhraseError};
 
 /// Error that can occur when parse from bytes.
 #[derive(thiserror::Error, Debug)]
@@ -22,10 +23,8 @@ pub enum Error {
     #[error("invalid version: please use `SSTP/1.0`or `SSTP/1.1`, `SSTP/1.2`, `SSTP/1.3`, `SSTP/1.4` for the version")]
     InvalidVersion,
 
-    #[error(
-        "invalid status code: please use 2xx, 4xx, or 5xx status codes based on the specification"
-    )]
-    InvalidStatusCode,
+    #[error("invalid status code: {0:?}")]
+    InvalidStatusCode(String),
 
     #[error("invalid header name: {0:?}")]
     InvalidHeaderName(#[from] HeaderNameError),
@@ -52,6 +51,15 @@ pub enum Error {
     UnexpectedEof,
 }
 
+impl From<ReasonPhraseError> for Error {
+    fn from(e: ReasonPhraseError) -> Self {
+        match e {
+            ReasonPhraseError::Io(e) => Error::Io(e),
+            ReasonPhraseError::InvalidStatusCode(s) => Error::InvalidStatusCode(s),
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub fn parse_request(input: &[u8]) -> Result<Request> {
@@ -87,7 +95,11 @@ pub fn parse_response(input: &[u8]) -> Result<Response> {
     let version = parse_version(&mut cursor)?;
     skip_spaces(&mut cursor)?;
     let status_code = parse_status_code(&mut cursor)?;
-    skip_newline(&mut cursor)?;
+    let reason_phrase = parse_reason_phrase(
+        &mut cursor,
+        status_code.as_u16(),
+        status_code.canonical_reason(),
+    )?;
     let headers = parse_headers(&mut cursor)?;
     let charset = headers
         .get(&HeaderName::CHARSET)
@@ -107,6 +119,7 @@ pub fn parse_response(input: &[u8]) -> Result<Response> {
     Ok(Response {
         version,
         status_code,
+        reason_phrase,
         headers,
         additional,
         charset,
@@ -136,21 +149,13 @@ fn parse_version(cursor: &mut Cursor<&[u8]>) -> Result<Version> {
 }
 
 fn parse_status_code(cursor: &mut Cursor<&[u8]>) -> Result<StatusCode> {
-    read_match!(cursor, {
-        b"200 OK" => StatusCode::OK,
-        b"204 No Content" => StatusCode::NO_CONTENT,
-        b"210 Break" => StatusCode::BREAK,
-        b"400 Bad Request" => StatusCode::BAD_REQUEST,
-        b"408 Request Timeout" => StatusCode::REQUEST_TIMEOUT,
-        b"409 Conflict" => StatusCode::CONFLICT,
-        b"420 Refuse" => StatusCode::REFUSE,
-        b"501 Not Implemented" => StatusCode::NOT_IMPLEMENTED,
-        b"503 Service Unavailable" => StatusCode::SERVICE_UNAVAILABLE,
-        b"510 Not Local IP" => StatusCode::NOT_LOCAL_IP,
-        b"511 In Black List" => StatusCode::IN_BLACK_LIST,
-        b"512 Invisible" => StatusCode::INVISIBLE,
-    }, 3)
-    .map_err(Error::from)
+    let code = read!(cursor, 3).map_err(Error::from)?;
+    std::str::from_utf8(&code)
+        .ok()
+        .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|s| s.parse::<u16>().ok())
+        .and_then(|code| StatusCode::from_u16(code).ok())
+        .ok_or_else(|| Error::InvalidStatusCode(String::from_utf8_lossy(&code).to_string()))
 }
 
 fn parse_headers(cursor: &mut Cursor<&[u8]>) -> Result<HeaderMap> {
@@ -445,9 +450,39 @@ mod tests {
     #[test]
     fn test_parse_status_code_undefined() -> Result<()> {
         let mut cursor = Cursor::new(b"999 Undefine Code\r\n".as_slice());
+        let code = parse_status_code(&mut cursor)?;
+        assert_eq!(code.as_u16(), 999);
+        let reason = parse_reason_phrase(&mut cursor, code.as_u16(), code.canonical_reason())?;
+        assert_eq!(reason.as_deref(), Some("Undefine Code"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_status_code_canonical_reason_is_not_kept() -> Result<()> {
+        let mut cursor = Cursor::new(b"200 OK\r\n".as_slice());
+        let code = parse_status_code(&mut cursor)?;
+        let reason = parse_reason_phrase(&mut cursor, code.as_u16(), code.canonical_reason())?;
+        assert_eq!(code, StatusCode::OK);
+        assert_eq!(reason, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_status_code_failed_not_digits() {
+        let mut cursor = Cursor::new(b"2O0 OK\r\n".as_slice());
         let res = parse_status_code(&mut cursor);
-        assert!(res.is_err());
-        matches!(res.unwrap_err(), Error::InvalidStatusCode);
+        assert!(matches!(res, Err(Error::InvalidStatusCode(s)) if s == "2O0"));
+    }
+
+    #[test]
+    fn test_parse_status_code_failed_too_many_digits() -> Result<()> {
+        let mut cursor = Cursor::new(b"2000 OK\r\n".as_slice());
+        let code = parse_status_code(&mut cursor)?;
+        let res = parse_reason_phrase(&mut cursor, code.as_u16(), code.canonical_reason())
+            .map_err(Error::from);
+        assert!(matches!(res, Err(Error::InvalidStatusCode(s)) if s == "2000 OK"));
 
         Ok(())
     }
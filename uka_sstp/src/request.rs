@@ -5,6 +5,7 @@ use crate::parse;
 use crate::parse::parse_request;
 use crate::version::Version;
 use std::collections::HashMap;
+use uka_util::header::HeaderMapExt;
 
 /// Request is a type that represents an SSTP request.
 ///
@@ -148,6 +149,16 @@ impl Request {
         self.headers.get(&HeaderName::REFERENCE7)
     }
 
+    /// Returns ReferenceN in SSTP header fields for any index `n`.
+    pub fn reference(&self, n: usize) -> Option<&HeaderValue> {
+        self.headers.get(&HeaderName::reference(n))
+    }
+
+    /// Returns all ReferenceN in SSTP header fields ordered by index.
+    pub fn references(&self) -> impl Iterator<Item = (usize, &HeaderValue)> {
+        self.headers.references()
+    }
+
     /// Returns script in SSTP header fields.
     pub fn script(&self) -> Vec<&HeaderValue> {
         self.headers.get_all(&HeaderName::SCRIPT)
@@ -408,6 +419,14 @@ impl Builder {
         })
     }
 
+    /// Set ReferenceN SSTP header field for any index `n`.
+    pub fn reference<V>(self, n: usize, value: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.header(HeaderName::reference(n), value)
+    }
+
     /// Set SSTP charset.
     pub fn charset(self, charset: Charset) -> Self {
         self.and_then(|mut inner| {
@@ -9,8 +9,9 @@ use uka_util::encode::{Encoder, Error as EncodeError};
 ///
 /// This data is preserved in the character set and encoding specified by Charset.
 /// To retrieve the data as a string, decode it according to the Charset.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub enum AdditionalData {
+    #[default]
     Empty,
     Text(Vec<u8>),
 }
@@ -106,12 +107,6 @@ impl From<Vec<u8>> for AdditionalData {
     }
 }
 
-impl Default for AdditionalData {
-    fn default() -> Self {
-        Self::Empty
-    }
-}
-
 /// Response is a type that represents an SSTP response.
 ///
 /// Response provides a builder to generate types, a parser to generate types from bytes.
@@ -136,6 +131,7 @@ impl Default for AdditionalData {
 pub struct Response {
     pub(crate) version: Version,
     pub(crate) status_code: StatusCode,
+    pub(crate) reason_phrase: Option<String>,
     pub(crate) headers: HeaderMap,
     pub(crate) charset: Charset,
     pub(crate) additional: AdditionalData,
@@ -198,6 +194,15 @@ impl Response {
         self.status_code
     }
 
+    /// Returns the reason phrase of the status line.
+    ///
+    /// If no custom reason phrase is set, the one defined in the specification is returned.
+    pub fn reason_phrase(&self) -> Option<&str> {
+        self.reason_phrase
+            .as_deref()
+            .or_else(|| self.status_code.canonical_reason())
+    }
+
     /// Returns SSTP header fields.
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -218,7 +223,11 @@ impl Response {
         let mut buf = Vec::new();
         buf.extend_from_slice(self.version.to_string().as_bytes());
         buf.extend_from_slice(b" ");
-        buf.extend_from_slice(self.status_code.to_string().as_bytes());
+        buf.extend_from_slice(self.status_code.as_u16().to_string().as_bytes());
+        if let Some(reason) = self.reason_phrase() {
+            buf.extend_from_slice(b" ");
+            buf.extend_from_slice(reason.as_bytes());
+        }
         buf.extend_from_slice(b"\r\n");
         for (name, value) in self.headers.iter() {
             buf.extend_from_slice(&name.to_vec());
@@ -242,6 +251,8 @@ pub enum Error {
     MissingVersion,
     #[error("status_code is required")]
     MissingStatusCode,
+    #[error("reason phrase cannot contain unprintable characters: {0:?}")]
+    InvalidReasonPhrase(String),
     #[error("charset is required")]
     MissingCharset,
     #[error("{0}")]
@@ -256,6 +267,7 @@ pub enum Error {
 struct Parts {
     version: Option<Version>,
     status_code: Option<StatusCode>,
+    reason_phrase: Option<String>,
     headers: HashMap<String, Vec<String>>,
     charset: Option<Charset>,
     additional: Option<String>,
@@ -293,6 +305,25 @@ impl Builder {
         })
     }
 
+    /// Set the custom reason phrase of the status line.
+    ///
+    /// If it is not set, the reason phrase defined in the specification is used.
+    pub fn reason_phrase<V>(self, reason_phrase: V) -> Self
+    where
+        V: Into<String>,
+    {
+        self.and_then(|parts| {
+            let reason_phrase = reason_phrase.into();
+            if reason_phrase.chars().any(|c| c.is_control()) {
+                return Err(Error::InvalidReasonPhrase(reason_phrase));
+            }
+            Ok(Parts {
+                reason_phrase: Some(reason_phrase),
+                ..parts
+            })
+        })
+    }
+
     /// Set SSTP header field.
     pub fn header<K, V>(self, name: K, value: V) -> Self
     where
@@ -349,9 +380,14 @@ impl Builder {
     pub fn build(self) -> Result<Response, Error> {
         let inner = self.inner?;
         let charset = inner.charset.ok_or(Error::MissingCharset)?;
+        let version = inner.version.ok_or(Error::MissingVersion)?;
+        let status_code = inner.status_code.ok_or(Error::MissingStatusCode)?;
         Ok(Response {
-            version: inner.version.ok_or(Error::MissingVersion)?,
-            status_code: inner.status_code.ok_or(Error::MissingStatusCode)?,
+            version,
+            status_code,
+            reason_phrase: inner
+                .reason_phrase
+                .filter(|v| !v.is_empty() && Some(v.as_str()) != status_code.canonical_reason()),
             headers: inner.headers.iter().try_fold(
                 HeaderMap::with_capacity(inner.headers.len()),
                 |acc, (name, value)| {
@@ -1,8 +1,19 @@
 use std::fmt;
 use std::fmt::Display;
 
+/// Error that can occur when converting StatusCode from integer.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("invalid status code `{0}`, the status code must be three digits")]
+    Invalid(u16),
+}
+
 /// StatusCode represents the status code of the SSTP response.
 ///
+/// Status codes that are not defined in the specification can also be represented with [`StatusCode::from_u16`].
+///
+/// # Examples
+///
 /// ```rust
 /// # use uka_sstp::StatusCode;
 /// assert_eq!(StatusCode::OK.to_string(), "200 OK");
@@ -17,6 +28,7 @@ use std::fmt::Display;
 /// assert_eq!(StatusCode::NOT_LOCAL_IP.to_string(), "510 Not Local IP");
 /// assert_eq!(StatusCode::IN_BLACK_LIST.to_string(), "511 In Black List");
 /// assert_eq!(StatusCode::INVISIBLE.to_string(), "512 Invisible");
+/// assert_eq!(StatusCode::from_u16(299).unwrap().to_string(), "299");
 /// ```
 #[derive(Debug, PartialEq, PartialOrd, Copy, Clone, Eq, Ord, Hash)]
 pub struct StatusCode(u16);
@@ -59,24 +71,82 @@ impl StatusCode {
 
     /// 512 Invisible
     pub const INVISIBLE: StatusCode = StatusCode(512);
+
+    ///　Converts a u16 to StatusCode.
+    ///
+    /// Any three digits code is accepted, even if it is not defined in the specification.
+    ///
+    /// ```rust
+    /// # use uka_sstp::StatusCode;
+    /// assert_eq!(StatusCode::from_u16(200).unwrap(), StatusCode::OK);
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().as_u16(), 299);
+    /// assert!(StatusCode::from_u16(99).is_err());
+    /// assert!(StatusCode::from_u16(1000).is_err());
+    /// ```
+    pub fn from_u16(code: u16) -> Result<StatusCode, Error> {
+        if (100..1000).contains(&code) {
+            Ok(StatusCode(code))
+        } else {
+            Err(Error::Invalid(code))
+        }
+    }
+
+    /// Returns the status code as u16.
+    pub fn as_u16(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns the reason phrase defined in the specification, if any.
+    ///
+    /// ```rust
+    /// # use uka_sstp::StatusCode;
+    /// assert_eq!(StatusCode::OK.canonical_reason(), Some("OK"));
+    /// assert_eq!(StatusCode::from_u16(299).unwrap().canonical_reason(), None);
+    /// ```
+    pub fn canonical_reason(&self) -> Option<&'static str> {
+        match self.0 {
+            200 => Some("OK"),
+            204 => Some("No Content"),
+            210 => Some("Break"),
+            400 => Some("Bad Request"),
+            408 => Some("Request Timeout"),
+            409 => Some("Conflict"),
+            420 => Some("Refuse"),
+            501 => Some("Not Implemented"),
+            503 => Some("Service Unavailable"),
+            510 => Some("Not Local IP"),
+            511 => Some("In Black List"),
+            512 => Some("Invisible"),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the status code is in the range 200-299.
+    pub fn is_success(&self) -> bool {
+        uka_util::status::is_success(self.0)
+    }
+
+    /// Returns true if the status code is in the range 300-399.
+    pub fn is_redirection(&self) -> bool {
+        uka_util::status::is_redirection(self.0)
+    }
+
+    /// Returns true if the status code is in the range 400-499.
+    pub fn is_client_error(&self) -> bool {
+        uka_util::status::is_client_error(self.0)
+    }
+
+    /// Returns true if the status code is in the range 500-599.
+    pub fn is_server_error(&self) -> bool {
+        uka_util::status::is_server_error(self.0)
+    }
 }
 
 impl Display for StatusCode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0 {
-            200 => write!(f, "200 OK"),
-            204 => write!(f, "204 No Content"),
-            210 => write!(f, "210 Break"),
-            400 => write!(f, "400 Bad Request"),
-            408 => write!(f, "408 Request Timeout"),
-            409 => write!(f, "409 Conflict"),
-            420 => write!(f, "420 Refuse"),
-            501 => write!(f, "501 Not Implemented"),
-            503 => write!(f, "503 Service Unavailable"),
-            510 => write!(f, "510 Not Local IP"),
-            511 => write!(f, "511 In Black List"),
-            512 => write!(f, "512 Invisible"),
-            _ => unreachable!("unreachable because StatusCode cannot be new instance"),
+        match self.canonical_reason() {
+            Some(reason) => write!(f, "{} {reason}", self.0),
+            None => write!(f, "{}", self.0),
         }
     }
 }
@@ -215,3 +215,86 @@ fn spec_response_header_value_cannot_contain_control_characters() -> Result<()>
 
     Ok(())
 }
+
+/// Undefined specification for materia.
+///
+/// `ReferenceN` is not limited to `Reference0` to `Reference7`.
+/// SSP sends `Reference8` and above in some events, so any index is handled as `ReferenceN`.
+#[test]
+fn spec_reference_headers_are_unbounded() -> Result<()> {
+    let input = [
+        b"NOTIFY SSTP/1.1\r\n".to_vec(),
+        b"Sender: SSP\r\n".to_vec(),
+        b"Event: OnNotifyOSInfo\r\n".to_vec(),
+        b"Reference0: zero\r\n".to_vec(),
+        b"Reference10: ten\r\n".to_vec(),
+        b"Reference8: eight\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+
+    let request = Request::parse(&input)?;
+    assert_eq!(
+        request
+            .reference(8)
+            .map(|v| v.text_with_charset(Charset::UTF8))
+            .transpose()?,
+        Some("eight".to_string())
+    );
+    assert_eq!(
+        request
+            .references()
+            .map(|(n, v)| Ok((n, v.text_with_charset(Charset::UTF8)?)))
+            .collect::<Result<Vec<_>>>()?,
+        vec![
+            (0, "zero".to_string()),
+            (8, "eight".to_string()),
+            (10, "ten".to_string())
+        ]
+    );
+    assert_eq!(request.to_vec(), input);
+
+    Ok(())
+}
+
+/// Extended specification of uka-rs.
+///
+/// Status codes and reason phrases that are not defined in the specification are accepted,
+/// so that a nonstandard response from third-party baseware does not break parsing.
+#[test]
+fn spec_response_accepts_unknown_status_code_and_reason_phrase() -> Result<()> {
+    let input = [
+        b"SSTP/1.4 299 Accepted Later\r\n".to_vec(),
+        b"Charset: UTF-8\r\n".to_vec(),
+        b"\r\n".to_vec(),
+    ]
+    .concat();
+    let response = Response::parse(&input)?;
+    assert_eq!(response.status_code().as_u16(), 299);
+    assert!(response.status_code().is_success());
+    assert_eq!(response.reason_phrase(), Some("Accepted Later"));
+    assert_eq!(response.to_vec(), input);
+
+    let built = Response::builder()
+        .version(Version::SSTP_14)
+        .status_code(StatusCode::from_u16(299)?)
+        .reason_phrase("Accepted Later")
+        .charset(Charset::UTF8)
+        .build()?;
+    assert_eq!(built.to_vec(), input);
+
+    let response = Response::parse(
+        &[
+            b"SSTP/1.4 420 Go Away\r\n".to_vec(),
+            b"Charset: UTF-8\r\n".to_vec(),
+            b"\r\n".to_vec(),
+        ]
+        .concat(),
+    )?;
+    assert_eq!(response.status_code(), StatusCode::REFUSE);
+    assert!(response.status_code().is_client_error());
+    assert_eq!(response.reason_phrase(), Some("Go Away"));
+
+    Ok(())
+}
@@ -23,7 +23,7 @@ type Pos = usize;
 /// bag.insert("key2", "value2");
 /// assert_eq!(bag.get("key1"), Some(&"value1"));
 /// ```
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct OrderedBag<K, V> {
     entries: Vec<(K, V)>,
     map: HashMap<K, Vec<Pos>>,
@@ -119,8 +119,6 @@ pub use read_repeat;
 #[macro_export]
 macro_rules! read_until {
     ( $cursor:expr, $bytes:expr ) => {{
-        use std::io::Seek;
-
         let mut buffer = Vec::new();
         loop {
             match $crate::read!($cursor, 1) {
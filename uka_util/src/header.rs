@@ -0,0 +1,90 @@
+use crate::bag::OrderedBag;
+use std::hash::Hash;
+
+/// `ReferenceName` is implemented by the header names that have `ReferenceN` header fields.
+pub trait ReferenceName {
+    /// Returns `N` if the header name is `ReferenceN`.
+    fn reference_index(&self) -> Option<usize>;
+}
+
+/// `HeaderMapExt` reads the header fields shared by the protocols from a header map.
+pub trait HeaderMapExt<V> {
+    /// Returns all ReferenceN header fields as `(n, value)` pairs in ascending order of `n`.
+    ///
+    /// If the same ReferenceN is defined more than once, the first one is returned.
+    fn references(&self) -> std::vec::IntoIter<(usize, &V)>;
+}
+
+impl<K: ReferenceName + Eq + Hash, V> HeaderMapExt<V> for OrderedBag<K, V> {
+    fn references(&self) -> std::vec::IntoIter<(usize, &V)> {
+        let mut references = self
+            .iter()
+            .filter_map(|(name, value)| name.reference_index().map(|n| (n, value)))
+            .collect::<Vec<_>>();
+        references.sort_by_key(|(n, _)| *n);
+        references.dedup_by_key(|(n, _)| *n);
+        references.into_iter()
+    }
+}
+
+/// Parse the index of `ReferenceN`.
+///
+/// Only the canonical decimal form is accepted so that the header name is written back as it was read,
+/// e.g. `Reference01` is treated as an extended header name.
+///
+/// # Example
+///
+/// ```rust
+/// # use uka_util::header::parse_reference_index;
+/// #
+/// assert_eq!(parse_reference_index("Reference12"), Some(12));
+/// assert_eq!(parse_reference_index("Reference012"), None);
+/// assert_eq!(parse_reference_index("Sender"), None);
+/// ```
+pub fn parse_reference_index(s: &str) -> Option<usize> {
+    let digits = s.strip_prefix("Reference")?;
+    if digits.is_empty()
+        || !digits.bytes().all(|b| b.is_ascii_digit())
+        || (digits.len() > 1 && digits.starts_with('0'))
+    {
+        return None;
+    }
+    digits.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    impl ReferenceName for &str {
+        fn reference_index(&self) -> Option<usize> {
+            parse_reference_index(self)
+        }
+    }
+
+    #[rstest]
+    #[case::zero("Reference0", Some(0))]
+    #[case::multiple_digits("Reference10", Some(10))]
+    #[case::leading_zero("Reference01", None)]
+    #[case::without_digits("Reference", None)]
+    #[case::sign("Reference+1", None)]
+    #[case::suffix("Reference1a", None)]
+    #[case::other("Sender", None)]
+    fn test_parse_reference_index(#[case] input: &str, #[case] expected: Option<usize>) {
+        assert_eq!(parse_reference_index(input), expected);
+    }
+
+    #[test]
+    fn test_references() {
+        let mut headers = OrderedBag::new();
+        headers.insert("Reference1", "b");
+        headers.insert("Sender", "SSP");
+        headers.insert("Reference0", "a");
+        headers.insert("Reference1", "c");
+        assert_eq!(
+            headers.references().collect::<Vec<_>>(),
+            vec![(0, &"a"), (1, &"b")]
+        );
+    }
+}
@@ -3,7 +3,9 @@ pub mod bag;
 pub mod cursor;
 pub mod decode;
 pub mod encode;
+pub mod header;
 pub mod ptr;
+pub mod status;
 pub mod string;
 
 pub use uka_macro;
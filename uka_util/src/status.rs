@@ -0,0 +1,109 @@
+use crate::cursor::read_until;
+use std::io;
+use std::io::Cursor;
+
+/// Error that can occur when parsing the reason phrase of a status line.
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    #[error("{0}")]
+    Io(#[from] io::Error),
+
+    #[error("invalid status code: {0:?}")]
+    InvalidStatusCode(String),
+}
+
+/// Parse the reason phrase that follows the status code until the end of the line.
+///
+/// Any reason phrase is accepted. The reason phrase defined in the specification is not kept,
+/// so that it is written back as the canonical one.
+///
+/// # Example
+///
+/// ```rust
+/// # use uka_util::status::parse_reason_phrase;
+/// # use std::io::Cursor;
+/// #
+/// let mut cursor = Cursor::new(&b" Dictionary Broken\r\n"[..]);
+/// assert_eq!(
+///     parse_reason_phrase(&mut cursor, 599, None).unwrap().as_deref(),
+///     Some("Dictionary Broken")
+/// );
+///
+/// let mut cursor = Cursor::new(&b" OK\r\n"[..]);
+/// assert_eq!(parse_reason_phrase(&mut cursor, 200, Some("OK")).unwrap(), None);
+/// ```
+pub fn parse_reason_phrase(
+    cursor: &mut Cursor<&[u8]>,
+    status_code: u16,
+    canonical_reason: Option<&str>,
+) -> Result<Option<String>, Error> {
+    let line = read_until!(cursor, b"\r\n")?;
+    if !line.is_empty() && !line.starts_with(b" ") {
+        return Err(Error::InvalidStatusCode(format!(
+            "{}{}",
+            status_code,
+            String::from_utf8_lossy(&line)
+        )));
+    }
+    let reason = String::from_utf8_lossy(line.trim_ascii()).to_string();
+    if reason.is_empty() || Some(reason.as_str()) == canonical_reason {
+        Ok(None)
+    } else {
+        Ok(Some(reason))
+    }
+}
+
+/// Returns true if the status code is in the range 200-299.
+pub fn is_success(status_code: u16) -> bool {
+    (200..300).contains(&status_code)
+}
+
+/// Returns true if the status code is in the range 300-399.
+pub fn is_redirection(status_code: u16) -> bool {
+    (300..400).contains(&status_code)
+}
+
+/// Returns true if the status code is in the range 400-499.
+pub fn is_client_error(status_code: u16) -> bool {
+    (400..500).contains(&status_code)
+}
+
+/// Returns true if the status code is in the range 500-599.
+pub fn is_server_error(status_code: u16) -> bool {
+    (500..600).contains(&status_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case::empty(b"\r\n", None)]
+    #[case::canonical(b" OK\r\n", None)]
+    #[case::custom(b" Fine\r\n", Some("Fine"))]
+    #[case::trimmed(b"  Fine  \r\n", Some("Fine"))]
+    fn test_parse_reason_phrase(#[case] input: &[u8], #[case] expected: Option<&str>) {
+        let mut cursor = Cursor::new(input);
+        let reason = parse_reason_phrase(&mut cursor, 200, Some("OK")).unwrap();
+        assert_eq!(reason.as_deref(), expected);
+    }
+
+    #[rstest]
+    #[case::no_space(b"0 OK\r\n")]
+    #[case::no_newline(b" OK")]
+    fn test_parse_reason_phrase_error(#[case] input: &[u8]) {
+        let mut cursor = Cursor::new(input);
+        assert!(parse_reason_phrase(&mut cursor, 200, Some("OK")).is_err());
+    }
+
+    #[test]
+    fn test_status_class() {
+        assert!(is_success(204));
+        assert!(is_redirection(310));
+        assert!(is_client_error(400));
+        assert!(is_server_error(599));
+        assert!(!is_success(300));
+        assert!(!is_server_error(600));
+    }
+}